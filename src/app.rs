@@ -3,36 +3,100 @@
 //! date: 2025/12/14 23:45:00 Sunday
 //! brief:
 
+use std::{cell::Cell, rc::Rc};
+
 use gpui::{
-    App, AppContext, Application, Bounds, Focusable, KeyBinding, Timer, WindowBounds,
-    WindowOptions, px, size,
+    App, AppContext, Application, Bounds, Focusable, KeyBinding, Timer, TitlebarOptions,
+    WindowBounds, WindowHandle, WindowOptions, px, size,
 };
 
 use crate::game::{
-    MoveDown, MoveLeft, MoveRight, MoveUp, QuitGame, RestartGame, SnakeGame, TogglePause,
+    ActivateSlot1, ActivateSlot2, BotInput, BotLink, CancelQuit, CycleLayout, CyclePace,
+    CycleSpeedCurve, CycleTheme, DismissResume, ExportPosition, FRAME, ForceQuit, ImportPosition,
+    MainMenu, MoveDown, MoveLeft, MoveRight, MoveUp, OpenSpectator, PracticeFaster,
+    PracticePosition, PracticeSlower, QuitGame, RequestQuit, RestartGame, ResumeRun, SaveRun,
+    ShowDaily, ShowDrills, ShowLobby, ShowProfile, SnakeGame, Spectator, StartDaily, StartTutorial,
+    StepTick, TickPacer, ToggleAdaptive, ToggleBoardSize, ToggleEditor, ToggleFreeze,
+    ToggleGravity, ToggleMirror, TogglePause, TogglePracticeMode, ToggleSelfCut, ToggleSpeedrun,
+    ToggleWallImmunity, ToggleWeather, TuneSpeedCurve, TurnLeft, TurnRight, WatchReplay,
+    WindowPlacement,
 };
 
-pub fn run() {
-    Application::new().run(|cx: &mut App| {
+/// Starts the game, with the spectator window open too when `spectator` is
+/// set and the live run handed to an external bot when `bot` is.
+pub fn run(spectator: bool, bot: Option<BotInput>) {
+    Application::new().run(move |cx: &mut App| {
         cx.bind_keys([
             KeyBinding::new("up", MoveUp, None),
             KeyBinding::new("down", MoveDown, None),
             KeyBinding::new("left", MoveLeft, None),
             KeyBinding::new("right", MoveRight, None),
-            KeyBinding::new("w", MoveUp, None),
-            KeyBinding::new("s", MoveDown, None),
-            KeyBinding::new("a", MoveLeft, None),
-            KeyBinding::new("d", MoveRight, None),
+            KeyBinding::new("w", MoveUp, Some("controls == standard")),
+            KeyBinding::new("s", MoveDown, Some("controls == standard")),
+            KeyBinding::new("a", MoveLeft, Some("controls == standard")),
+            KeyBinding::new("d", MoveRight, Some("controls == standard")),
+            KeyBinding::new("i", MoveUp, Some("controls == left_handed")),
+            KeyBinding::new("k", MoveDown, Some("controls == left_handed")),
+            KeyBinding::new("j", MoveLeft, Some("controls == left_handed")),
+            KeyBinding::new("l", MoveRight, Some("controls == left_handed")),
+            KeyBinding::new("left", TurnLeft, Some("controls == relative")),
+            KeyBinding::new("right", TurnRight, Some("controls == relative")),
+            KeyBinding::new("a", TurnLeft, Some("controls == relative")),
+            KeyBinding::new("d", TurnRight, Some("controls == relative")),
             KeyBinding::new("space", TogglePause, None),
             KeyBinding::new("enter", RestartGame, None),
-            KeyBinding::new("escape", QuitGame, None),
+            KeyBinding::new("r", WatchReplay, None),
+            KeyBinding::new("m", MainMenu, None),
+            KeyBinding::new("tab", CycleLayout, None),
+            KeyBinding::new("e", ToggleWeather, None),
+            KeyBinding::new("g", ToggleSpeedrun, None),
+            KeyBinding::new("x", ToggleSelfCut, None),
+            KeyBinding::new("q", ToggleGravity, None),
+            KeyBinding::new("shift-m", ToggleMirror, None),
+            KeyBinding::new("shift-a", ToggleAdaptive, None),
+            KeyBinding::new("shift-t", CycleTheme, None),
+            KeyBinding::new("shift-e", ToggleEditor, None),
+            KeyBinding::new("b", ToggleBoardSize, None),
+            KeyBinding::new("f", CyclePace, None),
+            KeyBinding::new("z", CycleSpeedCurve, None),
+            KeyBinding::new("shift-z", TuneSpeedCurve, None),
+            KeyBinding::new("shift-p", TogglePracticeMode, None),
+            KeyBinding::new("=", PracticeFaster, None),
+            KeyBinding::new("-", PracticeSlower, None),
+            KeyBinding::new("/", ToggleFreeze, None),
+            KeyBinding::new(".", StepTick, None),
+            KeyBinding::new("shift-w", ToggleWallImmunity, None),
+            KeyBinding::new("ctrl-c", ExportPosition, None),
+            KeyBinding::new("ctrl-v", ImportPosition, None),
+            KeyBinding::new("h", ShowDaily, None),
+            KeyBinding::new("o", ShowProfile, None),
+            KeyBinding::new("shift-d", ShowDrills, None),
+            KeyBinding::new("shift-s", OpenSpectator, None),
+            KeyBinding::new("v", ShowLobby, None),
+            KeyBinding::new("u", StartTutorial, None),
+            KeyBinding::new("t", StartDaily, None),
+            KeyBinding::new("p", PracticePosition, None),
+            KeyBinding::new("ctrl-s", SaveRun, None),
+            KeyBinding::new("c", ResumeRun, None),
+            KeyBinding::new("1", ActivateSlot1, None),
+            KeyBinding::new("2", ActivateSlot2, None),
+            KeyBinding::new("escape", RequestQuit, None),
+            KeyBinding::new("y", QuitGame, Some("quit_prompt")),
+            KeyBinding::new("n", CancelQuit, Some("quit_prompt")),
+            KeyBinding::new("escape", CancelQuit, Some("quit_prompt")),
+            KeyBinding::new("y", ResumeRun, Some("resume_prompt")),
+            KeyBinding::new("n", DismissResume, Some("resume_prompt")),
+            KeyBinding::new("escape", DismissResume, Some("resume_prompt")),
+            KeyBinding::new("ctrl-q", ForceQuit, None),
         ]);
 
-        let bounds = Bounds::centered(None, size(px(880.), px(720.)), cx);
+        let placement = WindowPlacement::load();
+        let bounds =
+            WindowPlacement::initial_bounds(placement.as_ref(), size(px(880.), px(720.)), cx);
         let window = cx
             .open_window(
                 WindowOptions {
-                    window_bounds: Some(WindowBounds::Windowed(bounds)),
+                    window_bounds: Some(bounds),
                     ..Default::default()
                 },
                 |_, cx| cx.new(SnakeGame::new),
@@ -42,33 +106,111 @@ pub fn run() {
         let game = window
             .update(cx, |view: &mut SnakeGame, window, cx| {
                 window.focus(&view.focus_handle(cx));
+                cx.observe_window_activation(window, |game, window, cx| {
+                    game.set_window_active(window.is_window_active(), cx)
+                })
+                .detach();
+                view.note_window_bounds(window, cx);
+                cx.observe_window_bounds(window, |game, window, cx| {
+                    game.note_window_bounds(window, cx)
+                })
+                .detach();
                 cx.activate(true);
                 cx.entity()
             })
             .unwrap();
 
+        if let Some(input) = bot {
+            game.update(cx, |game, _| game.attach_bot(BotLink::open(input)));
+        }
         spawn_game_loop(game.clone(), cx);
+        spawn_frame_loop(game.clone(), cx);
+        let spectator_window = Rc::new(Cell::new(
+            spectator
+                .then(|| open_spectator(game.clone(), cx))
+                .flatten(),
+        ));
+        cx.on_action({
+            let game = game.clone();
+            move |_: &OpenSpectator, cx| {
+                // An open spectator window is brought forward rather than doubled.
+                let shown = spectator_window.get().is_some_and(|handle| {
+                    handle
+                        .update(cx, |_, window, _| window.activate_window())
+                        .is_ok()
+                });
+                if !shown {
+                    spectator_window.set(open_spectator(game.clone(), cx));
+                }
+            }
+        });
+        // The versus lobby's address field takes digits before key bindings do.
+        cx.intercept_keystrokes(move |event, _, cx| {
+            if game.update(cx, |game, cx| game.type_address(&event.keystroke, cx)) {
+                cx.stop_propagation();
+            }
+        })
+        .detach();
         cx.on_action(|_: &QuitGame, cx| cx.quit());
+        cx.on_action(|_: &ForceQuit, cx| cx.quit());
         cx.activate(true);
     });
 }
 
+/// Opens the read-only spectator window without taking focus from the game.
+fn open_spectator(game: gpui::Entity<SnakeGame>, cx: &mut App) -> Option<WindowHandle<Spectator>> {
+    let bounds = Bounds::centered(None, size(px(960.), px(720.)), cx);
+    cx.open_window(
+        WindowOptions {
+            window_bounds: Some(WindowBounds::Windowed(bounds)),
+            titlebar: Some(TitlebarOptions {
+                title: Some("Snake — Spectator".into()),
+                ..Default::default()
+            }),
+            focus: false,
+            ..Default::default()
+        },
+        |_, cx| cx.new(|cx| Spectator::new(game, cx)),
+    )
+    .inspect_err(|err| tracing::warn!("Failed to open the spectator window: {err}"))
+    .ok()
+}
+
 fn spawn_game_loop(game: gpui::Entity<SnakeGame>, cx: &mut App) {
     cx.spawn({
-        async move |cx| loop {
-            let delay = match game.read_with(cx, |game, _| game.tick_delay()) {
-                Ok(duration) => duration,
-                Err(_) => break,
-            };
+        async move |cx| {
+            let mut pacer = TickPacer::new();
+            loop {
+                let delay = match game.read_with(cx, |game, _| game.tick_delay()) {
+                    Ok(duration) => duration,
+                    Err(_) => break,
+                };
 
-            Timer::after(delay).await;
-            if game
-                .update(cx, |game, cx| {
-                    game.tick(cx);
-                })
-                .is_err()
-            {
-                break;
+                Timer::after(pacer.begin(delay)).await;
+                pacer.finish();
+                if game
+                    .update(cx, |game, cx| {
+                        game.tick(cx);
+                    })
+                    .is_err()
+                {
+                    break;
+                }
+            }
+        }
+    })
+    .detach();
+}
+
+/// Drives screen effects at frame rate, independent of the game tick.
+fn spawn_frame_loop(game: gpui::Entity<SnakeGame>, cx: &mut App) {
+    cx.spawn({
+        async move |cx| {
+            loop {
+                Timer::after(FRAME).await;
+                if game.update(cx, |game, cx| game.animate_frame(cx)).is_err() {
+                    break;
+                }
             }
         }
     })
@@ -0,0 +1,100 @@
+//! file: simulate.rs
+//! author: Jacob Xie
+//! date: 2026/10/16 17:42:49 Friday
+//! brief:
+//!
+//! Plays games headlessly and reports how they went and how fast the engine
+//! ran them, as a baseline for engine changes. Build with `--release` for
+//! meaningful throughput; debug builds cross-check occupancy every tick.
+//!
+//!     cargo run --release --bin simulate -- --games 200 --policy greedy --seed 1
+
+use std::{process::ExitCode, time::Duration};
+
+use gpui_snake::{BoardLayout, GameConfig, Policy, simulate};
+
+const DEFAULT_GAMES: u64 = 100;
+const DEFAULT_MAX_TICKS: u64 = 20_000;
+
+struct Args {
+    games: u64,
+    policy: Policy,
+    seed: u64,
+    max_ticks: u64,
+}
+
+impl Args {
+    fn parse() -> Result<Self, String> {
+        let mut args = Args {
+            games: DEFAULT_GAMES,
+            policy: Policy::Autopilot,
+            seed: 0,
+            max_ticks: DEFAULT_MAX_TICKS,
+        };
+        let mut rest = std::env::args().skip(1);
+        while let Some(flag) = rest.next() {
+            let value = rest.next().ok_or_else(|| format!("{flag} needs a value"))?;
+            let number = || {
+                value
+                    .parse::<u64>()
+                    .map_err(|err| format!("{flag} {value}: {err}"))
+            };
+            match flag.as_str() {
+                "--games" => args.games = number()?,
+                "--seed" => args.seed = number()?,
+                "--max-ticks" => args.max_ticks = number()?,
+                "--policy" => {
+                    args.policy = Policy::from_name(&value).ok_or_else(|| {
+                        let names: Vec<&str> = Policy::ALL.iter().map(|p| p.name()).collect();
+                        format!(
+                            "unknown policy {value}, expected one of {}",
+                            names.join(", ")
+                        )
+                    })?;
+                }
+                other => return Err(format!("unknown flag {other}")),
+            }
+        }
+        Ok(args)
+    }
+}
+
+fn main() -> ExitCode {
+    let args = match Args::parse() {
+        Ok(args) => args,
+        Err(err) => {
+            eprintln!("simulate: {err}");
+            eprintln!(
+                "usage: simulate [--games N] [--policy autopilot|greedy|random] [--seed S] [--max-ticks T]"
+            );
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let config = GameConfig::new(BoardLayout::Open);
+    let (mut score, mut length, mut ticks, mut busy) = (0u64, 0u64, 0u64, Duration::ZERO);
+    for game in 0..args.games {
+        let result = simulate(
+            &config,
+            args.policy,
+            args.seed.wrapping_add(game),
+            args.max_ticks,
+        );
+        score += u64::from(result.score);
+        length += result.length as u64;
+        ticks += result.ticks;
+        busy += result.busy;
+    }
+
+    let games = args.games.max(1) as f64;
+    println!("policy:        {}", args.policy.name());
+    println!("games:         {}", args.games);
+    println!("avg score:     {:.2}", score as f64 / games);
+    println!("avg length:    {:.2}", length as f64 / games);
+    println!("avg ticks:     {:.1}", ticks as f64 / games);
+    println!(
+        "ticks/sec:     {:.0}",
+        ticks as f64 / busy.as_secs_f64().max(f64::EPSILON)
+    );
+    ExitCode::SUCCESS
+}
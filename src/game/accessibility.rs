@@ -0,0 +1,61 @@
+//! file: accessibility.rs
+//! author: Jacob Xie
+//! date: 2026/10/16 14:46:08 Friday
+//! brief:
+
+use std::io;
+
+use serde::{Deserialize, Serialize};
+
+use super::{Palette, storage};
+
+const SETTINGS_FILE: &str = "accessibility.json";
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub struct Accessibility {
+    pub palette: Palette,
+    /// Tells cells apart by shape as well as color: round food, glyphs on
+    /// special food, segments and pickups, and an eye on the head.
+    pub shapes: bool,
+    pub grid_lines: bool,
+    pub large_hud: bool,
+    /// Lists recent game events in plain language below the board.
+    #[serde(default)]
+    pub event_log: bool,
+    /// Shakes and flashes the board on death and big combos. Off for players
+    /// sensitive to motion.
+    #[serde(default = "screen_effects_default")]
+    pub screen_effects: bool,
+    /// Pings panned toward the food and a buzz before a crash, so the board
+    /// can be followed partly by ear.
+    #[serde(default)]
+    pub proximity_audio: bool,
+}
+
+fn screen_effects_default() -> bool {
+    true
+}
+
+impl Default for Accessibility {
+    fn default() -> Self {
+        Self {
+            palette: Palette::Standard,
+            shapes: false,
+            grid_lines: true,
+            large_hud: false,
+            event_log: false,
+            screen_effects: true,
+            proximity_audio: false,
+        }
+    }
+}
+
+impl Accessibility {
+    pub fn load() -> Self {
+        storage::read_json(&storage::data_path(SETTINGS_FILE)).unwrap_or_default()
+    }
+
+    pub fn store(&self) -> io::Result<()> {
+        storage::write_json(&storage::data_path(SETTINGS_FILE), self)
+    }
+}
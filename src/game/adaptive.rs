@@ -0,0 +1,157 @@
+//! file: adaptive.rs
+//! author: Jacob Xie
+//! date: 2026/10/16 18:05:12 Friday
+//! brief:
+
+use std::{collections::VecDeque, io};
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use super::{FoodKind, storage};
+
+const ADAPTIVE_FILE: &str = "adaptive.json";
+/// Finished runs the difficulty is judged on.
+const WINDOW: usize = 5;
+/// A run scoring below this died early and eases the next one.
+const EARLY_SCORE: u32 = 5;
+/// A run scoring at least this counts towards a streak and speeds things up.
+const STREAK_SCORE: u32 = 30;
+const MAX_LEVEL: i8 = 3;
+/// Tick length change per level, in percent.
+const TICK_STEP: i64 = 8;
+
+/// How far adaptive mode moves a run off the normal curve: below zero is
+/// slower with more bonus food, above zero faster with less.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Difficulty(i8);
+
+impl Difficulty {
+    pub fn level(self) -> i8 {
+        self.0
+    }
+
+    /// Stretches or shortens a tick. Faster levels may go below the curve's
+    /// floor, which is what makes a streak harder than the normal curve.
+    pub fn scale_tick(self, ms: u64) -> u64 {
+        (ms as i64 * (100 - TICK_STEP * self.0 as i64) / 100).max(1) as u64
+    }
+
+    /// Easier levels turn some plain food into bonus food; harder levels turn
+    /// some bonus food plain.
+    pub fn adjust_food(self, kind: FoodKind, rng: &mut impl Rng) -> FoodKind {
+        let odds = self.0.unsigned_abs() as u32;
+        match kind {
+            FoodKind::Plain if self.0 < 0 && rng.gen_range(0..10) < odds => {
+                if rng.gen_bool(0.5) {
+                    FoodKind::Glowing
+                } else {
+                    FoodKind::Armored
+                }
+            }
+            FoodKind::Glowing | FoodKind::Armored if self.0 > 0 && rng.gen_range(0..4) < odds => {
+                FoodKind::Plain
+            }
+            kind => kind,
+        }
+    }
+
+    pub fn label(self) -> String {
+        match self.0 {
+            0 => "Adaptive: normal".into(),
+            level if level < 0 => format!("Adaptive: easier {level} (slower, more bonus food)"),
+            level => format!("Adaptive: harder +{level} (faster, less bonus food)"),
+        }
+    }
+}
+
+/// Scores of the player's recent adaptive runs, and whether the mode is on.
+#[derive(Default, Serialize, Deserialize)]
+pub struct AdaptiveTracker {
+    pub enabled: bool,
+    recent: VecDeque<u32>,
+}
+
+impl AdaptiveTracker {
+    pub fn load() -> Self {
+        storage::read_json(&storage::data_path(ADAPTIVE_FILE)).unwrap_or_default()
+    }
+
+    pub fn store(&self) -> io::Result<()> {
+        storage::write_json(&storage::data_path(ADAPTIVE_FILE), self)
+    }
+
+    /// Adds a finished run, dropping the oldest once the window is full.
+    pub fn record(&mut self, score: u32) {
+        self.push(score);
+        if let Err(err) = self.store() {
+            tracing::warn!("Failed to save adaptive difficulty: {err}");
+        }
+    }
+
+    fn push(&mut self, score: u32) {
+        if self.recent.len() == WINDOW {
+            self.recent.pop_front();
+        }
+        self.recent.push_back(score);
+    }
+
+    /// Each early death in the window takes a level off, each streak run adds
+    /// one.
+    pub fn difficulty(&self) -> Difficulty {
+        let level: i32 = self
+            .recent
+            .iter()
+            .map(|score| match *score {
+                score if score < EARLY_SCORE => -1,
+                score if score >= STREAK_SCORE => 1,
+                _ => 0,
+            })
+            .sum();
+        Difficulty(level.clamp(-(MAX_LEVEL as i32), MAX_LEVEL as i32) as i8)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{SeedableRng, rngs::StdRng};
+
+    use super::*;
+
+    fn tracker(scores: &[u32]) -> AdaptiveTracker {
+        AdaptiveTracker {
+            enabled: true,
+            recent: scores.iter().copied().collect(),
+        }
+    }
+
+    #[test]
+    fn early_deaths_slow_and_streaks_speed_up() {
+        assert_eq!(tracker(&[]).difficulty(), Difficulty(0));
+        assert_eq!(tracker(&[12, 2]).difficulty(), Difficulty(-1));
+        assert_eq!(tracker(&[40, 35, 12]).difficulty(), Difficulty(2));
+        assert_eq!(tracker(&[40; 5]).difficulty(), Difficulty(MAX_LEVEL));
+        assert!(Difficulty(-1).scale_tick(100) > 100);
+        assert!(Difficulty(2).scale_tick(100) < 100);
+    }
+
+    #[test]
+    fn window_forgets_old_runs() {
+        let mut tracker = tracker(&[0, 0, 0, 20, 20]);
+        tracker.push(20);
+        assert_eq!(tracker.difficulty(), Difficulty(-2));
+    }
+
+    #[test]
+    fn easier_levels_hand_out_more_bonus_food() {
+        let bonus = |difficulty: Difficulty| {
+            let mut rng = StdRng::seed_from_u64(7);
+            (0..1000)
+                .map(|_| difficulty.adjust_food(FoodKind::roll(&mut rng), &mut rng))
+                .filter(|kind| matches!(kind, FoodKind::Glowing | FoodKind::Armored))
+                .count()
+        };
+        assert!(bonus(Difficulty(-3)) > bonus(Difficulty(0)));
+        assert!(bonus(Difficulty(3)) < bonus(Difficulty(0)));
+    }
+}
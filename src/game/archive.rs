@@ -0,0 +1,68 @@
+//! file: archive.rs
+//! author: Jacob Xie
+//! date: 2026/10/16 17:08:32 Friday
+//! brief:
+
+use std::{collections::VecDeque, io};
+
+use serde::{Deserialize, Serialize};
+
+use super::{Replay, RunSummary, storage};
+
+const ARCHIVE_FILE: &str = "replays.json";
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ArchivedReplay {
+    pub score: u32,
+    pub length: usize,
+    pub replay: Replay,
+}
+
+/// Replays of the latest finished runs, oldest first.
+#[derive(Default, Serialize, Deserialize)]
+pub struct ReplayArchive {
+    replays: VecDeque<ArchivedReplay>,
+}
+
+impl ReplayArchive {
+    pub fn load() -> Self {
+        storage::read_json(&storage::data_path(ARCHIVE_FILE)).unwrap_or_default()
+    }
+
+    fn store(&self) -> io::Result<()> {
+        storage::write_json(&storage::data_path(ARCHIVE_FILE), self)
+    }
+
+    pub fn replays(&self) -> impl DoubleEndedIterator<Item = &ArchivedReplay> + ExactSizeIterator {
+        self.replays.iter()
+    }
+
+    /// Keeps the run's replay, dropping the oldest ones past `keep`.
+    pub fn add(&mut self, summary: &RunSummary, keep: usize) {
+        self.replays.push_back(ArchivedReplay {
+            score: summary.score,
+            length: summary.length,
+            replay: summary.replay.clone(),
+        });
+        self.prune(keep);
+        if let Err(err) = self.store() {
+            tracing::warn!("Failed to save replays: {err}");
+        }
+    }
+
+    /// Drops the oldest replays past `keep`, returning whether any went.
+    pub fn prune(&mut self, keep: usize) -> bool {
+        let excess = self.replays.len().saturating_sub(keep);
+        self.replays.drain(..excess);
+        excess > 0
+    }
+
+    /// Prunes to `keep` and saves if that dropped anything.
+    pub fn retain(&mut self, keep: usize) {
+        if self.prune(keep)
+            && let Err(err) = self.store()
+        {
+            tracing::warn!("Failed to save replays: {err}");
+        }
+    }
+}
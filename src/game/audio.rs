@@ -0,0 +1,169 @@
+//! file: audio.rs
+//! author: Jacob Xie
+//! date: 2026/10/16 18:17:49 Friday
+//! brief:
+
+use super::DANGER_MOVES;
+
+/// Output rate of the synthesized cues.
+const SAMPLE_RATE: u32 = 44_100;
+const VOLUME: f32 = 0.25;
+
+/// A short sound played on a game event. Cues are synthesized rather than
+/// loaded, so there are no sound files to ship.
+#[derive(Clone, Copy)]
+pub enum Cue {
+    /// A rising sweep for the speed moving up a tier.
+    Riser,
+    /// A sonar ping for the food, higher the nearer it is (`closeness` from
+    /// 0.0 to 1.0) and panned toward it.
+    FoodPing { closeness: f32, pan: f32 },
+    /// A low buzz for a crash `moves` ahead, lower and longer as it nears.
+    Danger { moves: u32 },
+}
+
+impl Cue {
+    /// Mono samples at `SAMPLE_RATE`.
+    #[cfg_attr(not(feature = "audio"), allow(dead_code))]
+    fn samples(self) -> Vec<f32> {
+        match self {
+            Cue::Riser => sweep(330.0, 990.0, 0.3),
+            Cue::FoodPing { closeness, .. } => {
+                let frequency = 440.0 * 3.0f32.powf(closeness.clamp(0.0, 1.0));
+                sweep(frequency, frequency, 0.05)
+            }
+            Cue::Danger { moves } => {
+                let nearness = (DANGER_MOVES + 1).saturating_sub(moves) as f32;
+                sweep(
+                    260.0 - 40.0 * nearness,
+                    200.0 - 40.0 * nearness,
+                    0.04 * nearness,
+                )
+            }
+        }
+    }
+
+    /// Where the cue sits between the speakers: -1.0 left to 1.0 right.
+    #[cfg_attr(not(feature = "audio"), allow(dead_code))]
+    fn pan(self) -> f32 {
+        match self {
+            Cue::FoodPing { pan, .. } => pan.clamp(-1.0, 1.0),
+            Cue::Riser | Cue::Danger { .. } => 0.0,
+        }
+    }
+}
+
+/// Interleaved left-right samples from mono ones, panned with equal power so
+/// a cue is as loud at the side as in the middle.
+#[cfg_attr(not(feature = "audio"), allow(dead_code))]
+fn stereo(samples: &[f32], pan: f32) -> Vec<f32> {
+    let angle = (pan + 1.0) * std::f32::consts::FRAC_PI_4;
+    let (left, right) = (angle.cos(), angle.sin());
+    samples
+        .iter()
+        .flat_map(|sample| [sample * left, sample * right])
+        .collect()
+}
+
+/// A sine sweeping from `from` to `to` Hz over `secs`, with a quick fade in
+/// and a fade out to the end.
+#[cfg_attr(not(feature = "audio"), allow(dead_code))]
+fn sweep(from: f32, to: f32, secs: f32) -> Vec<f32> {
+    let count = (SAMPLE_RATE as f32 * secs) as usize;
+    let mut phase = 0.0f32;
+    (0..count)
+        .map(|index| {
+            let t = index as f32 / count as f32;
+            let frequency = from * (to / from).powf(t);
+            phase = (phase + std::f32::consts::TAU * frequency / SAMPLE_RATE as f32)
+                % std::f32::consts::TAU;
+            let envelope = (t * 20.0).min(1.0) * (1.0 - t);
+            phase.sin() * envelope * VOLUME
+        })
+        .collect()
+}
+
+/// The sound output. Silent when built without the `audio` feature or when
+/// there is no output device.
+pub struct Audio {
+    #[cfg(feature = "audio")]
+    stream: Option<rodio::OutputStream>,
+}
+
+impl Audio {
+    pub fn open() -> Self {
+        #[cfg(feature = "audio")]
+        {
+            let stream = rodio::OutputStreamBuilder::open_default_stream()
+                .inspect_err(|err| tracing::warn!("No audio output: {err}"))
+                .ok()
+                .map(|mut stream| {
+                    stream.log_on_drop(false);
+                    stream
+                });
+            Self { stream }
+        }
+        #[cfg(not(feature = "audio"))]
+        Self {}
+    }
+
+    pub fn play(&self, cue: Cue) {
+        #[cfg(feature = "audio")]
+        if let Some(stream) = &self.stream {
+            stream.mixer().add(rodio::buffer::SamplesBuffer::new(
+                2,
+                SAMPLE_RATE,
+                stereo(&cue.samples(), cue.pan()),
+            ));
+        }
+        #[cfg(not(feature = "audio"))]
+        let _ = cue;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_riser_climbs_and_stays_quiet_at_the_ends() {
+        let samples = Cue::Riser.samples();
+        assert_eq!(samples.len(), (SAMPLE_RATE as f32 * 0.3) as usize);
+        assert!(samples.iter().all(|sample| sample.abs() <= VOLUME));
+        assert!(samples[0].abs() < 0.01 && samples[samples.len() - 1].abs() < 0.01);
+        // More zero crossings in the second half means a higher pitch.
+        let crossings = |half: &[f32]| {
+            half.windows(2)
+                .filter(|pair| pair[0].signum() != pair[1].signum())
+                .count()
+        };
+        let (first, second) = samples.split_at(samples.len() / 2);
+        assert!(crossings(second) > crossings(first));
+    }
+
+    #[test]
+    fn food_pings_pan_toward_the_food() {
+        let cue = Cue::FoodPing {
+            closeness: 0.5,
+            pan: 1.0,
+        };
+        let frames = stereo(&cue.samples(), cue.pan());
+        let (left, right): (Vec<f32>, Vec<f32>) =
+            frames.chunks(2).map(|frame| (frame[0], frame[1])).unzip();
+        assert!(left.iter().all(|sample| sample.abs() < 1e-6));
+        assert!(right.iter().any(|sample| sample.abs() > 0.1));
+
+        let centered = stereo(&[1.0], 0.0);
+        assert!((centered[0] - centered[1]).abs() < 1e-6);
+    }
+
+    #[test]
+    fn danger_grows_as_it_nears() {
+        let far = Cue::Danger {
+            moves: DANGER_MOVES,
+        }
+        .samples();
+        let near = Cue::Danger { moves: 1 }.samples();
+        assert!(near.len() > far.len());
+    }
+}
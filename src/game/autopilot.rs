@@ -0,0 +1,67 @@
+//! file: autopilot.rs
+//! author: Jacob Xie
+//! date: 2026/10/16 14:37:53 Friday
+//! brief:
+
+use std::collections::{HashSet, VecDeque};
+
+use super::{Cell, Direction, Engine};
+
+/// Picks the next turn for a computer-driven snake: the first step of the
+/// shortest path to the food, or, when the food is unreachable, the safe move
+/// that leaves the most room to wander.
+pub fn plan(engine: &Engine) -> Option<Direction> {
+    let head = engine.snake().front().copied()?;
+    let moves: Vec<(Direction, Cell)> = safe_moves(engine, head, engine.direction()).collect();
+
+    let mut visited: HashSet<Cell> = moves.iter().map(|&(_, cell)| cell).collect();
+    let mut queue: VecDeque<(Cell, Direction, Direction)> = moves
+        .iter()
+        .map(|&(direction, cell)| (cell, direction, direction))
+        .collect();
+    while let Some((cell, first, heading)) = queue.pop_front() {
+        if cell == engine.food() {
+            return Some(first);
+        }
+        for (direction, next) in safe_moves(engine, cell, heading) {
+            if visited.insert(next) {
+                queue.push_back((next, first, direction));
+            }
+        }
+    }
+
+    moves
+        .into_iter()
+        .max_by_key(|&(direction, cell)| reachable_area(engine, cell, direction))
+        .map(|(direction, _)| direction)
+}
+
+fn safe_moves(
+    engine: &Engine,
+    from: Cell,
+    heading: Direction,
+) -> impl Iterator<Item = (Direction, Cell)> + '_ {
+    Direction::ALL
+        .into_iter()
+        .filter(move |direction| !direction.is_opposite(heading))
+        .filter_map(move |direction| {
+            engine
+                .resolve_step(from, direction)
+                .ok()
+                .filter(|cell| !engine.is_occupied(*cell))
+                .map(|cell| (direction, cell))
+        })
+}
+
+fn reachable_area(engine: &Engine, start: Cell, heading: Direction) -> usize {
+    let mut visited = HashSet::from([start]);
+    let mut queue = VecDeque::from([(start, heading)]);
+    while let Some((cell, heading)) = queue.pop_front() {
+        for (direction, next) in safe_moves(engine, cell, heading) {
+            if visited.insert(next) {
+                queue.push_back((next, direction));
+            }
+        }
+    }
+    visited.len()
+}
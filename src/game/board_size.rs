@@ -0,0 +1,88 @@
+//! file: board_size.rs
+//! author: Jacob Xie
+//! date: 2026/10/16 18:43:54 Friday
+//! brief:
+
+use super::{
+    Engine, GIANT_HEIGHT, GIANT_WIDTH, GRID_HEIGHT, GRID_WIDTH, LARGE_HEIGHT, LARGE_WIDTH,
+};
+
+/// The free-play board sizes B cycles through.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BoardSize {
+    #[default]
+    Normal,
+    /// Scrolled through the camera.
+    Large,
+    /// Shown whole, painted onto one canvas; doubles as a renderer stress test.
+    Giant,
+}
+
+impl BoardSize {
+    pub fn next(self) -> Self {
+        match self {
+            BoardSize::Normal => BoardSize::Large,
+            BoardSize::Large => BoardSize::Giant,
+            BoardSize::Giant => BoardSize::Normal,
+        }
+    }
+
+    pub fn dimensions(self) -> (i32, i32) {
+        match self {
+            BoardSize::Normal => (GRID_WIDTH, GRID_HEIGHT),
+            BoardSize::Large => (LARGE_WIDTH, LARGE_HEIGHT),
+            BoardSize::Giant => (GIANT_WIDTH, GIANT_HEIGHT),
+        }
+    }
+
+    pub fn label(self) -> String {
+        let (width, height) = self.dimensions();
+        match self {
+            BoardSize::Normal => "Normal".into(),
+            BoardSize::Large => format!("Large {width}x{height}"),
+            BoardSize::Giant => format!("Giant {width}x{height} (stress)"),
+        }
+    }
+
+    /// Whether `engine` is on a giant board, which is painted rather than
+    /// built from thousands of cell elements.
+    pub fn is_giant(engine: &Engine) -> bool {
+        (engine.board_width(), engine.board_height()) == BoardSize::Giant.dimensions()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{BoardLayout, GameConfig};
+
+    #[test]
+    fn sizes_cycle_back_to_normal() {
+        let mut size = BoardSize::default();
+        let mut seen = Vec::new();
+        for _ in 0..3 {
+            seen.push(size.dimensions());
+            size = size.next();
+        }
+        assert_eq!(size, BoardSize::Normal);
+        assert_eq!(seen[2], (GIANT_WIDTH, GIANT_HEIGHT));
+    }
+
+    #[test]
+    fn giant_boards_run_on_the_occupancy_grid() {
+        let mut config = GameConfig::new(BoardLayout::Open);
+        let (width, height) = BoardSize::Giant.dimensions();
+        config.resize(width, height);
+        let mut engine = Engine::new(&config, 11);
+        assert!(BoardSize::is_giant(&engine));
+        engine.start();
+        for _ in 0..GIANT_WIDTH {
+            engine.tick();
+        }
+        assert!(engine.death().is_some());
+        assert!(!BoardSize::is_giant(&Engine::new(
+            &GameConfig::new(BoardLayout::Open),
+            11
+        )));
+    }
+}
@@ -0,0 +1,198 @@
+//! file: bot.rs
+//! author: Jacob Xie
+//! date: 2026/10/16 18:25:47 Friday
+//! brief:
+
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader, Write},
+    path::PathBuf,
+    sync::mpsc::{self, Receiver, Sender},
+    thread,
+};
+
+use serde::Serialize;
+
+use super::{Cell, DeathCause, Direction, Engine, FoodKind, GameStatus, Tile};
+
+/// Where an external bot's moves come from. Game state always goes to
+/// stdout, one JSON object per line.
+pub enum BotInput {
+    Stdin,
+    /// A named pipe, so the bot can read stdout and write moves back without
+    /// a two-way pipe: `mkfifo moves; gpui-snake --bot-pipe moves | bot > moves`.
+    Pipe(PathBuf),
+}
+
+/// One line from the bot: `up`, `down`, `left`, `right`, `start` or
+/// `pause`. Case and surrounding spaces are ignored.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BotCommand {
+    Turn(Direction),
+    /// Starts a waiting run, or a new one once the last is over.
+    Start,
+    Pause,
+}
+
+impl BotCommand {
+    pub fn parse(line: &str) -> Option<Self> {
+        match line.trim().to_ascii_lowercase().as_str() {
+            "up" => Some(BotCommand::Turn(Direction::Up)),
+            "down" => Some(BotCommand::Turn(Direction::Down)),
+            "left" => Some(BotCommand::Turn(Direction::Left)),
+            "right" => Some(BotCommand::Turn(Direction::Right)),
+            "start" => Some(BotCommand::Start),
+            "pause" => Some(BotCommand::Pause),
+            _ => None,
+        }
+    }
+}
+
+/// What the bot is told about the board each tick.
+#[derive(Serialize)]
+struct BotState {
+    tick: u64,
+    status: GameStatus,
+    score: u32,
+    width: i32,
+    height: i32,
+    direction: Direction,
+    /// Head first.
+    snake: Vec<Cell>,
+    food: Cell,
+    food_kind: FoodKind,
+    /// Every cell that is not empty floor.
+    tiles: Vec<(Cell, Tile)>,
+    death: Option<DeathCause>,
+}
+
+impl BotState {
+    fn capture(engine: &Engine) -> Self {
+        Self {
+            tick: engine.ticks(),
+            status: engine.state(),
+            score: engine.score(),
+            width: engine.board_width(),
+            height: engine.board_height(),
+            direction: engine.direction(),
+            snake: engine.snake().iter().copied().collect(),
+            food: engine.food(),
+            food_kind: engine.food_kind(),
+            tiles: engine
+                .tiles()
+                .cells()
+                .filter(|(_, tile)| *tile != Tile::Empty)
+                .collect(),
+            death: engine.death(),
+        }
+    }
+}
+
+/// An external bot playing the live run. Reading moves and writing state
+/// each happen on their own thread, so a slow bot never stalls the window.
+pub struct BotLink {
+    commands: Receiver<BotCommand>,
+    states: Sender<String>,
+    /// Tick and status last sent, so an unchanged board is not sent twice.
+    sent: Option<(u64, GameStatus)>,
+}
+
+impl BotLink {
+    pub fn open(input: BotInput) -> Self {
+        let (command_tx, commands) = mpsc::channel();
+        let (states, state_rx) = mpsc::channel::<String>();
+        thread::spawn(move || {
+            // Opening a named pipe blocks until the bot opens the other end.
+            let reader: Box<dyn BufRead> = match input {
+                BotInput::Stdin => Box::new(io::stdin().lock()),
+                BotInput::Pipe(path) => match File::open(&path) {
+                    Ok(file) => Box::new(BufReader::new(file)),
+                    Err(err) => {
+                        tracing::warn!("Failed to open bot pipe {}: {err}", path.display());
+                        return;
+                    }
+                },
+            };
+            for line in reader.lines() {
+                let Ok(line) = line else { break };
+                match BotCommand::parse(&line) {
+                    Some(command) => {
+                        if command_tx.send(command).is_err() {
+                            break;
+                        }
+                    }
+                    None if line.trim().is_empty() => {}
+                    None => tracing::warn!("Ignored bot command: {line}"),
+                }
+            }
+            tracing::info!("Bot input closed");
+        });
+        thread::spawn(move || {
+            let mut stdout = io::stdout().lock();
+            for line in state_rx {
+                if writeln!(stdout, "{line}")
+                    .and_then(|_| stdout.flush())
+                    .is_err()
+                {
+                    tracing::warn!("Bot output closed");
+                    break;
+                }
+            }
+        });
+        tracing::info!("Bot attached");
+        Self {
+            commands,
+            states,
+            sent: None,
+        }
+    }
+
+    /// Commands the bot has sent since the last call.
+    pub fn drain(&self) -> Vec<BotCommand> {
+        self.commands.try_iter().collect()
+    }
+
+    /// Sends the board if it changed since the last line.
+    pub fn publish(&mut self, engine: &Engine) {
+        let key = (engine.ticks(), engine.state());
+        if self.sent == Some(key) {
+            return;
+        }
+        self.sent = Some(key);
+        match serde_json::to_string(&BotState::capture(engine)) {
+            Ok(line) => {
+                self.states.send(line).ok();
+            }
+            Err(err) => tracing::warn!("Failed to encode bot state: {err}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{BoardLayout, GameConfig};
+
+    #[test]
+    fn commands_parse_loosely() {
+        assert_eq!(
+            BotCommand::parse("  Left\r"),
+            Some(BotCommand::Turn(Direction::Left))
+        );
+        assert_eq!(BotCommand::parse("START"), Some(BotCommand::Start));
+        assert_eq!(BotCommand::parse("jump"), None);
+    }
+
+    #[test]
+    fn state_lines_carry_the_board() {
+        let engine = Engine::new(&GameConfig::new(BoardLayout::Open), 7);
+        let line = serde_json::to_value(BotState::capture(&engine)).unwrap();
+        assert_eq!(line["status"], "Ready");
+        assert_eq!(line["width"], engine.board_width());
+        assert_eq!(
+            line["snake"].as_array().unwrap().len(),
+            engine.snake().len()
+        );
+        assert_eq!(line["food"]["x"], engine.food().x);
+    }
+}
@@ -0,0 +1,143 @@
+//! file: camera.rs
+//! author: Jacob Xie
+//! date: 2026/10/16 17:13:05 Friday
+//! brief:
+
+use std::ops::Range;
+
+use super::{BoardSize, Cell, Engine, GRID_HEIGHT, GRID_WIDTH};
+
+/// Most cells shown across and down; larger boards scroll.
+pub const VIEW_WIDTH: i32 = GRID_WIDTH;
+pub const VIEW_HEIGHT: i32 = GRID_HEIGHT;
+/// Share of the remaining distance the camera covers each frame.
+const SMOOTHING: f32 = 0.2;
+/// Closer than this, in cells, the camera settles on its target.
+const SETTLE: f32 = 0.01;
+
+/// The part of a board larger than the view that is on screen. Its position
+/// is the top-left corner in cells and eases toward keeping the head centred.
+#[derive(Clone, Copy, Default)]
+pub struct Camera {
+    x: f32,
+    y: f32,
+}
+
+impl Camera {
+    /// Whether the whole board fits the view, so nothing scrolls. A giant
+    /// board is shrunk to fit rather than scrolled.
+    pub fn fits(engine: &Engine) -> bool {
+        (engine.board_width() <= VIEW_WIDTH && engine.board_height() <= VIEW_HEIGHT)
+            || BoardSize::is_giant(engine)
+    }
+
+    /// Where the camera heads: the head centred, kept inside the board.
+    fn target(engine: &Engine) -> (f32, f32) {
+        if Self::fits(engine) {
+            return (0.0, 0.0);
+        }
+        let head = engine
+            .snake()
+            .front()
+            .copied()
+            .unwrap_or(Cell { x: 0, y: 0 });
+        let axis = |head: i32, view: i32, board: i32| {
+            (head - view / 2).clamp(0, (board - view).max(0)) as f32
+        };
+        (
+            axis(head.x, VIEW_WIDTH, engine.board_width()),
+            axis(head.y, VIEW_HEIGHT, engine.board_height()),
+        )
+    }
+
+    /// Eases one frame toward the target, returning whether the camera moved.
+    /// A jump of more than half the view, through a portal or across a
+    /// wrapping edge, is taken at once rather than panned across the board.
+    pub fn follow(&mut self, engine: &Engine) -> bool {
+        let (x, y) = Self::target(engine);
+        let (dx, dy) = (x - self.x, y - self.y);
+        if dx == 0.0 && dy == 0.0 {
+            return false;
+        }
+        let jumped = dx.abs() > VIEW_WIDTH as f32 / 2.0 || dy.abs() > VIEW_HEIGHT as f32 / 2.0;
+        if jumped || dx.abs().max(dy.abs()) < SETTLE {
+            (self.x, self.y) = (x, y);
+        } else {
+            self.x += dx * SMOOTHING;
+            self.y += dy * SMOOTHING;
+        }
+        true
+    }
+
+    /// Columns and rows to draw: the cells under the view plus the partly
+    /// scrolled-in ones at its far edges.
+    pub fn visible(&self, engine: &Engine) -> (Range<i32>, Range<i32>) {
+        if Self::fits(engine) {
+            return (0..engine.board_width(), 0..engine.board_height());
+        }
+        let (x, y) = (self.x.floor() as i32, self.y.floor() as i32);
+        (
+            x..(x + VIEW_WIDTH + 1).min(engine.board_width()),
+            y..(y + VIEW_HEIGHT + 1).min(engine.board_height()),
+        )
+    }
+
+    /// How far past the first visible cell the view starts, as a fraction of a
+    /// cell on each axis.
+    pub fn offset(&self) -> (f32, f32) {
+        (self.x.fract(), self.y.fract())
+    }
+
+    /// Top-left corner in cells.
+    pub fn position(&self) -> (f32, f32) {
+        (self.x, self.y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{BoardLayout, GameConfig};
+
+    fn large(head: Cell) -> Engine {
+        let mut config = GameConfig::new(BoardLayout::Open);
+        config.resize(120, 100);
+        config.spawn.head = head;
+        Engine::new(&config, 1)
+    }
+
+    #[test]
+    fn a_board_that_fits_shows_everything() {
+        let engine = Engine::new(&GameConfig::new(BoardLayout::Open), 1);
+        assert!(Camera::fits(&engine));
+        let (columns, rows) = Camera::default().visible(&engine);
+        assert_eq!((columns, rows), (0..GRID_WIDTH, 0..GRID_HEIGHT));
+    }
+
+    #[test]
+    fn the_target_centres_the_head_inside_the_board() {
+        let engine = large(Cell { x: 60, y: 50 });
+        assert_eq!(Camera::target(&engine), (48.0, 40.0));
+        let corner = large(Cell { x: 5, y: 98 });
+        assert_eq!(Camera::target(&corner), (0.0, 80.0));
+    }
+
+    #[test]
+    fn following_eases_then_settles() {
+        let engine = large(Cell { x: 60, y: 50 });
+        let mut camera = Camera { x: 44.0, y: 40.0 };
+        assert!(camera.follow(&engine));
+        assert!(camera.x > 44.0 && camera.x < 48.0);
+        while camera.follow(&engine) {}
+        assert_eq!(camera.position(), (48.0, 40.0));
+        assert_eq!(camera.visible(&engine), (48..73, 40..61));
+    }
+
+    #[test]
+    fn long_jumps_cut_straight_to_the_target() {
+        let engine = large(Cell { x: 60, y: 50 });
+        let mut camera = Camera::default();
+        assert!(camera.follow(&engine));
+        assert_eq!(camera.position(), (48.0, 40.0));
+    }
+}
@@ -3,9 +3,11 @@
 //! date: 2025/12/14 23:45:17 Sunday
 //! brief:
 
+use serde::{Deserialize, Serialize};
+
 use super::Direction;
 
-#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Cell {
     pub x: i32,
     pub y: i32,
@@ -19,4 +21,23 @@ impl Cell {
             y: self.y + dy,
         }
     }
+
+    /// The direction of a neighbouring cell, if `other` is one.
+    pub fn direction_to(self, other: Cell) -> Option<Direction> {
+        Direction::ALL
+            .into_iter()
+            .find(|direction| self.offset(*direction) == other)
+    }
+
+    /// Spreadsheet-style name: a column letter and a 1-based row, e.g. `C4`.
+    pub fn label(self) -> String {
+        let mut column = String::new();
+        let mut x = self.x + 1;
+        while x > 0 {
+            x -= 1;
+            column.insert(0, (b'A' + (x % 26) as u8) as char);
+            x /= 26;
+        }
+        format!("{column}{}", self.y + 1)
+    }
 }
@@ -0,0 +1,65 @@
+//! file: combo.rs
+//! author: Jacob Xie
+//! date: 2026/10/16 14:58:57 Friday
+//! brief:
+
+use serde::{Deserialize, Serialize};
+
+/// Ticks after a meal in which the next one extends the combo.
+const COMBO_WINDOW: u64 = 30;
+const MAX_MULTIPLIER: u32 = 5;
+
+/// Score multiplier built by eating in quick succession.
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub struct Combo {
+    multiplier: u32,
+    last_food_tick: Option<u64>,
+}
+
+impl Default for Combo {
+    fn default() -> Self {
+        Self {
+            multiplier: 1,
+            last_food_tick: None,
+        }
+    }
+}
+
+impl Combo {
+    pub fn multiplier(&self) -> u32 {
+        self.multiplier
+    }
+
+    pub fn last_food_tick(&self) -> Option<u64> {
+        self.last_food_tick
+    }
+
+    /// Registers a meal, returning the multiplier it scores with.
+    pub fn feed(&mut self, tick: u64) -> u32 {
+        self.multiplier = if self.remaining(tick) > 0.0 {
+            (self.multiplier + 1).min(MAX_MULTIPLIER)
+        } else {
+            1
+        };
+        self.last_food_tick = Some(tick);
+        self.multiplier
+    }
+
+    /// Drops the multiplier once the window since the last meal has lapsed.
+    pub fn decay(&mut self, tick: u64) {
+        if self.remaining(tick) <= 0.0 {
+            self.multiplier = 1;
+        }
+    }
+
+    /// Fraction of the combo window left, from 1.0 right after a meal down to
+    /// 0.0 once it has lapsed.
+    pub fn remaining(&self, tick: u64) -> f32 {
+        match self.last_food_tick {
+            Some(last) if tick - last <= COMBO_WINDOW => {
+                1.0 - (tick - last) as f32 / (COMBO_WINDOW + 1) as f32
+            }
+            _ => 0.0,
+        }
+    }
+}
@@ -0,0 +1,250 @@
+//! file: config.rs
+//! author: Jacob Xie
+//! date: 2026/10/16 14:29:30 Friday
+//! brief:
+
+use serde::{Deserialize, Serialize};
+
+use super::{
+    Collisions, Difficulty, GRID_HEIGHT, GRID_WIDTH, Level, Position, Spawn, SpeedSetting, Tile,
+    TileMap,
+};
+
+/// Smallest and largest board side a config may ask for.
+const MIN_BOARD: i32 = 5;
+const MAX_BOARD: i32 = 128;
+
+#[derive(Clone, Serialize, Deserialize)]
+pub enum BoardLayout {
+    Open,
+    RandomObstacles {
+        walls: usize,
+        portal_pairs: u8,
+    },
+    Level(Level),
+    /// A shared position, started exactly as it was exported.
+    Puzzle(Position),
+}
+
+impl BoardLayout {
+    pub fn presets() -> Vec<BoardLayout> {
+        let mut layouts = vec![
+            BoardLayout::Open,
+            BoardLayout::RandomObstacles {
+                walls: 14,
+                portal_pairs: 2,
+            },
+        ];
+        layouts.extend(Level::builtin().into_iter().map(BoardLayout::Level));
+        layouts
+    }
+
+    /// The presets followed by any installed level files.
+    pub fn available() -> Vec<BoardLayout> {
+        let mut layouts = Self::presets();
+        layouts.extend(Level::installed().into_iter().map(BoardLayout::Level));
+        layouts
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            BoardLayout::Open => "Open".into(),
+            BoardLayout::RandomObstacles { .. } => "Random obstacles".into(),
+            BoardLayout::Level(level) => format!("Level: {}", level.name()),
+            BoardLayout::Puzzle(_) => "Puzzle".into(),
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct GameConfig {
+    pub board_width: i32,
+    pub board_height: i32,
+    pub layout: BoardLayout,
+    pub spawn: Spawn,
+    /// Enables periodic wind and rain events.
+    pub weather: bool,
+    /// Speedrun mode: the run ends as soon as the snake reaches this length.
+    pub goal_length: Option<usize>,
+    /// Leaving the board re-enters on the opposite edge instead of crashing.
+    pub wrap: bool,
+    /// Biting the body cuts it there instead of ending the run.
+    #[serde(default)]
+    pub self_cut: bool,
+    /// Food falls a cell every `GRAVITY_EVERY` ticks until something holds it up.
+    #[serde(default)]
+    pub gravity: bool,
+    /// Draws the board flipped left to right; the snake still moves unflipped.
+    #[serde(default)]
+    pub mirror: bool,
+    /// Which collisions end the run rather than just blocking the move.
+    #[serde(default)]
+    pub collisions: Collisions,
+    /// How the tick shortens as the score climbs.
+    #[serde(default)]
+    pub speed: SpeedSetting,
+    /// Set in adaptive mode: how far this run is tuned off the normal curve.
+    #[serde(default)]
+    pub adaptive: Option<Difficulty>,
+    /// Day of the daily challenge this run belongs to.
+    pub daily: Option<u64>,
+}
+
+impl GameConfig {
+    pub fn new(layout: BoardLayout) -> Self {
+        let (board_width, board_height) = match &layout {
+            BoardLayout::Level(level) => (level.tiles().width(), level.tiles().height()),
+            BoardLayout::Puzzle(position) => (
+                position.level().tiles().width(),
+                position.level().tiles().height(),
+            ),
+            _ => (GRID_WIDTH, GRID_HEIGHT),
+        };
+        let spawn = match &layout {
+            BoardLayout::Level(level) => level.spawn(),
+            _ => None,
+        }
+        .unwrap_or_else(|| Spawn::centered(board_width, board_height));
+        // A shared position is played on the same kind of board it came from.
+        let wrap = matches!(&layout, BoardLayout::Puzzle(position) if position.wrap());
+        Self {
+            board_width,
+            board_height,
+            layout,
+            spawn,
+            weather: false,
+            goal_length: None,
+            wrap,
+            self_cut: false,
+            gravity: false,
+            mirror: false,
+            collisions: Collisions::default(),
+            speed: SpeedSetting::default(),
+            adaptive: None,
+            daily: None,
+        }
+    }
+
+    /// Changes the board size of a generated layout, moving the spawn back to
+    /// the middle. Levels and puzzles keep their own size.
+    pub fn resize(&mut self, width: i32, height: i32) {
+        if matches!(
+            self.layout,
+            BoardLayout::Open | BoardLayout::RandomObstacles { .. }
+        ) {
+            self.board_width = width;
+            self.board_height = height;
+            self.spawn = Spawn::centered(width, height);
+        }
+    }
+
+    /// Checks that an engine can be built from this config: the board has a
+    /// sensible size, the layout matches it, the obstacles fit and the snake
+    /// has somewhere to start. Configs received from elsewhere go through this
+    /// before they are played.
+    pub fn validate(&self) -> Result<(), String> {
+        let (width, height) = (self.board_width, self.board_height);
+        let sides = MIN_BOARD..=MAX_BOARD;
+        if !sides.contains(&width) || !sides.contains(&height) {
+            return Err(format!(
+                "a {width}x{height} board is outside {MIN_BOARD}..={MAX_BOARD} cells a side"
+            ));
+        }
+        let tiles = match &self.layout {
+            BoardLayout::Open | BoardLayout::RandomObstacles { .. } => TileMap::new(width, height),
+            BoardLayout::Level(level) => level.tiles().clone(),
+            BoardLayout::Puzzle(position) => {
+                position.level().tiles().validate()?;
+                Position::decode(&position.encode())?;
+                position.level().tiles().clone()
+            }
+        };
+        tiles.validate()?;
+        if tiles.width() != width || tiles.height() != height {
+            return Err(format!(
+                "layout is {}x{} but the board is {width}x{height}",
+                tiles.width(),
+                tiles.height()
+            ));
+        }
+        // A puzzle places its own snake and food, both checked by decoding it.
+        if matches!(self.layout, BoardLayout::Puzzle(_)) {
+            return Ok(());
+        }
+        let spawn = match self.spawn.validate(&tiles) {
+            Ok(()) => self.spawn,
+            Err(err) => self.spawn.fallback(&tiles).ok_or(err)?,
+        };
+        if let BoardLayout::RandomObstacles {
+            walls,
+            portal_pairs,
+        } = self.layout
+        {
+            let off_lane = tiles
+                .cells()
+                .filter(|&(cell, _)| !self.spawn.lane_contains(cell))
+                .count();
+            // One cell is left over for the first food.
+            if walls + 2 * usize::from(portal_pairs) >= off_lane {
+                return Err(format!(
+                    "{walls} walls and {portal_pairs} portal pairs do not fit the \
+                     {off_lane} cells off the spawn lane"
+                ));
+            }
+        }
+        let empty = tiles
+            .cells()
+            .filter(|&(_, tile)| tile == Tile::Empty)
+            .count();
+        if spawn.length >= empty {
+            return Err(format!(
+                "a snake of length {} leaves no room for food",
+                spawn.length
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn presets_are_valid() {
+        for layout in BoardLayout::presets() {
+            let label = layout.label();
+            assert_eq!(GameConfig::new(layout).validate(), Ok(()), "{label}");
+        }
+    }
+
+    #[test]
+    fn tiny_and_huge_boards_are_rejected() {
+        for (width, height) in [(0, 0), (1, 1), (GRID_WIDTH, 1), (1000, 1000)] {
+            let config = GameConfig {
+                board_width: width,
+                board_height: height,
+                ..GameConfig::new(BoardLayout::Open)
+            };
+            assert!(config.validate().is_err(), "{width}x{height}");
+        }
+    }
+
+    #[test]
+    fn too_many_walls_are_rejected() {
+        let config = GameConfig::new(BoardLayout::RandomObstacles {
+            walls: (GRID_WIDTH * GRID_HEIGHT) as usize,
+            portal_pairs: 0,
+        });
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn a_spawn_off_the_board_falls_back() {
+        let mut config = GameConfig::new(BoardLayout::Open);
+        config.spawn.head.x = -10;
+        assert_eq!(config.validate(), Ok(()));
+        config.spawn.length = (GRID_WIDTH * GRID_HEIGHT) as usize;
+        assert!(config.validate().is_err());
+    }
+}
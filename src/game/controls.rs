@@ -0,0 +1,76 @@
+//! file: controls.rs
+//! author: Jacob Xie
+//! date: 2026/10/16 14:24:42 Friday
+//! brief:
+
+use std::io;
+
+use serde::{Deserialize, Serialize};
+
+use super::storage;
+
+const CONTROLS_FILE: &str = "controls.json";
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ControlProfile {
+    #[default]
+    Standard,
+    LeftHanded,
+    /// Two keys that turn left or right of the current heading.
+    Relative,
+}
+
+impl ControlProfile {
+    pub fn load() -> Self {
+        storage::read_json(&storage::data_path(CONTROLS_FILE)).unwrap_or_default()
+    }
+
+    pub fn store(self) -> io::Result<()> {
+        storage::write_json(&storage::data_path(CONTROLS_FILE), &self)
+    }
+
+    pub fn key_context(self) -> &'static str {
+        match self {
+            ControlProfile::Standard => "standard",
+            ControlProfile::LeftHanded => "left_handed",
+            ControlProfile::Relative => "relative",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            ControlProfile::Standard => ControlProfile::LeftHanded,
+            ControlProfile::LeftHanded => ControlProfile::Relative,
+            ControlProfile::Relative => ControlProfile::Standard,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ControlProfile::Standard => "Right-handed",
+            ControlProfile::LeftHanded => "Left-handed",
+            ControlProfile::Relative => "Two-key",
+        }
+    }
+
+    pub fn is_mirrored(self) -> bool {
+        self == ControlProfile::LeftHanded
+    }
+
+    pub fn instructions(self) -> [&'static str; 7] {
+        let steer = match self {
+            ControlProfile::Standard => "Arrows / WASD to steer",
+            ControlProfile::LeftHanded => "Arrows / IJKL to steer",
+            ControlProfile::Relative => "Left / Right or A / D to turn",
+        };
+        [
+            "Enter to start or restart",
+            steer,
+            "1 / 2 to use a power-up",
+            "Space to pause or resume",
+            "Ctrl+S to save, C to resume",
+            "Ctrl+C / Ctrl+V to share a position",
+            "Esc to quit, Ctrl+Q to quit now",
+        ]
+    }
+}
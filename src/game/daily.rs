@@ -0,0 +1,151 @@
+//! file: daily.rs
+//! author: Jacob Xie
+//! date: 2026/10/16 14:49:41 Friday
+//! brief:
+
+use std::{
+    collections::BTreeMap,
+    io,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha12Rng;
+use serde::{Deserialize, Serialize};
+
+use super::{BoardLayout, GameConfig, Spawn, storage};
+
+const HISTORY_FILE: &str = "daily.json";
+const BOARD_SIZES: [(i32, i32); 3] = [(16, 14), (20, 16), (24, 20)];
+const OBSTACLE_DENSITIES: [usize; 4] = [0, 8, 16, 24];
+
+/// The challenge for one calendar day (UTC). Everyone playing on the same day
+/// gets the same seed and modifiers.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct DailyChallenge {
+    day: u64,
+}
+
+impl DailyChallenge {
+    pub fn today() -> Self {
+        let secs = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |since| since.as_secs());
+        Self { day: secs / 86_400 }
+    }
+
+    pub fn day(self) -> u64 {
+        self.day
+    }
+
+    pub fn seed(self) -> u64 {
+        self.day.wrapping_mul(0x9e37_79b9_7f4a_7c15) ^ 0x5eed_da11
+    }
+
+    pub fn config(self) -> GameConfig {
+        let mut rng = ChaCha12Rng::seed_from_u64(self.seed());
+        let (board_width, board_height) = BOARD_SIZES[rng.gen_range(0..BOARD_SIZES.len())];
+        let walls = OBSTACLE_DENSITIES[rng.gen_range(0..OBSTACLE_DENSITIES.len())];
+        let layout = if walls == 0 {
+            BoardLayout::Open
+        } else {
+            BoardLayout::RandomObstacles {
+                walls,
+                portal_pairs: 0,
+            }
+        };
+        let mut config = GameConfig::new(layout);
+        config.board_width = board_width;
+        config.board_height = board_height;
+        config.spawn = Spawn::centered(board_width, board_height);
+        config.wrap = rng.gen_bool(0.5);
+        config.daily = Some(self.day);
+        config
+    }
+
+    pub fn describe(self) -> String {
+        let config = self.config();
+        let walls = match config.layout {
+            BoardLayout::RandomObstacles { walls, .. } => walls,
+            _ => 0,
+        };
+        format!(
+            "{}x{}, {}, {} obstacles",
+            config.board_width,
+            config.board_height,
+            if config.wrap { "wrap" } else { "walls" },
+            walls
+        )
+    }
+}
+
+/// `YYYY-MM-DD` for a count of days since the Unix epoch.
+pub fn date_text(day: u64) -> String {
+    // Civil-from-days conversion by Howard Hinnant.
+    let z = day as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = doy - (153 * mp + 2) / 5 + 1;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 };
+    let y = yoe + era * 400 + i64::from(m <= 2);
+    format!("{y:04}-{m:02}-{d:02}")
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub struct DailyResult {
+    pub score: u32,
+    pub length: usize,
+    /// False while the attempt is still being played (or was abandoned).
+    pub finished: bool,
+}
+
+/// Local record of daily attempts, keyed by day.
+#[derive(Default, Serialize, Deserialize)]
+pub struct DailyHistory {
+    results: BTreeMap<u64, DailyResult>,
+}
+
+impl DailyHistory {
+    pub fn load() -> Self {
+        storage::read_json(&storage::data_path(HISTORY_FILE)).unwrap_or_default()
+    }
+
+    fn store(&self) -> io::Result<()> {
+        storage::write_json(&storage::data_path(HISTORY_FILE), self)
+    }
+
+    pub fn get(&self, day: u64) -> Option<DailyResult> {
+        self.results.get(&day).copied()
+    }
+
+    pub fn played(&self, day: u64) -> bool {
+        self.results.contains_key(&day)
+    }
+
+    /// Drops the oldest results until the saved history fits in `cap` bytes,
+    /// saving it if anything went.
+    pub fn prune_to(&mut self, cap: u64) {
+        let size = |history: &Self| {
+            serde_json::to_string_pretty(history).map_or(0, |text| text.len() as u64)
+        };
+        let mut pruned = false;
+        while size(self) > cap && self.results.pop_first().is_some() {
+            pruned = true;
+        }
+        if pruned && let Err(err) = self.store() {
+            tracing::warn!("Failed to save daily history: {err}");
+        }
+    }
+
+    /// Stores the result for `day`. Starting an attempt records it straight
+    /// away so quitting midway still uses up the day's attempt.
+    pub fn record(&mut self, day: u64, result: DailyResult) {
+        self.results.insert(day, result);
+        if let Err(err) = self.store() {
+            tracing::warn!("Failed to save daily history: {err}");
+        }
+    }
+}
@@ -0,0 +1,38 @@
+//! file: death.rs
+//! author: Jacob Xie
+//! date: 2026/10/16 14:25:49 Friday
+//! brief:
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DeathCause {
+    Wall,
+    SelfCollision,
+    Obstacle,
+}
+
+impl DeathCause {
+    pub const ALL: [DeathCause; 3] = [
+        DeathCause::Wall,
+        DeathCause::SelfCollision,
+        DeathCause::Obstacle,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            DeathCause::Wall => "Hit the wall",
+            DeathCause::SelfCollision => "Bit itself",
+            DeathCause::Obstacle => "Crashed into an obstacle",
+        }
+    }
+
+    /// What the snake runs into, for naming the collision setting.
+    pub fn collider(self) -> &'static str {
+        match self {
+            DeathCause::Wall => "Walls",
+            DeathCause::SelfCollision => "Self",
+            DeathCause::Obstacle => "Obstacles",
+        }
+    }
+}
@@ -3,7 +3,9 @@
 //! date: 2025/12/14 23:45:13 Sunday
 //! brief:
 
-#[derive(Clone, Copy, PartialEq, Eq)]
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Direction {
     Up,
     Down,
@@ -12,6 +14,13 @@ pub enum Direction {
 }
 
 impl Direction {
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Down,
+        Direction::Left,
+        Direction::Right,
+    ];
+
     pub fn vector(self) -> (i32, i32) {
         match self {
             Direction::Up => (0, -1),
@@ -21,13 +30,25 @@ impl Direction {
         }
     }
 
+    pub fn opposite(self) -> Direction {
+        match self {
+            Direction::Up => Direction::Down,
+            Direction::Down => Direction::Up,
+            Direction::Left => Direction::Right,
+            Direction::Right => Direction::Left,
+        }
+    }
+
+    /// This direction as seen in a left-to-right mirror.
+    pub fn mirrored(self) -> Direction {
+        match self {
+            Direction::Left => Direction::Right,
+            Direction::Right => Direction::Left,
+            vertical => vertical,
+        }
+    }
+
     pub fn is_opposite(self, other: Direction) -> bool {
-        matches!(
-            (self, other),
-            (Direction::Up, Direction::Down)
-                | (Direction::Down, Direction::Up)
-                | (Direction::Left, Direction::Right)
-                | (Direction::Right, Direction::Left)
-        )
+        self.opposite() == other
     }
 }
@@ -0,0 +1,217 @@
+//! file: drills.rs
+//! author: Jacob Xie
+//! date: 2026/10/16 18:11:58 Friday
+//! brief:
+
+use std::{collections::HashMap, io, time::Duration};
+
+use serde::{Deserialize, Serialize};
+
+use super::{BoardLayout, Engine, GameConfig, GameStatus, Position, storage};
+
+const DRILLS_FILE: &str = "drills.json";
+
+/// A structured exercise: a scripted starting position, in the position code
+/// format, and a number of foods to eat from it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Drill {
+    TightTurns,
+    TailChase,
+    CornerEscape,
+}
+
+impl Drill {
+    pub const ALL: [Drill; 3] = [Drill::TightTurns, Drill::TailChase, Drill::CornerEscape];
+
+    fn key(self) -> &'static str {
+        match self {
+            Drill::TightTurns => "tight_turns",
+            Drill::TailChase => "tail_chase",
+            Drill::CornerEscape => "corner_escape",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Drill::TightTurns => "Tight turns",
+            Drill::TailChase => "Tail chasing",
+            Drill::CornerEscape => "Corner escape",
+        }
+    }
+
+    pub fn describe(self) -> &'static str {
+        match self {
+            Drill::TightTurns => "Weave through a grid of pillars one lane at a time",
+            Drill::TailChase => "A long snake in a small box: follow your own tail",
+            Drill::CornerEscape => "Racing at a wall - turn off before the corner",
+        }
+    }
+
+    /// Foods to eat to pass the drill.
+    pub fn goal(self) -> usize {
+        match self {
+            Drill::TightTurns => 5,
+            Drill::TailChase => 5,
+            Drill::CornerEscape => 3,
+        }
+    }
+
+    fn code(self) -> &'static str {
+        match self {
+            Drill::TightTurns => {
+                "SNK1;###########/#.........#/#.#.#.#.#.#/#.........#/#.#.#.#.#.#/\
+                 #.........#/#.#.#.#.#.#/#.........#/###########;4,1:3,1:2,1;5,6;R"
+            }
+            Drill::TailChase => {
+                "SNK1;########/#......#/#......#/#......#/#......#/#......#/########;\
+                 1,2:1,1:2,1:3,1:4,1:5,1:6,1:6,2:5,2:4,2:3,2;4,4;D"
+            }
+            Drill::CornerEscape => {
+                "SNK1;##########/#........#/#........#/#........#/#........#/#........#/\
+                 #........#/##########;7,1:6,1:5,1:4,1:3,1:2,1;1,6;R"
+            }
+        }
+    }
+
+    pub fn config(self) -> GameConfig {
+        let position = Position::decode(self.code()).expect("built-in drill positions are valid");
+        GameConfig::new(BoardLayout::Puzzle(position))
+    }
+
+    /// Fixed so every attempt drops the same food in the same places.
+    fn seed(self) -> u64 {
+        match self {
+            Drill::TightTurns => 11,
+            Drill::TailChase => 12,
+            Drill::CornerEscape => 13,
+        }
+    }
+}
+
+/// A drill being played: every crash puts the snake back at the scripted
+/// position until the goal is eaten.
+pub struct DrillRun {
+    drill: Drill,
+    attempts: u32,
+    time: Option<Duration>,
+}
+
+impl DrillRun {
+    pub fn new(drill: Drill) -> Self {
+        Self {
+            drill,
+            attempts: 0,
+            time: None,
+        }
+    }
+
+    pub fn drill(&self) -> Drill {
+        self.drill
+    }
+
+    /// A fresh attempt, paused so the player can get ready.
+    pub fn restore(&mut self) -> Engine {
+        self.attempts += 1;
+        let mut engine = Engine::new(&self.drill.config(), self.drill.seed());
+        engine.start();
+        engine.toggle_pause();
+        engine
+    }
+
+    pub fn attempts(&self) -> u32 {
+        self.attempts
+    }
+
+    /// How long the passing attempt took, once there is one.
+    pub fn time(&self) -> Option<Duration> {
+        self.time
+    }
+
+    /// Checks an attempt after a tick: notes the time once the goal is eaten,
+    /// returns whether the attempt failed.
+    pub fn check(&mut self, engine: &Engine) -> bool {
+        if engine.state() == GameStatus::GameOver {
+            return true;
+        }
+        if self.time.is_none() && engine.stats().eaten >= self.drill.goal() {
+            self.time = Some(engine.elapsed());
+        }
+        false
+    }
+}
+
+/// Fastest passing time of each drill.
+#[derive(Default, Serialize, Deserialize)]
+pub struct DrillRecords {
+    best: HashMap<String, Duration>,
+}
+
+impl DrillRecords {
+    pub fn load() -> Self {
+        storage::read_json(&storage::data_path(DRILLS_FILE)).unwrap_or_default()
+    }
+
+    fn store(&self) -> io::Result<()> {
+        storage::write_json(&storage::data_path(DRILLS_FILE), self)
+    }
+
+    pub fn best(&self, drill: Drill) -> Option<Duration> {
+        self.best.get(drill.key()).copied()
+    }
+
+    /// Keeps  if it beats the drill's best, returning whether it did.
+    pub fn record(&mut self, drill: Drill, time: Duration) -> bool {
+        if self.best(drill).is_some_and(|best| best <= time) {
+            return false;
+        }
+        self.best.insert(drill.key().to_string(), time);
+        if let Err(err) = self.store() {
+            tracing::warn!("Failed to save drill times: {err}");
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::Direction;
+
+    #[test]
+    fn every_drill_position_is_valid() {
+        for drill in Drill::ALL {
+            assert_eq!(drill.config().validate(), Ok(()), "{}", drill.label());
+            let mut run = DrillRun::new(drill);
+            let engine = run.restore();
+            assert_eq!(engine.state(), GameStatus::Paused);
+            assert_eq!(run.attempts(), 1);
+        }
+    }
+
+    #[test]
+    fn a_crash_fails_the_attempt_and_the_goal_passes_the_drill() {
+        let mut run = DrillRun::new(Drill::CornerEscape);
+        let mut engine = run.restore();
+        engine.toggle_pause();
+        while engine.state() == GameStatus::Running {
+            engine.tick();
+        }
+        assert!(run.check(&engine));
+        assert!(run.time().is_none());
+
+        // Turning down at once and sweeping back along the bottom reaches
+        // the food in the far corner.
+        let mut engine = run.restore();
+        engine.toggle_pause();
+        engine.queue_direction(Direction::Down);
+        for _ in 0..5 {
+            engine.tick();
+        }
+        engine.queue_direction(Direction::Left);
+        for _ in 0..6 {
+            engine.tick();
+            assert!(!run.check(&engine));
+        }
+        assert_eq!(engine.stats().eaten, 1);
+    }
+}
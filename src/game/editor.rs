@@ -0,0 +1,267 @@
+//! file: editor.rs
+//! author: Jacob Xie
+//! date: 2026/10/16 17:46:44 Friday
+//! brief:
+
+use std::io;
+
+use super::{Cell, Direction, Level, Spawn, Tile, TileMap};
+
+/// Smallest and largest side the editor lets a board have.
+pub const MIN_SIDE: i32 = 8;
+pub const MAX_SIDE: i32 = 40;
+/// Length of a snake placed with the spawn brush.
+const SPAWN_LENGTH: usize = 4;
+/// Portal ids a level can hold, one per digit.
+const PORTAL_IDS: u8 = 10;
+
+/// What a click on the editor grid does.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Brush {
+    Wall,
+    /// Places the next free portal end, pairing ends in the order placed.
+    Portal,
+    /// Places the snake's head; clicking the head again turns it.
+    Spawn,
+    Erase,
+}
+
+impl Brush {
+    pub const ALL: [Brush; 4] = [Brush::Wall, Brush::Portal, Brush::Spawn, Brush::Erase];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Brush::Wall => "Wall",
+            Brush::Portal => "Portal",
+            Brush::Spawn => "Spawn",
+            Brush::Erase => "Erase",
+        }
+    }
+
+    /// Whether dragging with the button held keeps painting. Portals and the
+    /// spawn are placed one click at a time.
+    pub fn drags(self) -> bool {
+        matches!(self, Brush::Wall | Brush::Erase)
+    }
+}
+
+/// A level being drawn by hand, to be play-tested and exported.
+pub struct Editor {
+    tiles: TileMap,
+    spawn: Option<Spawn>,
+    brush: Brush,
+    /// The outcome of the last play-test or export attempt, shown in the toolbar.
+    message: Option<String>,
+}
+
+impl Editor {
+    pub fn new(width: i32, height: i32) -> Self {
+        Self {
+            tiles: TileMap::new(width, height),
+            spawn: None,
+            brush: Brush::Wall,
+            message: None,
+        }
+    }
+
+    pub fn tiles(&self) -> &TileMap {
+        &self.tiles
+    }
+
+    pub fn spawn(&self) -> Option<Spawn> {
+        self.spawn
+    }
+
+    pub fn brush(&self) -> Brush {
+        self.brush
+    }
+
+    pub fn set_brush(&mut self, brush: Brush) {
+        self.brush = brush;
+    }
+
+    pub fn message(&self) -> Option<&str> {
+        self.message.as_deref()
+    }
+
+    pub fn set_message(&mut self, message: impl Into<String>) {
+        self.message = Some(message.into());
+    }
+
+    /// Applies the brush to `cell`. Returns whether anything changed.
+    pub fn paint(&mut self, cell: Cell) -> bool {
+        let before = (
+            self.tiles.get(cell),
+            self.spawn.map(|spawn| (spawn.head, spawn.direction)),
+        );
+        match self.brush {
+            Brush::Spawn => {
+                let direction = match self.spawn {
+                    Some(spawn) if spawn.head == cell => clockwise(spawn.direction),
+                    _ => Direction::Right,
+                };
+                self.tiles.set(cell, Tile::Empty);
+                self.spawn = Some(Spawn {
+                    head: cell,
+                    length: SPAWN_LENGTH,
+                    direction,
+                });
+            }
+            brush => {
+                let tile = match brush {
+                    Brush::Wall => Tile::Wall,
+                    Brush::Portal if matches!(self.tiles.get(cell), Tile::Portal(_)) => {
+                        return false;
+                    }
+                    Brush::Portal => match self.open_portal() {
+                        Some(id) => Tile::Portal(id),
+                        None => {
+                            self.set_message(format!("No more than {PORTAL_IDS} portal pairs"));
+                            return false;
+                        }
+                    },
+                    _ => Tile::Empty,
+                };
+                // Anything painted over the snake clears it; it is redrawn whole.
+                if tile != Tile::Empty
+                    && self
+                        .spawn
+                        .is_some_and(|spawn| spawn.cells().any(|body| body == cell))
+                {
+                    self.spawn = None;
+                }
+                self.tiles.set(cell, tile);
+            }
+        }
+        let changed = before
+            != (
+                self.tiles.get(cell),
+                self.spawn.map(|spawn| (spawn.head, spawn.direction)),
+            );
+        if changed {
+            self.message = None;
+        }
+        changed
+    }
+
+    /// The id for the next portal end: one waiting for its pair, else the
+    /// first unused one.
+    fn open_portal(&self) -> Option<u8> {
+        let mut ends = [0u8; PORTAL_IDS as usize];
+        for (_, tile) in self.tiles.cells() {
+            if let Tile::Portal(id) = tile {
+                ends[id as usize] += 1;
+            }
+        }
+        let id = ends
+            .iter()
+            .position(|&count| count == 1)
+            .or_else(|| ends.iter().position(|&count| count == 0))?;
+        Some(id as u8)
+    }
+
+    /// Changes the board size, keeping whatever still fits. A spawn whose head
+    /// falls off the board is dropped.
+    pub fn resize(&mut self, width: i32, height: i32) {
+        let (width, height) = (
+            width.clamp(MIN_SIDE, MAX_SIDE),
+            height.clamp(MIN_SIDE, MAX_SIDE),
+        );
+        let mut tiles = TileMap::new(width, height);
+        for (cell, tile) in self.tiles.cells() {
+            tiles.set(cell, tile);
+        }
+        self.tiles = tiles;
+        if self
+            .spawn
+            .is_some_and(|spawn| spawn.head.x >= width || spawn.head.y >= height)
+        {
+            self.spawn = None;
+        }
+        self.message = None;
+    }
+
+    /// The drawing as a playable level, or why it is not one yet. It goes
+    /// through the text form so it is held to the same rules as a level file.
+    pub fn level(&self, name: &str) -> Result<Level, String> {
+        let level = Level::new(name, self.tiles.clone()).with_spawn(self.spawn);
+        Level::parse(name, &level.to_text())
+    }
+
+    /// Installs the drawing as a level file under the first free "Custom N"
+    /// name, where the layout list picks it up.
+    pub fn export(&self) -> io::Result<Level> {
+        let name = (1..)
+            .map(|n| format!("Custom {n}"))
+            .find(|name| !Level::file_path(name).exists())
+            .expect("some custom level name is free");
+        let level = self.level(&name).map_err(io::Error::other)?;
+        level.install()?;
+        Ok(level)
+    }
+}
+
+fn clockwise(direction: Direction) -> Direction {
+    match direction {
+        Direction::Up => Direction::Right,
+        Direction::Right => Direction::Down,
+        Direction::Down => Direction::Left,
+        Direction::Left => Direction::Up,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn portal_ends_pair_up_in_order() {
+        let mut editor = Editor::new(10, 10);
+        editor.set_brush(Brush::Portal);
+        for x in 0..3 {
+            editor.paint(Cell { x, y: 0 });
+        }
+        assert_eq!(editor.tiles().get(Cell { x: 1, y: 0 }), Tile::Portal(0));
+        assert_eq!(editor.tiles().get(Cell { x: 2, y: 0 }), Tile::Portal(1));
+        // Half a pair keeps the drawing from being a level.
+        assert!(editor.level("Test").is_err());
+        editor.paint(Cell { x: 3, y: 0 });
+        assert!(editor.level("Test").is_ok());
+    }
+
+    #[test]
+    fn the_spawn_turns_and_survives_the_round_trip() {
+        let mut editor = Editor::new(10, 10);
+        editor.set_brush(Brush::Spawn);
+        let head = Cell { x: 5, y: 5 };
+        editor.paint(head);
+        editor.paint(head);
+        let level = editor.level("Test").expect("spawn fits");
+        let spawn = level.spawn().expect("spawn is kept");
+        assert_eq!(spawn.head, head);
+        assert_eq!(spawn.direction, Direction::Down);
+
+        editor.set_brush(Brush::Wall);
+        editor.paint(Cell { x: 5, y: 4 });
+        assert!(editor.spawn().is_none());
+    }
+
+    #[test]
+    fn resizing_keeps_what_fits() {
+        let mut editor = Editor::new(10, 10);
+        editor.paint(Cell { x: 2, y: 2 });
+        editor.paint(Cell { x: 9, y: 9 });
+        editor.resize(8, 100);
+        assert_eq!(editor.tiles().width(), 8);
+        assert_eq!(editor.tiles().height(), MAX_SIDE);
+        assert_eq!(editor.tiles().get(Cell { x: 2, y: 2 }), Tile::Wall);
+        assert_eq!(
+            editor
+                .tiles()
+                .cells()
+                .filter(|(_, tile)| *tile == Tile::Wall)
+                .count(),
+            1
+        );
+    }
+}
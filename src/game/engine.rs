@@ -0,0 +1,1468 @@
+//! file: engine.rs
+//! author: Jacob Xie
+//! date: 2026/10/16 14:26:15 Friday
+//! brief:
+
+use std::{collections::VecDeque, time::Duration};
+
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha12Rng;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    ActiveEffect, BASE_TICK_MS, BoardLayout, CHECKPOINT_EVERY, Cell, Combo, DeathCause, Direction,
+    FoodKind, FoodState, GLOW_BONUS, GRAVITY_EVERY, GameConfig, GameEvent, GameStatus, Input,
+    Inventory, MAX_EXTRA_FOOD, MAX_SPEED_BIAS, MIN_LENGTH, MIN_TICK_MS, Mutation, Occupancy,
+    PELLET_LIFETIME, PELLETS_PER_POINT, PICKUP_LIFETIME, POWER_UP_EVERY, Pellet, Pickup, PowerUp,
+    Replay, RunStats, SCORE_PER_LEVEL, SHRINK_BY, SPLIT_EVERY, SegmentEffect, Tile, TileMap,
+    WIND_EVERY, Weather, WeatherEvent,
+};
+
+const INPUT_QUEUE_LEN: usize = 3;
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Engine {
+    config: GameConfig,
+    board_width: i32,
+    board_height: i32,
+    tiles: TileMap,
+    snake: VecDeque<Cell>,
+    /// Effects per body segment, indexed from the head. Kept apart from the
+    /// cells because moving shifts cells along the body, not segments.
+    segment_effects: Vec<Option<SegmentEffect>>,
+    occupancy: Occupancy,
+    direction: Direction,
+    input_queue: VecDeque<Direction>,
+    food: Cell,
+    food_kind: FoodKind,
+    food_state: FoodState,
+    /// Segments still to be grown, one per tick, by keeping the tail in place.
+    pending_growth: usize,
+    seed: u64,
+    rng: ChaCha12Rng,
+    state: GameStatus,
+    score: u32,
+    ticks: u64,
+    elapsed: Duration,
+    death: Option<DeathCause>,
+    inputs: Vec<(u64, Input)>,
+    /// What happened during the latest tick.
+    #[serde(skip)]
+    events: Vec<GameEvent>,
+    /// Pieces of food eaten this run.
+    eaten: usize,
+    wall_ticks: u64,
+    /// Elapsed time at each speedrun split.
+    splits: Vec<Duration>,
+    /// Longest length a split has been taken at, so shrinking and growing
+    /// back past a split does not take it again.
+    split_length: usize,
+    /// Elapsed time at each speedrun ghost checkpoint.
+    checkpoints: Vec<Duration>,
+    combo: Combo,
+    pickup: Option<Pickup>,
+    /// Severed segments lying on the board in self-cut mode.
+    pellets: Vec<Pellet>,
+    pellets_eaten: u32,
+    weather: Option<WeatherEvent>,
+    next_weather_at: u64,
+    inventory: Inventory,
+    effects: Vec<ActiveEffect>,
+    pending_activation: Option<usize>,
+    base_tick_ms: u64,
+    min_tick_ms: u64,
+    /// Percent mutator scripts have sped the pace up by, or slowed it down
+    /// when negative.
+    #[serde(default)]
+    speed_bias: i8,
+    #[serde(skip, default = "verify_occupancy_default")]
+    verify_occupancy: bool,
+    /// Practice mode: timed ticks do nothing and the run only moves on `step`.
+    #[serde(skip)]
+    stepping: bool,
+    /// Practice mode: walls and obstacles stop the snake instead of killing it.
+    #[serde(skip)]
+    wall_immune: bool,
+}
+
+fn verify_occupancy_default() -> bool {
+    cfg!(debug_assertions)
+}
+
+impl Engine {
+    pub fn new(config: &GameConfig, seed: u64) -> Self {
+        let board_width = config.board_width;
+        let board_height = config.board_height;
+        let mut rng = ChaCha12Rng::seed_from_u64(seed);
+        let tiles = Self::build_tiles(config, &mut rng);
+        let spawn = match &config.layout {
+            // A puzzle places its own snake, so the configured spawn is unused.
+            BoardLayout::Puzzle(_) => config.spawn,
+            _ => match config.spawn.validate(&tiles) {
+                Ok(()) => config.spawn,
+                Err(err) => match config.spawn.fallback(&tiles) {
+                    Some(spawn) => {
+                        tracing::warn!(
+                            "Invalid spawn, falling back to ({}, {}): {err}",
+                            spawn.head.x,
+                            spawn.head.y
+                        );
+                        spawn
+                    }
+                    None => {
+                        tracing::warn!("Invalid spawn and no free lane to fall back to: {err}");
+                        config.spawn
+                    }
+                },
+            },
+        };
+        let snake: VecDeque<Cell> = match &config.layout {
+            BoardLayout::Puzzle(position) => position.snake().iter().copied().collect(),
+            _ => spawn.cells().collect(),
+        };
+        let occupancy = Occupancy::from_cells(board_width, board_height, &snake);
+        let (food, direction) = match &config.layout {
+            BoardLayout::Puzzle(position) => (position.food(), position.direction()),
+            _ => (
+                Self::random_food(&occupancy, &tiles, &mut rng),
+                spawn.direction,
+            ),
+        };
+        let food_kind = Self::roll_food(config, &mut rng);
+        let segment_effects = vec![None; snake.len()];
+        let next_weather_at = WeatherEvent::calm_ticks(&mut rng);
+
+        Self {
+            config: config.clone(),
+            board_width,
+            board_height,
+            tiles,
+            snake,
+            segment_effects,
+            occupancy,
+            direction,
+            input_queue: VecDeque::with_capacity(INPUT_QUEUE_LEN),
+            food,
+            food_kind,
+            food_state: FoodState::Whole,
+            pending_growth: 0,
+            seed,
+            rng,
+            state: GameStatus::Ready,
+            score: 0,
+            ticks: 0,
+            elapsed: Duration::ZERO,
+            death: None,
+            inputs: Vec::new(),
+            events: Vec::new(),
+            eaten: 0,
+            wall_ticks: 0,
+            splits: Vec::new(),
+            split_length: 0,
+            checkpoints: Vec::new(),
+            combo: Combo::default(),
+            pickup: None,
+            pellets: Vec::new(),
+            pellets_eaten: 0,
+            weather: None,
+            next_weather_at,
+            inventory: Inventory::default(),
+            effects: Vec::new(),
+            pending_activation: None,
+            base_tick_ms: BASE_TICK_MS,
+            min_tick_ms: MIN_TICK_MS,
+            speed_bias: 0,
+            verify_occupancy: verify_occupancy_default(),
+            stepping: false,
+            wall_immune: false,
+        }
+    }
+
+    /// Lays out the board's walls and portals. Random obstacles keep the spawn
+    /// lane clear so the snake always has room to get going.
+    fn build_tiles(config: &GameConfig, rng: &mut ChaCha12Rng) -> TileMap {
+        let (width, height) = (config.board_width, config.board_height);
+        match &config.layout {
+            BoardLayout::Open => TileMap::new(width, height),
+            BoardLayout::Level(level) => level.tiles().clone(),
+            BoardLayout::Puzzle(position) => position.level().tiles().clone(),
+            BoardLayout::RandomObstacles {
+                walls,
+                portal_pairs,
+            } => {
+                let mut tiles = TileMap::new(width, height);
+                let mut free_cell = |tiles: &TileMap| loop {
+                    let cell = Cell {
+                        x: rng.gen_range(0..width),
+                        y: rng.gen_range(0..height),
+                    };
+                    if !config.spawn.lane_contains(cell) && tiles.get(cell) == Tile::Empty {
+                        return cell;
+                    }
+                };
+                for _ in 0..*walls {
+                    let cell = free_cell(&tiles);
+                    tiles.set(cell, Tile::Wall);
+                }
+                for id in 0..*portal_pairs {
+                    for _ in 0..2 {
+                        let cell = free_cell(&tiles);
+                        tiles.set(cell, Tile::Portal(id));
+                    }
+                }
+                tiles
+            }
+        }
+    }
+
+    fn roll_food(config: &GameConfig, rng: &mut ChaCha12Rng) -> FoodKind {
+        let kind = FoodKind::roll(rng);
+        match config.adaptive {
+            Some(difficulty) => difficulty.adjust_food(kind, rng),
+            None => kind,
+        }
+    }
+
+    fn random_food(occupancy: &Occupancy, tiles: &TileMap, rng: &mut ChaCha12Rng) -> Cell {
+        loop {
+            let cell = Cell {
+                x: rng.gen_range(0..tiles.width()),
+                y: rng.gen_range(0..tiles.height()),
+            };
+            if !occupancy.contains(cell) && tiles.get(cell) == Tile::Empty {
+                return cell;
+            }
+        }
+    }
+
+    pub fn pickup(&self) -> Option<Pickup> {
+        self.pickup
+    }
+
+    pub fn pellets(&self) -> &[Pellet] {
+        &self.pellets
+    }
+
+    pub fn weather(&self) -> Option<Weather> {
+        self.weather.map(|event| event.weather)
+    }
+
+    pub fn inventory(&self) -> &Inventory {
+        &self.inventory
+    }
+
+    pub fn effects(&self) -> &[ActiveEffect] {
+        &self.effects
+    }
+
+    pub fn has_effect(&self, power_up: PowerUp) -> bool {
+        self.effects
+            .iter()
+            .any(|effect| effect.power_up == power_up)
+    }
+
+    pub fn config(&self) -> &GameConfig {
+        &self.config
+    }
+
+    pub fn board_width(&self) -> i32 {
+        self.board_width
+    }
+
+    pub fn board_height(&self) -> i32 {
+        self.board_height
+    }
+
+    pub fn tiles(&self) -> &TileMap {
+        &self.tiles
+    }
+
+    pub fn snake(&self) -> &VecDeque<Cell> {
+        &self.snake
+    }
+
+    pub fn direction(&self) -> Direction {
+        self.direction
+    }
+
+    pub fn food(&self) -> Cell {
+        self.food
+    }
+
+    pub fn food_kind(&self) -> FoodKind {
+        self.food_kind
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn food_state(&self) -> FoodState {
+        self.food_state
+    }
+
+    /// Body cells from head to tail along with the effect each segment carries.
+    pub fn segments(&self) -> impl Iterator<Item = (Cell, Option<SegmentEffect>)> + '_ {
+        self.snake
+            .iter()
+            .copied()
+            .zip(self.segment_effects.iter().copied())
+    }
+
+    pub fn state(&self) -> GameStatus {
+        self.state
+    }
+
+    pub fn score(&self) -> u32 {
+        self.score
+    }
+
+    pub fn ticks(&self) -> u64 {
+        self.ticks
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    pub fn death(&self) -> Option<DeathCause> {
+        self.death
+    }
+
+    pub fn events(&self) -> &[GameEvent] {
+        &self.events
+    }
+
+    pub fn splits(&self) -> &[Duration] {
+        &self.splits
+    }
+
+    pub fn checkpoints(&self) -> &[Duration] {
+        &self.checkpoints
+    }
+
+    pub fn combo(&self) -> &Combo {
+        &self.combo
+    }
+
+    /// Whether a speedrun reached its goal length.
+    pub fn completed(&self) -> bool {
+        self.config
+            .goal_length
+            .is_some_and(|goal| self.snake.len() >= goal)
+    }
+
+    pub fn stats(&self) -> RunStats {
+        RunStats {
+            ticks: self.ticks,
+            eaten: self.eaten,
+            turns: self
+                .inputs
+                .iter()
+                .filter(|(_, input)| matches!(input, Input::Turn(_)))
+                .count(),
+            wall_ticks: self.wall_ticks,
+        }
+    }
+
+    /// Whether the head sits right next to a wall or obstacle.
+    fn hugging_wall(&self) -> bool {
+        self.snake.front().is_some_and(|&head| {
+            Direction::ALL
+                .into_iter()
+                .any(|direction| self.resolve_step(head, direction).is_err())
+        })
+    }
+
+    pub fn replay(&self) -> Replay {
+        Replay::new(self.config.clone(), self.seed, self.inputs.clone())
+    }
+
+    pub fn level(&self) -> u32 {
+        self.score / SCORE_PER_LEVEL
+    }
+
+    /// Tick length in ms from the speed curve and mutators, held to its
+    /// floor, then adaptive tuning, before any power-up stretches it.
+    fn paced_ms(&self) -> u64 {
+        let ms = self
+            .config
+            .speed
+            .curve()
+            .tick_ms(self.base_tick_ms, self.score);
+        let ms = (ms * (100 - self.speed_bias as i64) as u64 / 100).max(self.min_tick_ms);
+        self.config
+            .adaptive
+            .map_or(ms, |difficulty| difficulty.scale_tick(ms))
+    }
+
+    pub fn tick_delay(&self) -> Duration {
+        let ms = self.paced_ms();
+        if self.has_effect(PowerUp::SlowMotion) {
+            Duration::from_millis(ms * 3 / 2)
+        } else {
+            Duration::from_millis(ms)
+        }
+    }
+
+    fn board_contains(&self, cell: &Cell) -> bool {
+        (0..self.board_width).contains(&cell.x) && (0..self.board_height).contains(&cell.y)
+    }
+
+    /// Where a head at `from` ends up after moving one cell in `direction`,
+    /// following portals, or the cause of death if the move is fatal. Collision
+    /// with the body is checked against the returned (exit) cell.
+    pub fn resolve_step(&self, from: Cell, direction: Direction) -> Result<Cell, DeathCause> {
+        let mut next = from.offset(direction);
+        if !self.board_contains(&next) {
+            if !self.config.wrap {
+                return Err(DeathCause::Wall);
+            }
+            next = Cell {
+                x: next.x.rem_euclid(self.board_width),
+                y: next.y.rem_euclid(self.board_height),
+            };
+        }
+        match self.tiles.get(next) {
+            Tile::Wall => Err(DeathCause::Obstacle),
+            Tile::Gate(allowed) if allowed != direction => Err(DeathCause::Obstacle),
+            Tile::Portal(_) => Ok(self.tiles.portal_exit(next).unwrap_or(next)),
+            Tile::Empty | Tile::Conveyor(_) | Tile::Gate(_) => Ok(next),
+        }
+    }
+
+    pub fn is_occupied(&self, cell: Cell) -> bool {
+        self.occupancy.contains(cell)
+    }
+
+    /// Buffers a turn for a later tick. Turns are checked against reversal only
+    /// when consumed, so quick zig-zags within one tick are not lost.
+    pub fn queue_direction(&mut self, direction: Direction) {
+        if matches!(self.state, GameStatus::GameOver | GameStatus::Ready) {
+            return;
+        }
+        if direction == self.heading() {
+            return;
+        }
+        if self.input_queue.len() >= INPUT_QUEUE_LEN {
+            tracing::debug!(
+                tick = self.ticks,
+                ?direction,
+                "Input queue full, dropped a turn"
+            );
+            return;
+        }
+        self.input_queue.push_back(direction);
+    }
+
+    /// Applies a mutator's change to a running game, within the bounds
+    /// mutators are allowed.
+    pub fn mutate(&mut self, mutation: Mutation) {
+        if self.state != GameStatus::Running {
+            return;
+        }
+        self.inputs.push((self.ticks, Input::Mutate(mutation)));
+        match mutation {
+            Mutation::ExtraFood(count) => {
+                let expires_at = self.ticks + PELLET_LIFETIME;
+                for _ in 0..count.min(MAX_EXTRA_FOOD) {
+                    let cell = self.random_empty_cell();
+                    self.pellets.push(Pellet { cell, expires_at });
+                }
+            }
+            Mutation::Speed(percent) => {
+                self.speed_bias = self
+                    .speed_bias
+                    .saturating_add(percent)
+                    .clamp(-MAX_SPEED_BIAS, MAX_SPEED_BIAS);
+            }
+        }
+    }
+
+    /// The way the snake will be going once its queued turns are taken.
+    pub fn heading(&self) -> Direction {
+        self.input_queue.back().copied().unwrap_or(self.direction)
+    }
+
+    /// Queues the power-up in `slot` to take effect on the next tick.
+    pub fn activate(&mut self, slot: usize) {
+        if self.state == GameStatus::Running && self.inventory.get(slot).is_some() {
+            self.pending_activation = Some(slot);
+        }
+    }
+
+    fn next_queued_direction(&mut self) -> Option<Direction> {
+        while let Some(direction) = self.input_queue.pop_front() {
+            if !(direction.is_opposite(self.direction) && self.snake.len() > 1) {
+                return Some(direction);
+            }
+            self.events.push(GameEvent::ReversalBlocked);
+        }
+        None
+    }
+
+    pub fn start(&mut self) {
+        self.state = GameStatus::Running;
+    }
+
+    pub fn toggle_pause(&mut self) {
+        self.state = match self.state {
+            GameStatus::Running => GameStatus::Paused,
+            GameStatus::Paused => GameStatus::Running,
+            other => other,
+        };
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::new(&self.config, rand::random());
+    }
+
+    /// A free cell that is also clear of the food, any power-up pickup and
+    /// any pellets.
+    fn random_empty_cell(&mut self) -> Cell {
+        loop {
+            let cell = Self::random_food(&self.occupancy, &self.tiles, &mut self.rng);
+            if cell != self.food
+                && self.pickup.is_none_or(|pickup| pickup.cell != cell)
+                && self.pellets.iter().all(|pellet| pellet.cell != cell)
+            {
+                return cell;
+            }
+        }
+    }
+
+    fn spawn_pickup(&mut self) {
+        let power_up = PowerUp::ALL[self.rng.gen_range(0..PowerUp::ALL.len())];
+        let cell = self.random_empty_cell();
+        self.pickup = Some(Pickup {
+            cell,
+            power_up,
+            expires_at: self.ticks + PICKUP_LIFETIME,
+        });
+    }
+
+    fn advance_effects(&mut self) {
+        for effect in &mut self.effects {
+            effect.remaining_ticks = effect.remaining_ticks.saturating_sub(1);
+        }
+        self.effects.retain(|effect| effect.remaining_ticks > 0);
+        if self
+            .pickup
+            .is_some_and(|pickup| self.ticks >= pickup.expires_at)
+        {
+            self.pickup = None;
+        }
+        let ticks = self.ticks;
+        self.pellets.retain(|pellet| ticks < pellet.expires_at);
+        if self.config.gravity && ticks.is_multiple_of(GRAVITY_EVERY) {
+            self.drop_food();
+        }
+        if self.food_state.heals_by(ticks) {
+            self.food_state = FoodState::Whole;
+            self.events.push(GameEvent::FoodHealed { cell: self.food });
+        }
+    }
+
+    /// Gravity: moves the food down a cell unless it rests on the bottom edge,
+    /// a tile, the snake or anything else lying on the board.
+    fn drop_food(&mut self) {
+        let below = self.food.offset(Direction::Down);
+        let resting = !self.board_contains(&below)
+            || self.tiles.get(below) != Tile::Empty
+            || self.occupancy.contains(below)
+            || self.pickup.is_some_and(|pickup| pickup.cell == below)
+            || self.pellets.iter().any(|pellet| pellet.cell == below);
+        if !resting {
+            self.food = below;
+        }
+    }
+
+    /// Self-cut mode: severs the body at `at`, where the head is about to
+    /// bite it. The bitten segment goes to the head and the ones behind it
+    /// drop off as pellets.
+    fn cut(&mut self, at: Cell) {
+        let Some(index) = self.snake.iter().position(|cell| *cell == at) else {
+            return;
+        };
+        let severed: Vec<Cell> = self.snake.drain(index..).collect();
+        // Unless the snake is growing its tail has already moved on, so only
+        // drop the effects of the segments actually severed.
+        self.segment_effects
+            .truncate(self.segment_effects.len() - severed.len());
+        for cell in &severed {
+            self.occupancy.remove(*cell);
+        }
+        let expires_at = self.ticks + PELLET_LIFETIME;
+        self.pellets.extend(
+            severed
+                .iter()
+                .filter(|cell| **cell != at)
+                .map(|&cell| Pellet { cell, expires_at }),
+        );
+        self.events.push(GameEvent::Cut {
+            cell: at,
+            severed: severed.len(),
+        });
+    }
+
+    /// Drops up to `SHRINK_BY` segments off the tail, down to `MIN_LENGTH`.
+    fn shrink(&mut self) {
+        let removed = SHRINK_BY.min(self.snake.len().saturating_sub(MIN_LENGTH));
+        for _ in 0..removed {
+            if let Some(tail) = self.snake.pop_back() {
+                self.occupancy.remove(tail);
+            }
+        }
+        self.segment_effects
+            .truncate(self.segment_effects.len().saturating_sub(removed));
+        self.events.push(GameEvent::Shrunk { removed });
+    }
+
+    fn eat_pellet(&mut self, cell: Cell) {
+        let Some(index) = self.pellets.iter().position(|pellet| pellet.cell == cell) else {
+            return;
+        };
+        self.pellets.swap_remove(index);
+        self.pellets_eaten += 1;
+        if self.pellets_eaten.is_multiple_of(PELLETS_PER_POINT) {
+            self.score += 1;
+        }
+        self.events.push(GameEvent::PelletEaten {
+            cell,
+            score: self.score,
+        });
+    }
+
+    /// The sideways direction the wind pushes the snake this tick, if any.
+    fn wind_drift(&self) -> Option<Direction> {
+        match self.weather {
+            Some(WeatherEvent {
+                weather: Weather::Wind(wind),
+                ..
+            }) if self.ticks.is_multiple_of(WIND_EVERY)
+                && wind != self.direction
+                && !wind.is_opposite(self.direction) =>
+            {
+                Some(wind)
+            }
+            _ => None,
+        }
+    }
+
+    fn advance_weather(&mut self) {
+        if !self.config.weather {
+            return;
+        }
+        match &mut self.weather {
+            Some(event) => {
+                event.remaining_ticks = event.remaining_ticks.saturating_sub(1);
+                if event.remaining_ticks == 0 {
+                    self.weather = None;
+                    self.events.push(GameEvent::WeatherCleared);
+                    self.next_weather_at = self.ticks + WeatherEvent::calm_ticks(&mut self.rng);
+                }
+            }
+            None if self.ticks >= self.next_weather_at => {
+                let event = WeatherEvent::roll(&mut self.rng);
+                tracing::debug!(
+                    "Weather: {} for {} ticks",
+                    event.weather.banner(),
+                    event.remaining_ticks
+                );
+                self.events.push(GameEvent::WeatherStarted(event.weather));
+                self.weather = Some(event);
+            }
+            None => {}
+        }
+    }
+
+    fn die(&mut self, cause: DeathCause) {
+        self.end_run();
+        self.death = Some(cause);
+        self.events.push(GameEvent::Died { cause });
+    }
+
+    fn end_run(&mut self) {
+        let glowing = self
+            .segment_effects
+            .iter()
+            .filter(|effect| **effect == Some(SegmentEffect::Glowing))
+            .count() as u32;
+        self.score += glowing * GLOW_BONUS;
+        self.state = GameStatus::GameOver;
+    }
+
+    /// Speedrun bookkeeping after a move that ate or changed the length.
+    /// Queued growth keeps lengthening the snake on moves that eat nothing,
+    /// so splits and the goal are checked on those too.
+    fn take_split(&mut self, ate_food: bool) {
+        if self.config.goal_length.is_none() {
+            return;
+        }
+        let length = self.snake.len();
+        if length > self.split_length && (length.is_multiple_of(SPLIT_EVERY) || self.completed()) {
+            self.split_length = length;
+            self.splits.push(self.elapsed);
+            self.events.push(GameEvent::Split {
+                length,
+                elapsed: self.elapsed,
+            });
+        }
+        if (ate_food && self.eaten.is_multiple_of(CHECKPOINT_EVERY)) || self.completed() {
+            self.checkpoints.push(self.elapsed);
+            self.events.push(GameEvent::Checkpoint {
+                eaten: self.eaten,
+                elapsed: self.elapsed,
+            });
+        }
+        if self.completed() {
+            self.end_run();
+            self.events.push(GameEvent::Completed);
+        }
+    }
+
+    /// The way a conveyor pushes a head that just moved off `from` onto it. A
+    /// head held in place by armor stays put rather than riding again.
+    fn conveyor_push(&self, from: Option<Cell>) -> Option<Direction> {
+        let head = self.snake.front().copied()?;
+        if Some(head) == from {
+            return None;
+        }
+        match self.tiles.get(head) {
+            Tile::Conveyor(direction) => Some(direction),
+            _ => None,
+        }
+    }
+
+    /// Handles a fatal collision: wall immunity or an armored segment absorbs
+    /// the hit and the snake holds its position for this tick, otherwise the
+    /// run ends. Returns whether the snake survived.
+    fn crash(&mut self, cause: DeathCause) -> bool {
+        if !self.config.collisions.is_fatal(cause)
+            || (self.wall_immune && matches!(cause, DeathCause::Wall | DeathCause::Obstacle))
+        {
+            return true;
+        }
+        if let Some(effect) = self
+            .segment_effects
+            .iter_mut()
+            .find(|effect| **effect == Some(SegmentEffect::Armored))
+        {
+            *effect = None;
+            self.events.push(GameEvent::ArmorAbsorbed(cause));
+            return true;
+        }
+        self.die(cause);
+        false
+    }
+
+    pub fn set_stepping(&mut self, stepping: bool) {
+        self.stepping = stepping;
+    }
+
+    pub fn set_wall_immune(&mut self, wall_immune: bool) {
+        self.wall_immune = wall_immune;
+    }
+
+    /// Advances the simulation on the game clock, unless it is being stepped
+    /// by hand. Returns whether anything changed.
+    pub fn tick(&mut self) -> bool {
+        !self.stepping && self.step()
+    }
+
+    /// Advances the simulation by one step, returning whether anything changed.
+    pub fn step(&mut self) -> bool {
+        if self.state != GameStatus::Running {
+            return false;
+        }
+        if self.snake.is_empty() {
+            return false;
+        }
+        self.events.clear();
+        let paced_ms = self.paced_ms();
+
+        if let Some(slot) = self.pending_activation.take()
+            && let Some(power_up) = self.inventory.take(slot)
+        {
+            self.inputs.push((self.ticks, Input::Activate(slot)));
+            self.events.push(GameEvent::Activated(power_up));
+            self.effects.push(ActiveEffect {
+                power_up,
+                remaining_ticks: power_up.duration_ticks(),
+            });
+        }
+        if let Some(direction) = self.next_queued_direction()
+            && direction != self.direction
+        {
+            self.inputs.push((self.ticks, Input::Turn(direction)));
+            self.events.push(GameEvent::DirectionChanged(direction));
+            self.direction = direction;
+        }
+        self.elapsed += self.tick_delay();
+        self.ticks += 1;
+        let from = self.snake.front().copied();
+        self.advance(self.direction);
+        if self.state == GameStatus::Running
+            && let Some(push) = self.conveyor_push(from)
+        {
+            self.advance(push);
+        }
+        if self.state == GameStatus::Running
+            && let Some(wind) = self.wind_drift()
+        {
+            self.advance(wind);
+        }
+        if self.state == GameStatus::Running {
+            self.advance_effects();
+            self.advance_weather();
+            self.combo.decay(self.ticks);
+            if self.hugging_wall() {
+                self.wall_ticks += 1;
+            }
+        }
+        let tick_ms = self.paced_ms();
+        if tick_ms < paced_ms {
+            self.events.push(GameEvent::SpeedUp { tick_ms });
+        }
+        self.events.push(GameEvent::TickAdvanced);
+        self.check_occupancy();
+        true
+    }
+
+    /// Moves the snake one cell in `direction`, handling crashes, food and pickups.
+    fn advance(&mut self, direction: Direction) {
+        let Some(head) = self.snake.front().copied() else {
+            return;
+        };
+        let next = match self.resolve_step(head, direction) {
+            Ok(next) => next,
+            Err(cause) => {
+                tracing::debug!(
+                    "Crash ({}): head=({}, {}), direction={:?}, board=({}, {})",
+                    cause.label(),
+                    head.x,
+                    head.y,
+                    direction,
+                    self.board_width,
+                    self.board_height
+                );
+                self.crash(cause);
+                return;
+            }
+        };
+
+        let reached_food = next == self.food;
+        let ate_food = reached_food && self.food_state.eaten_by_hit(self.food_kind);
+        // Food only queues growth; the first queued segment grows on this very
+        // move, so plain food still lengthens the snake the tick it is eaten.
+        let growing = self.pending_growth > 0 || (ate_food && self.food_kind.growth() > 0);
+        let tail = if growing { None } else { self.snake.pop_back() };
+        if let Some(tail) = tail {
+            self.occupancy.remove(tail);
+        }
+
+        if self.config.self_cut && self.occupancy.contains(next) && !self.has_effect(PowerUp::Ghost)
+        {
+            self.cut(next);
+        }
+        if self.occupancy.contains(next) && !self.has_effect(PowerUp::Ghost) {
+            tracing::debug!(
+                "Crash (self): head=({}, {}), next=({}, {}), len={}, board=({}, {})",
+                head.x,
+                head.y,
+                next.x,
+                next.y,
+                self.snake.len(),
+                self.board_width,
+                self.board_height
+            );
+            if self.crash(DeathCause::SelfCollision)
+                && let Some(tail) = tail
+            {
+                self.snake.push_back(tail);
+                self.occupancy.insert(tail);
+            }
+            return;
+        }
+
+        self.snake.push_front(next);
+        self.occupancy.insert(next);
+        if ate_food {
+            self.pending_growth += self.food_kind.growth();
+        }
+        if growing {
+            self.pending_growth -= 1;
+            let effect = if ate_food {
+                self.food_kind.segment_effect()
+            } else {
+                None
+            };
+            self.segment_effects.push(effect);
+        }
+        self.eat_pellet(next);
+        if reached_food && !ate_food {
+            self.food_state = FoodState::crack(self.ticks);
+            self.events.push(GameEvent::FoodCracked { cell: next });
+        }
+        if ate_food {
+            let previous = self.score;
+            let base = if self.has_effect(PowerUp::DoublePoints) {
+                2
+            } else {
+                1
+            };
+            let multiplier = self.combo.feed(self.ticks);
+            self.score += base * self.food_kind.points() * multiplier;
+            self.events.push(GameEvent::FoodEaten {
+                cell: next,
+                score: self.score,
+                multiplier,
+            });
+            if previous / SCORE_PER_LEVEL != self.score / SCORE_PER_LEVEL {
+                self.events.push(GameEvent::LevelUp {
+                    level: self.level(),
+                });
+            }
+            if self.food_kind == FoodKind::Shrink {
+                self.shrink();
+            }
+            self.eaten += 1;
+            self.food = self.random_empty_cell();
+            self.food_kind = Self::roll_food(&self.config, &mut self.rng);
+            self.food_state = FoodState::Whole;
+            if previous / POWER_UP_EVERY != self.score / POWER_UP_EVERY && self.pickup.is_none() {
+                self.spawn_pickup();
+            }
+        }
+        if growing || ate_food {
+            self.take_split(ate_food);
+        }
+        if let Some(pickup) = self.pickup
+            && pickup.cell == next
+            && self.inventory.add(pickup.power_up)
+        {
+            self.pickup = None;
+            self.events.push(GameEvent::PickedUp {
+                cell: next,
+                power_up: pickup.power_up,
+            });
+        }
+    }
+
+    /// Debug cross-check of the incremental occupancy grid against one rebuilt
+    /// from the snake body, logging the full state on any divergence.
+    fn check_occupancy(&self) {
+        if !self.verify_occupancy {
+            return;
+        }
+        let rebuilt = Occupancy::from_cells(self.board_width, self.board_height, &self.snake);
+        let diff = self.occupancy.diff(&rebuilt);
+        if !diff.is_empty() {
+            tracing::error!(
+                "Occupancy divergence at tick {}: cells={:?}, snake={:?}, food={:?}, direction={:?}, board=({}, {})",
+                self.ticks,
+                diff,
+                self.snake,
+                self.food,
+                self.direction,
+                self.board_width,
+                self.board_height
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{Level, food::CRACK_WINDOW};
+
+    fn engine(source: &str) -> Engine {
+        let level = Level::parse("Test", source).expect("test level is valid");
+        let mut engine = Engine::new(&GameConfig::new(BoardLayout::Level(level)), 1);
+        engine.start();
+        engine
+    }
+
+    fn head(engine: &Engine) -> Cell {
+        engine.snake()[0]
+    }
+
+    fn open() -> Engine {
+        let mut engine = Engine::new(&GameConfig::new(BoardLayout::Open), 1);
+        engine.start();
+        engine
+    }
+
+    #[test]
+    fn queued_turns_apply_one_per_tick() {
+        let mut engine = open();
+        engine.queue_direction(Direction::Up);
+        engine.queue_direction(Direction::Left);
+        engine.tick();
+        assert_eq!(engine.direction(), Direction::Up);
+        engine.tick();
+        assert_eq!(engine.direction(), Direction::Left);
+    }
+
+    #[test]
+    fn repeated_and_excess_turns_are_dropped() {
+        let mut engine = open();
+        engine.queue_direction(Direction::Right);
+        assert!(engine.input_queue.is_empty());
+        engine.queue_direction(Direction::Up);
+        engine.queue_direction(Direction::Up);
+        assert_eq!(engine.input_queue.len(), 1);
+        for direction in [Direction::Left, Direction::Down, Direction::Right] {
+            engine.queue_direction(direction);
+        }
+        assert_eq!(
+            engine.input_queue,
+            [Direction::Up, Direction::Left, Direction::Down]
+        );
+    }
+
+    #[test]
+    fn a_reversal_is_skipped_for_the_next_turn() {
+        let mut engine = open();
+        engine.queue_direction(Direction::Left);
+        engine.queue_direction(Direction::Up);
+        engine.tick();
+        assert_eq!(engine.direction(), Direction::Up);
+        assert!(
+            engine
+                .events()
+                .iter()
+                .any(|event| matches!(event, GameEvent::ReversalBlocked))
+        );
+    }
+
+    #[test]
+    fn turns_are_ignored_before_the_run_starts() {
+        let mut engine = Engine::new(&GameConfig::new(BoardLayout::Open), 1);
+        engine.queue_direction(Direction::Up);
+        assert!(engine.input_queue.is_empty());
+    }
+
+    #[test]
+    fn a_wrapping_board_reenters_on_the_opposite_edge() {
+        let mut config = GameConfig::new(BoardLayout::Open);
+        config.wrap = true;
+        let engine = Engine::new(&config, 1);
+        let (right, bottom) = (config.board_width - 1, config.board_height - 1);
+        assert_eq!(
+            engine
+                .resolve_step(Cell { x: right, y: 3 }, Direction::Right)
+                .ok(),
+            Some(Cell { x: 0, y: 3 })
+        );
+        assert_eq!(
+            engine
+                .resolve_step(Cell { x: 0, y: 3 }, Direction::Left)
+                .ok(),
+            Some(Cell { x: right, y: 3 })
+        );
+        assert_eq!(
+            engine.resolve_step(Cell { x: 5, y: 0 }, Direction::Up).ok(),
+            Some(Cell { x: 5, y: bottom })
+        );
+        assert_eq!(
+            engine
+                .resolve_step(Cell { x: 5, y: bottom }, Direction::Down)
+                .ok(),
+            Some(Cell { x: 5, y: 0 })
+        );
+    }
+
+    #[test]
+    fn leaving_a_walled_board_crashes() {
+        let mut engine = open();
+        let right = engine.board_width() - 1;
+        assert!(matches!(
+            engine.resolve_step(Cell { x: right, y: 3 }, Direction::Right),
+            Err(DeathCause::Wall)
+        ));
+        while engine.state() == GameStatus::Running {
+            engine.tick();
+        }
+        assert!(engine.death() == Some(DeathCause::Wall));
+        assert_eq!(head(&engine).x, right);
+    }
+
+    #[test]
+    fn a_wrapping_snake_crosses_the_edge() {
+        let mut config = GameConfig::new(BoardLayout::Open);
+        config.wrap = true;
+        let mut engine = Engine::new(&config, 1);
+        engine.start();
+        for _ in 0..config.board_width {
+            engine.tick();
+        }
+        assert_eq!(engine.state(), GameStatus::Running);
+        assert_eq!(head(&engine), config.spawn.head);
+    }
+
+    /// A hooked snake heading left whose head is one step above its own body.
+    fn hooked(self_cut: bool) -> Engine {
+        let mut config = GameConfig::new(BoardLayout::Open);
+        config.self_cut = self_cut;
+        let mut engine = Engine::new(&config, 1);
+        let cells = [(3, 3), (4, 3), (4, 4), (3, 4), (2, 4), (1, 4)];
+        engine.snake = cells.map(|(x, y)| Cell { x, y }).into();
+        engine.segment_effects = vec![None; cells.len()];
+        engine.occupancy =
+            Occupancy::from_cells(engine.board_width, engine.board_height, &engine.snake);
+        engine.direction = Direction::Left;
+        engine.food = Cell { x: 20, y: 15 };
+        engine.start();
+        engine
+    }
+
+    #[test]
+    fn biting_the_body_cuts_it_in_self_cut_mode() {
+        let mut engine = hooked(true);
+        engine.queue_direction(Direction::Down);
+        engine.tick();
+        assert_eq!(engine.state(), GameStatus::Running);
+        let snake: Vec<Cell> = engine.snake().iter().copied().collect();
+        assert_eq!(
+            snake,
+            [(3, 4), (3, 3), (4, 3), (4, 4)].map(|(x, y)| Cell { x, y })
+        );
+        let pellets: Vec<Cell> = engine.pellets().iter().map(|pellet| pellet.cell).collect();
+        assert_eq!(pellets, [Cell { x: 2, y: 4 }]);
+        assert_eq!(engine.segment_effects.len(), engine.snake().len());
+
+        engine.queue_direction(Direction::Left);
+        engine.tick();
+        assert!(engine.pellets().is_empty());
+        assert_eq!(engine.pellets_eaten, 1);
+        assert_eq!(engine.score(), 0);
+        assert_eq!(engine.snake().len(), 4);
+    }
+
+    #[test]
+    fn biting_the_body_is_fatal_otherwise() {
+        let mut engine = hooked(false);
+        engine.queue_direction(Direction::Down);
+        engine.tick();
+        assert!(engine.death() == Some(DeathCause::SelfCollision));
+    }
+
+    #[test]
+    fn food_falls_until_something_holds_it() {
+        let mut engine = engine(
+            "...........
+             .oo>.......
+             ...........
+             ...........
+             .......#...",
+        );
+        engine.config.gravity = true;
+        engine.food = Cell { x: 7, y: 0 };
+        // Only the food's clock runs; the snake stays where it is.
+        let wait = |engine: &mut Engine, ticks: u64| {
+            for _ in 0..ticks {
+                engine.ticks += 1;
+                engine.advance_effects();
+            }
+        };
+        wait(&mut engine, GRAVITY_EVERY * 2);
+        assert_eq!(engine.food(), Cell { x: 7, y: 2 });
+        wait(&mut engine, GRAVITY_EVERY * 2);
+        assert_eq!(engine.food(), Cell { x: 7, y: 3 });
+        engine.food = Cell { x: 2, y: 0 };
+        wait(&mut engine, GRAVITY_EVERY * 2);
+        assert_eq!(engine.food(), Cell { x: 2, y: 0 });
+    }
+
+    #[test]
+    fn pellets_fade() {
+        let mut engine = hooked(true);
+        engine.config.wrap = true;
+        engine.queue_direction(Direction::Down);
+        engine.tick();
+        for _ in 1..PELLET_LIFETIME {
+            engine.tick();
+        }
+        assert_eq!(engine.pellets().len(), 1);
+        engine.tick();
+        assert_eq!(engine.state(), GameStatus::Running);
+        assert!(engine.pellets().is_empty());
+    }
+
+    /// An open board with tough food right in front of the head.
+    fn tough_ahead() -> (Engine, Cell) {
+        let mut engine = open();
+        assert_eq!(engine.direction(), Direction::Right);
+        let food = head(&engine).offset(Direction::Right);
+        engine.food = food;
+        engine.food_kind = FoodKind::Tough;
+        (engine, food)
+    }
+
+    #[test]
+    fn tough_food_takes_two_passes() {
+        let (mut engine, food) = tough_ahead();
+        let length = engine.snake().len();
+        engine.tick();
+        assert!(engine.food_state() != FoodState::Whole);
+        assert_eq!(engine.snake().len(), length);
+        assert_eq!(engine.score(), 0);
+        for direction in [
+            Direction::Down,
+            Direction::Right,
+            Direction::Right,
+            Direction::Up,
+            Direction::Left,
+            Direction::Left,
+        ] {
+            engine.queue_direction(direction);
+            engine.tick();
+        }
+        assert_eq!(head(&engine), food);
+        assert_eq!(engine.snake().len(), length + 1);
+        assert_eq!(engine.score(), FoodKind::Tough.points());
+        assert!(engine.food_state() == FoodState::Whole);
+    }
+
+    #[test]
+    fn a_wall_that_is_not_fatal_blocks_the_move() {
+        let mut config = GameConfig::new(BoardLayout::Open);
+        config.collisions = config.collisions.toggled(DeathCause::Wall);
+        let mut engine = Engine::new(&config, 1);
+        engine.start();
+        engine.food = Cell { x: 0, y: 0 };
+        let edge = Cell {
+            x: engine.board_width() - 1,
+            y: head(&engine).y,
+        };
+        for _ in 0..engine.board_width() {
+            engine.tick();
+        }
+        assert_eq!(engine.state(), GameStatus::Running);
+        assert_eq!(head(&engine), edge);
+    }
+
+    /// An open board with food of `kind` right in front of the head.
+    fn food_ahead(kind: FoodKind) -> Engine {
+        let mut engine = open();
+        engine.food = head(&engine).offset(Direction::Right);
+        engine.food_kind = kind;
+        engine
+    }
+
+    #[test]
+    fn crossing_a_speed_tier_reports_the_new_tick() {
+        let mut engine = food_ahead(FoodKind::Plain);
+        engine.score = SCORE_PER_LEVEL - 1;
+        let before = engine.tick_delay();
+        engine.tick();
+        let sped_up = engine.events().iter().find_map(|event| match event {
+            GameEvent::SpeedUp { tick_ms } => Some(*tick_ms),
+            _ => None,
+        });
+        assert!(sped_up.is_some_and(|ms| Duration::from_millis(ms) < before));
+        engine.tick();
+        assert!(
+            !engine
+                .events()
+                .iter()
+                .any(|event| matches!(event, GameEvent::SpeedUp { .. }))
+        );
+    }
+
+    #[test]
+    fn a_heavy_meal_grows_a_segment_a_tick() {
+        let mut engine = food_ahead(FoodKind::Heavy);
+        let length = engine.snake().len();
+        for grown in 1..=3 {
+            engine.tick();
+            // Keep the next food out of the way.
+            engine.food = Cell { x: 0, y: 0 };
+            assert_eq!(engine.snake().len(), length + grown);
+        }
+        engine.tick();
+        assert_eq!(engine.snake().len(), length + 3);
+        assert_eq!(engine.segment_effects.len(), engine.snake().len());
+    }
+
+    #[test]
+    fn a_shrink_potion_stops_at_the_minimum_length() {
+        let mut engine = food_ahead(FoodKind::Shrink);
+        let length = engine.snake().len();
+        assert!(length - MIN_LENGTH < SHRINK_BY);
+        engine.tick();
+        assert_eq!(engine.state(), GameStatus::Running);
+        assert_eq!(engine.snake().len(), MIN_LENGTH);
+        assert_eq!(engine.segment_effects.len(), MIN_LENGTH);
+        assert_eq!(engine.score(), 1);
+    }
+
+    #[test]
+    fn a_heavy_meal_can_reach_the_speedrun_goal() {
+        let mut engine = food_ahead(FoodKind::Heavy);
+        engine.config.goal_length = Some(engine.snake().len() + 3);
+        for _ in 0..3 {
+            assert_eq!(engine.state(), GameStatus::Running);
+            engine.tick();
+            engine.food = Cell { x: 0, y: 0 };
+        }
+        assert!(engine.completed());
+        assert_eq!(engine.state(), GameStatus::GameOver);
+        assert!(
+            engine
+                .events()
+                .iter()
+                .any(|event| matches!(event, GameEvent::Completed))
+        );
+        assert_eq!(engine.splits().len(), 1);
+    }
+
+    #[test]
+    fn a_crack_heals_after_its_window() {
+        let (mut engine, _) = tough_ahead();
+        engine.tick();
+        // Stand still rather than steer clear of the food for the whole window.
+        engine.ticks += CRACK_WINDOW - 1;
+        engine.advance_effects();
+        assert!(engine.food_state() != FoodState::Whole);
+        engine.ticks += 1;
+        engine.advance_effects();
+        assert!(engine.food_state() == FoodState::Whole);
+    }
+
+    #[test]
+    fn entering_a_portal_comes_out_of_its_pair() {
+        let mut engine = engine(
+            "........
+             .oo>..1.
+             ........
+             ...1....
+             ........",
+        );
+        engine.tick();
+        engine.tick();
+        engine.tick();
+        assert_eq!(head(&engine), Cell { x: 3, y: 3 });
+        assert_eq!(engine.direction(), Direction::Right);
+        engine.tick();
+        assert_eq!(head(&engine), Cell { x: 4, y: 3 });
+        assert_eq!(engine.state(), GameStatus::Running);
+    }
+
+    #[test]
+    fn walls_and_portals_resolve_the_step() {
+        let engine = engine(
+            "........
+             .oo>.#1.
+             ........
+             ...1....
+             ........",
+        );
+        assert!(matches!(
+            engine.resolve_step(Cell { x: 4, y: 1 }, Direction::Right),
+            Err(DeathCause::Obstacle)
+        ));
+        assert!(matches!(
+            engine.resolve_step(Cell { x: 6, y: 2 }, Direction::Up),
+            Ok(Cell { x: 3, y: 3 })
+        ));
+    }
+
+    #[test]
+    fn a_conveyor_pushes_the_head_one_more_cell() {
+        let mut engine = engine(
+            "........
+             .oo>.R..
+             ........",
+        );
+        engine.tick();
+        engine.tick();
+        assert_eq!(head(&engine), Cell { x: 6, y: 1 });
+        assert_eq!(engine.snake().len(), 3);
+        // The push does not chain: the next tick is a plain step.
+        engine.tick();
+        assert_eq!(head(&engine), Cell { x: 7, y: 1 });
+    }
+
+    #[test]
+    fn a_conveyor_can_push_into_a_wall() {
+        let mut engine = engine(
+            ".....#..
+             .oo>.U..
+             ........",
+        );
+        engine.tick();
+        assert_eq!(engine.state(), GameStatus::Running);
+        engine.tick();
+        assert!(engine.death() == Some(DeathCause::Obstacle));
+    }
+
+    #[test]
+    fn a_conveyor_can_push_back_into_the_body() {
+        let mut engine = engine(
+            "........
+             .oo>.L..
+             ........",
+        );
+        engine.tick();
+        engine.tick();
+        assert!(engine.death() == Some(DeathCause::SelfCollision));
+    }
+
+    #[test]
+    fn a_stepped_engine_only_moves_on_step() {
+        let mut engine = open();
+        engine.set_stepping(true);
+        let start = head(&engine);
+        assert!(!engine.tick());
+        assert_eq!(head(&engine), start);
+        assert!(engine.step());
+        assert_eq!(head(&engine), start.offset(Direction::Right));
+    }
+
+    #[test]
+    fn wall_immunity_holds_the_snake_at_the_wall() {
+        let mut engine = engine(
+            "........
+             .oo>.#..
+             ........",
+        );
+        engine.set_wall_immune(true);
+        engine.tick();
+        engine.tick();
+        assert_eq!(engine.state(), GameStatus::Running);
+        assert_eq!(head(&engine), Cell { x: 4, y: 1 });
+        engine.queue_direction(Direction::Down);
+        engine.tick();
+        assert_eq!(head(&engine), Cell { x: 4, y: 2 });
+    }
+
+    #[test]
+    fn a_gate_lets_the_snake_through_its_way_only() {
+        let mut engine = engine(
+            "........
+             .oo>.r..
+             .....l..",
+        );
+        assert!(matches!(
+            engine.resolve_step(Cell { x: 5, y: 2 }, Direction::Up),
+            Err(DeathCause::Obstacle)
+        ));
+        engine.tick();
+        engine.tick();
+        assert_eq!(head(&engine), Cell { x: 5, y: 1 });
+        engine.queue_direction(Direction::Down);
+        engine.tick();
+        assert!(engine.death() == Some(DeathCause::Obstacle));
+    }
+
+    #[test]
+    fn a_speed_mutation_keeps_the_tick_floor() {
+        let mut engine = open();
+        engine.score = u32::MAX;
+        engine.mutate(Mutation::Speed(MAX_SPEED_BIAS));
+        assert_eq!(engine.tick_delay(), Duration::from_millis(MIN_TICK_MS));
+    }
+}
@@ -0,0 +1,134 @@
+//! file: event.rs
+//! author: Jacob Xie
+//! date: 2026/10/16 15:01:34 Friday
+//! brief:
+
+use std::time::Duration;
+
+use super::{Cell, DeathCause, Direction, PowerUp, Weather, speedrun};
+
+/// Something that happened during a tick. The engine collects these and the
+/// game entity re-emits them, so features subscribe to the stream rather than
+/// hooking into the tick itself.
+#[derive(Clone, Copy)]
+pub enum GameEvent {
+    /// Sent last on every tick.
+    TickAdvanced,
+    FoodEaten {
+        cell: Cell,
+        score: u32,
+        multiplier: u32,
+    },
+    /// Tough food took its first hit and is waiting for a second pass.
+    FoodCracked {
+        cell: Cell,
+    },
+    /// Cracked food went uneaten for too long and is whole again.
+    FoodHealed {
+        cell: Cell,
+    },
+    /// The snake sped up a step.
+    LevelUp {
+        level: u32,
+    },
+    /// The speed curve moved up a tier: ticks are now `tick_ms` long.
+    SpeedUp {
+        tick_ms: u64,
+    },
+    PickedUp {
+        cell: Cell,
+        power_up: PowerUp,
+    },
+    DirectionChanged(Direction),
+    /// A turn straight back into the body was ignored.
+    ReversalBlocked,
+    Activated(PowerUp),
+    ArmorAbsorbed(DeathCause),
+    WeatherStarted(Weather),
+    WeatherCleared,
+    Split {
+        length: usize,
+        elapsed: Duration,
+    },
+    Checkpoint {
+        eaten: usize,
+        elapsed: Duration,
+    },
+    /// A self-cutting snake bit itself and lost `severed` segments.
+    Cut {
+        cell: Cell,
+        severed: usize,
+    },
+    PelletEaten {
+        cell: Cell,
+        score: u32,
+    },
+    /// A shrink potion took `removed` segments off the tail.
+    Shrunk {
+        removed: usize,
+    },
+    Died {
+        cause: DeathCause,
+    },
+    Completed,
+    /// Sent by the game entity, not the engine, once a scored run has ended
+    /// and its summary is up. Whatever a run leaves behind is recorded here.
+    GameOver,
+}
+
+impl GameEvent {
+    /// A plain-language line for the event log, or `None` for routine events
+    /// not worth a line.
+    pub fn describe(self) -> Option<String> {
+        let text = match self {
+            GameEvent::TickAdvanced => return None,
+            GameEvent::FoodEaten {
+                cell,
+                score,
+                multiplier,
+            } if multiplier > 1 => {
+                format!(
+                    "Ate food at {}, score {score}, combo x{multiplier}",
+                    cell.label()
+                )
+            }
+            GameEvent::FoodEaten { cell, score, .. } => {
+                format!("Ate food at {}, score {score}", cell.label())
+            }
+            GameEvent::FoodCracked { cell } => {
+                format!("Cracked the tough food at {}", cell.label())
+            }
+            GameEvent::FoodHealed { cell } => format!("The food at {} healed", cell.label()),
+            GameEvent::PickedUp { cell, power_up } => {
+                format!("Picked up {} at {}", power_up.label(), cell.label())
+            }
+            GameEvent::LevelUp { level } => format!("Sped up to level {level}"),
+            GameEvent::SpeedUp { tick_ms } => format!("Speed tier up: {tick_ms} ms ticks"),
+            GameEvent::DirectionChanged(direction) => format!("Turned {direction:?}"),
+            GameEvent::ReversalBlocked => "Can't reverse straight into the body".into(),
+            GameEvent::Activated(power_up) => format!("Activated {}", power_up.label()),
+            GameEvent::ArmorAbsorbed(cause) => format!("Armor absorbed a crash: {}", cause.label()),
+            GameEvent::WeatherStarted(weather) => weather.banner(),
+            GameEvent::WeatherCleared => "The weather cleared".into(),
+            GameEvent::Split { length, elapsed } => format!(
+                "Split at length {length}: {}",
+                speedrun::timer_text(elapsed)
+            ),
+            GameEvent::Checkpoint { eaten, elapsed } => format!(
+                "Checkpoint at {eaten} food: {}",
+                speedrun::timer_text(elapsed)
+            ),
+            GameEvent::Cut { cell, severed } => {
+                format!("Cut off {severed} segments at {}", cell.label())
+            }
+            GameEvent::PelletEaten { cell, score } => {
+                format!("Ate a pellet at {}, score {score}", cell.label())
+            }
+            GameEvent::Shrunk { removed } => format!("Shrank by {removed} segments"),
+            GameEvent::Died { cause } => format!("Game over: {}", cause.label()),
+            GameEvent::Completed => "Reached the goal length".into(),
+            GameEvent::GameOver => return None,
+        };
+        Some(text)
+    }
+}
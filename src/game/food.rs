@@ -0,0 +1,121 @@
+//! file: food.rs
+//! author: Jacob Xie
+//! date: 2026/10/16 14:40:13 Friday
+//! brief:
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use super::SegmentEffect;
+
+/// Ticks between each cell food falls with gravity on.
+pub const GRAVITY_EVERY: u64 = 6;
+/// Ticks a cracked tough food waits for its second pass before healing.
+pub const CRACK_WINDOW: u64 = 40;
+/// Segments a shrink potion takes off the tail.
+pub const SHRINK_BY: usize = 3;
+/// A shrink potion never leaves the snake shorter than this.
+pub const MIN_LENGTH: usize = 2;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum FoodKind {
+    Plain,
+    Armored,
+    Glowing,
+    /// Has to be passed over twice within `CRACK_WINDOW` ticks to be eaten.
+    Tough,
+    /// Grows the snake by three, one segment a tick.
+    Heavy,
+    /// Takes `SHRINK_BY` segments off the tail instead of growing it.
+    Shrink,
+}
+
+impl FoodKind {
+    pub fn roll(rng: &mut impl Rng) -> Self {
+        match rng.gen_range(0..10) {
+            0 => FoodKind::Armored,
+            1 => FoodKind::Glowing,
+            2 => FoodKind::Tough,
+            3 => FoodKind::Heavy,
+            4 => FoodKind::Shrink,
+            _ => FoodKind::Plain,
+        }
+    }
+
+    /// The effect carried by the segment grown from eating this food.
+    pub fn segment_effect(self) -> Option<SegmentEffect> {
+        match self {
+            FoodKind::Plain | FoodKind::Tough | FoodKind::Heavy | FoodKind::Shrink => None,
+            FoodKind::Armored => Some(SegmentEffect::Armored),
+            FoodKind::Glowing => Some(SegmentEffect::Glowing),
+        }
+    }
+
+    /// Segments the snake grows by after eating this food.
+    pub fn growth(self) -> usize {
+        match self {
+            FoodKind::Heavy => 3,
+            FoodKind::Shrink => 0,
+            _ => 1,
+        }
+    }
+
+    /// Points before multipliers; tough food pays for the extra pass.
+    pub fn points(self) -> u32 {
+        match self {
+            FoodKind::Tough => 2,
+            _ => 1,
+        }
+    }
+
+    /// Shape drawn on special food when shapes are enabled; plain food is a dot.
+    pub fn glyph(self) -> Option<&'static str> {
+        match self {
+            FoodKind::Plain => None,
+            FoodKind::Armored => Some("◆"),
+            FoodKind::Glowing => Some("★"),
+            FoodKind::Tough => Some("■"),
+            FoodKind::Heavy => Some("▲"),
+            FoodKind::Shrink => Some("▼"),
+        }
+    }
+
+    pub fn color(self) -> u32 {
+        match self {
+            FoodKind::Plain => 0xf97316,
+            FoodKind::Armored => 0xcbd5e1,
+            FoodKind::Glowing => 0xfacc15,
+            FoodKind::Tough => 0xa855f7,
+            FoodKind::Heavy => 0xb45309,
+            FoodKind::Shrink => 0xf472b6,
+        }
+    }
+}
+
+/// Where the current food is in being eaten. Only tough food ever cracks.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FoodState {
+    #[default]
+    Whole,
+    /// Hit once; a second pass before `heals_at` eats it.
+    Cracked { heals_at: u64 },
+}
+
+impl FoodState {
+    /// Whether the head reaching food of `kind` in this state eats it.
+    pub fn eaten_by_hit(self, kind: FoodKind) -> bool {
+        kind != FoodKind::Tough || matches!(self, FoodState::Cracked { .. })
+    }
+
+    /// The state after a hit at `tick` that did not eat the food.
+    pub fn crack(tick: u64) -> Self {
+        FoodState::Cracked {
+            heals_at: tick + CRACK_WINDOW,
+        }
+    }
+
+    /// Whether a crack has run out of time by `tick`.
+    pub fn heals_by(self, tick: u64) -> bool {
+        matches!(self, FoodState::Cracked { heals_at } if tick >= heals_at)
+    }
+}
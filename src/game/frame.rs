@@ -0,0 +1,412 @@
+//! file: frame.rs
+//! author: Jacob Xie
+//! date: 2026/10/16 18:12:40 Friday
+//! brief:
+
+use super::{
+    Cell, Engine, FoodKind, FoodState, Palette, PowerUp, RAIN_VISIBILITY, SegmentEffect, Tile,
+    Weather,
+};
+
+/// What one board cell shows, before skins and glyphs are applied.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CellView {
+    /// Hidden by rain.
+    Fog,
+    /// The head, with the effect of the segment it occupies.
+    Head(Option<SegmentEffect>),
+    Food(FoodKind),
+    /// Tough food after its first hit.
+    CrackedFood,
+    Segment(Option<SegmentEffect>),
+    Pickup(PowerUp),
+    Pellet,
+    Tile(Tile),
+}
+
+impl CellView {
+    /// Background color of the cell. With shapes on, food sits on an empty
+    /// cell and carries its color in the marker instead.
+    pub fn color(self, palette: Palette, shapes: bool, ghosting: bool) -> u32 {
+        match self {
+            CellView::Fog => palette.fog(),
+            CellView::Head(_) => palette.head(),
+            CellView::Food(_) | CellView::CrackedFood if shapes => palette.empty(),
+            CellView::Food(kind) => palette.food(kind),
+            CellView::CrackedFood => palette.food(FoodKind::Tough),
+            CellView::Segment(Some(effect)) => palette.segment(effect),
+            CellView::Segment(None) => palette.body(ghosting),
+            CellView::Pickup(power_up) => palette.pickup(power_up),
+            CellView::Pellet => palette.pellet(),
+            CellView::Tile(Tile::Empty) => palette.empty(),
+            CellView::Tile(Tile::Wall) => palette.wall(),
+            CellView::Tile(Tile::Portal(id)) => palette.portal(id),
+            CellView::Tile(Tile::Conveyor(_)) => palette.conveyor(),
+            CellView::Tile(Tile::Gate(_)) => palette.gate(),
+        }
+    }
+}
+
+/// Every cell of the board as the grid draws it for one engine state.
+pub struct Frame {
+    width: i32,
+    ghosting: bool,
+    cells: Vec<CellView>,
+}
+
+impl Frame {
+    pub fn capture(engine: &Engine) -> Self {
+        let width = engine.board_width();
+        let height = engine.board_height();
+        let head = engine.snake().front().copied();
+        let raining = engine.weather() == Some(Weather::Rain);
+        let pickup = engine.pickup();
+        let mut cells: Vec<CellView> = (0..height)
+            .flat_map(|y| (0..width).map(move |x| Cell { x, y }))
+            .map(|cell| CellView::Tile(engine.tiles().get(cell)))
+            .collect();
+        let index = |cell: Cell| (cell.y * width + cell.x) as usize;
+        if let Some(pickup) = pickup {
+            cells[index(pickup.cell)] = CellView::Pickup(pickup.power_up);
+        }
+        for pellet in engine.pellets() {
+            cells[index(pellet.cell)] = CellView::Pellet;
+        }
+        let mut segments = engine.segments();
+        let head_effect = segments.next().and_then(|(_, effect)| effect);
+        for (cell, effect) in segments {
+            cells[index(cell)] = CellView::Segment(effect);
+        }
+        cells[index(engine.food())] = match engine.food_state() {
+            FoodState::Whole => CellView::Food(engine.food_kind()),
+            FoodState::Cracked { .. } => CellView::CrackedFood,
+        };
+        if let Some(head) = head {
+            cells[index(head)] = CellView::Head(head_effect);
+            if raining {
+                for (i, view) in cells.iter_mut().enumerate() {
+                    let (x, y) = (i as i32 % width, i as i32 / width);
+                    if (head.x - x).abs().max((head.y - y).abs()) > RAIN_VISIBILITY {
+                        *view = CellView::Fog;
+                    }
+                }
+            }
+        }
+
+        Self {
+            width,
+            ghosting: engine.has_effect(PowerUp::Ghost),
+            cells,
+        }
+    }
+
+    pub fn view(&self, cell: Cell) -> CellView {
+        self.cells[(cell.y * self.width + cell.x) as usize]
+    }
+
+    pub fn ghosting(&self) -> bool {
+        self.ghosting
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, path::PathBuf};
+
+    use gpui::{Bounds, Pixels, TestAppContext, VisualTestContext};
+    use image::{
+        Rgb, RgbImage,
+        imageops::{self, FilterType},
+    };
+
+    use super::*;
+    use crate::game::{
+        Accessibility, BoardLayout, Direction, GameConfig, GameStatus, Skin, SnakeGame, Sprite,
+    };
+
+    const CELL_PX: f32 = 12.0;
+    /// Largest per-channel difference a pixel may have before it counts as changed.
+    const CHANNEL_TOLERANCE: u8 = 8;
+    /// Share of changed pixels a frame may have before the test fails.
+    const PIXEL_TOLERANCE: f64 = 0.002;
+    const FLAT: usize = 0;
+    const CLASSIC: usize = 1;
+
+    fn rgb_of(color: u32) -> Rgb<u8> {
+        Rgb([(color >> 16) as u8, (color >> 8) as u8, color as u8])
+    }
+
+    /// Every color the palette can paint. The grid tags each element with the
+    /// color it was drawn in, and these are the candidates read back.
+    fn palette_colors(palette: Palette) -> Vec<u32> {
+        let mut colors = vec![
+            palette.board(),
+            palette.empty(),
+            palette.fog(),
+            palette.wall(),
+            palette.head(),
+            palette.body(false),
+            palette.body(true),
+            palette.marker(),
+            palette.pellet(),
+            palette.conveyor(),
+            palette.gate(),
+        ];
+        colors.extend((0..4).map(|id| palette.portal(id)));
+        colors.extend(
+            [
+                FoodKind::Plain,
+                FoodKind::Armored,
+                FoodKind::Glowing,
+                FoodKind::Tough,
+                FoodKind::Heavy,
+                FoodKind::Shrink,
+            ]
+            .map(|kind| palette.food(kind)),
+        );
+        colors.extend(
+            [SegmentEffect::Armored, SegmentEffect::Glowing].map(|effect| palette.segment(effect)),
+        );
+        colors.extend(PowerUp::ALL.map(|power_up| palette.pickup(power_up)));
+        colors.sort_unstable();
+        colors.dedup();
+        colors
+    }
+
+    /// The skin's pictures keyed by the image id the grid tags sprites with.
+    fn skin_sprites(skin: &Skin) -> Vec<(u64, RgbImage)> {
+        let mut sprites: Vec<Sprite> = Direction::ALL.map(Sprite::Head).to_vec();
+        sprites.extend([Sprite::BodyHorizontal, Sprite::BodyVertical]);
+        sprites.extend([
+            Sprite::Corner(Direction::Up, Direction::Right),
+            Sprite::Corner(Direction::Up, Direction::Left),
+            Sprite::Corner(Direction::Down, Direction::Right),
+            Sprite::Corner(Direction::Down, Direction::Left),
+        ]);
+        sprites.extend(
+            [
+                FoodKind::Plain,
+                FoodKind::Armored,
+                FoodKind::Glowing,
+                FoodKind::Tough,
+                FoodKind::Heavy,
+                FoodKind::Shrink,
+            ]
+            .map(Sprite::Food),
+        );
+        sprites
+            .into_iter()
+            .filter_map(|sprite| skin.sprite(sprite))
+            .map(|image| {
+                let decoded = image::load_from_memory(&image.bytes).unwrap().to_rgb8();
+                (image.id(), decoded)
+            })
+            .collect()
+    }
+
+    fn bounds(cx: &mut VisualTestContext, selector: String) -> Option<Bounds<Pixels>> {
+        cx.debug_bounds(selector.leak())
+    }
+
+    /// Bounds and color of the `kind` element the grid drew for cell `x`,`y`.
+    fn tagged(
+        cx: &mut VisualTestContext,
+        kind: &str,
+        x: i32,
+        y: i32,
+        colors: &[u32],
+    ) -> Option<(Bounds<Pixels>, u32)> {
+        colors.iter().find_map(|color| {
+            bounds(cx, format!("{kind} {x},{y} #{color:06x}")).map(|bounds| (bounds, *color))
+        })
+    }
+
+    /// Pixel rectangle of `bounds` relative to the board's top-left corner.
+    fn rect(bounds: Bounds<Pixels>, board: Bounds<Pixels>) -> (u32, u32, u32, u32) {
+        let left = f32::from(bounds.origin.x - board.origin.x).round() as u32;
+        let top = f32::from(bounds.origin.y - board.origin.y).round() as u32;
+        let width = f32::from(bounds.size.width).round() as u32;
+        let height = f32::from(bounds.size.height).round() as u32;
+        (left, top, width, height)
+    }
+
+    fn fill(image: &mut RgbImage, (left, top, width, height): (u32, u32, u32, u32), color: u32) {
+        for y in top..(top + height).min(image.height()) {
+            for x in left..(left + width).min(image.width()) {
+                image.put_pixel(x, y, rgb_of(color));
+            }
+        }
+    }
+
+    /// Draws the game showing `engine` in a gpui test window and rebuilds the
+    /// board from the laid-out bounds of what the grid drew: cell backgrounds,
+    /// shape markers and skin sprites. The test platform has no rasterizer, so
+    /// boxes are filled flat (no rounded corners or glyph outlines) and sprites
+    /// are scaled into their bounds.
+    fn capture(
+        cx: &mut TestAppContext,
+        engine: Engine,
+        accessibility: Accessibility,
+        skin: usize,
+    ) -> RgbImage {
+        let (width, height) = (engine.board_width(), engine.board_height());
+        let (game, cx) = cx.add_window_view(|_, cx| SnakeGame::new(cx));
+        cx.update(|_, app| {
+            game.update(app, |game, cx| {
+                game.engine = engine;
+                game.accessibility = accessibility;
+                game.skin_index = skin;
+                game.cell_px = CELL_PX;
+                cx.notify();
+            })
+        });
+        cx.run_until_parked();
+
+        let board = bounds(cx, "board".into()).expect("the board was not drawn");
+        let colors = palette_colors(accessibility.palette);
+        let cell = |cx: &mut VisualTestContext, x: i32, y: i32| {
+            tagged(cx, "cell", x, y, &colors)
+                .unwrap_or_else(|| panic!("cell {x},{y} was not drawn in a palette color"))
+        };
+        // The board stretches with the window, so the frame ends at the last cell.
+        let (left, top, cell_width, cell_height) = rect(cell(cx, width - 1, height - 1).0, board);
+        let mut image = RgbImage::from_pixel(
+            left + cell_width,
+            top + cell_height,
+            rgb_of(accessibility.palette.board()),
+        );
+        let sprites = skin_sprites(
+            &game.read_with(cx, |game, _| Skin::available().swap_remove(game.skin_index)),
+        );
+        for y in 0..height {
+            for x in 0..width {
+                let (bounds_of_cell, color) = cell(cx, x, y);
+                fill(&mut image, rect(bounds_of_cell, board), color);
+                if let Some((marker, color)) = tagged(cx, "marker", x, y, &colors) {
+                    fill(&mut image, rect(marker, board), color);
+                }
+                if let Some((sprite, picture)) = sprites.iter().find_map(|(id, picture)| {
+                    bounds(cx, format!("sprite {x},{y} {id:x}")).map(|sprite| (sprite, picture))
+                }) {
+                    let (left, top, width, height) = rect(sprite, board);
+                    let scaled = imageops::resize(picture, width, height, FilterType::Nearest);
+                    imageops::replace(&mut image, &scaled, left.into(), top.into());
+                }
+            }
+        }
+        image
+    }
+
+    fn golden_path(name: &str) -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/golden")
+            .join(format!("{name}.png"))
+    }
+
+    /// Compares against `tests/golden/<name>.png`. Run with `UPDATE_GOLDEN=1`
+    /// to rewrite the golden image after an intended visual change.
+    fn assert_golden(name: &str, actual: &RgbImage) {
+        let path = golden_path(name);
+        if env::var_os("UPDATE_GOLDEN").is_some() {
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            actual.save(&path).unwrap();
+            return;
+        }
+        let expected = image::open(&path)
+            .unwrap_or_else(|err| panic!("{}: {err}; run with UPDATE_GOLDEN=1", path.display()))
+            .to_rgb8();
+        let actual_path = env::temp_dir().join(format!("gpui-snake-{name}.png"));
+        if expected.dimensions() != actual.dimensions() {
+            actual.save(&actual_path).unwrap();
+            panic!(
+                "{name}: size {:?} differs from golden {:?}, actual frame at {}",
+                actual.dimensions(),
+                expected.dimensions(),
+                actual_path.display()
+            );
+        }
+        let changed = expected
+            .pixels()
+            .zip(actual.pixels())
+            .filter(|(expected, actual)| {
+                expected
+                    .0
+                    .iter()
+                    .zip(actual.0)
+                    .any(|(e, a)| e.abs_diff(a) > CHANNEL_TOLERANCE)
+            })
+            .count();
+        let share = changed as f64 / expected.pixels().len() as f64;
+        if share > PIXEL_TOLERANCE {
+            actual.save(&actual_path).unwrap();
+            panic!(
+                "{name}: {changed} pixels differ from golden, actual frame at {}",
+                actual_path.display()
+            );
+        }
+    }
+
+    /// Plays a fixed opening so the snake has a bend in it.
+    fn played(config: &GameConfig, seed: u64) -> Engine {
+        let mut engine = Engine::new(config, seed);
+        engine.start();
+        for (tick, direction) in [
+            (3, Direction::Up),
+            (6, Direction::Left),
+            (10, Direction::Down),
+        ] {
+            while engine.ticks() < tick {
+                engine.tick();
+                assert_eq!(engine.state(), GameStatus::Running, "the opening crashed");
+            }
+            engine.queue_direction(direction);
+        }
+        while engine.ticks() < 12 {
+            engine.tick();
+            assert_eq!(engine.state(), GameStatus::Running, "the opening crashed");
+        }
+        engine
+    }
+
+    #[gpui::test]
+    fn opening_board(cx: &mut TestAppContext) {
+        let engine = Engine::new(&GameConfig::new(BoardLayout::Open), 7);
+        let frame = capture(cx, engine, Accessibility::default(), FLAT);
+        assert_golden("opening", &frame);
+    }
+
+    #[gpui::test]
+    fn played_board_with_shapes(cx: &mut TestAppContext) {
+        let engine = played(&GameConfig::new(BoardLayout::Open), 7);
+        let accessibility = Accessibility {
+            palette: Palette::Colorblind,
+            shapes: true,
+            ..Accessibility::default()
+        };
+        let frame = capture(cx, engine, accessibility, FLAT);
+        assert_golden("played-shapes", &frame);
+    }
+
+    #[gpui::test]
+    fn obstacles_high_contrast(cx: &mut TestAppContext) {
+        let layout = BoardLayout::RandomObstacles {
+            walls: 14,
+            portal_pairs: 2,
+        };
+        let engine = played(&GameConfig::new(layout), 12);
+        let accessibility = Accessibility {
+            palette: Palette::HighContrast,
+            grid_lines: false,
+            ..Accessibility::default()
+        };
+        let frame = capture(cx, engine, accessibility, FLAT);
+        assert_golden("obstacles-contrast", &frame);
+    }
+
+    #[gpui::test]
+    fn classic_skin_sprites(cx: &mut TestAppContext) {
+        let engine = played(&GameConfig::new(BoardLayout::Open), 7);
+        let frame = capture(cx, engine, Accessibility::default(), CLASSIC);
+        assert_golden("classic-skin", &frame);
+    }
+}
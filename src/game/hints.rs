@@ -0,0 +1,84 @@
+//! file: hints.rs
+//! author: Jacob Xie
+//! date: 2026/10/16 18:23:34 Friday
+//! brief:
+
+use std::time::Duration;
+
+use super::Profile;
+
+/// How long each hint stays up on the Ready screen.
+pub const HINT_EVERY: Duration = Duration::from_secs(5);
+
+const TIPS: [&str; 8] = [
+    "Tip: Tab cycles through the boards",
+    "Tip: a turn queued before the tick still counts",
+    "Tip: Shift+P opens practice mode to learn a layout",
+    "Tip: Shift+D has drills for tight spots",
+    "Tip: T starts today's daily challenge",
+    "Tip: E turns weather on for rain and wind",
+    "Tip: Shift+A tunes the speed to your recent runs",
+    "Tip: the timeline on the game-over screen scrubs through the run",
+];
+
+/// Gameplay tips and the player's own numbers, shown one at a time while a
+/// run waits to start.
+#[derive(Default)]
+pub struct Hints {
+    index: usize,
+}
+
+impl Hints {
+    pub fn advance(&mut self) {
+        self.index = self.index.wrapping_add(1);
+    }
+
+    /// The hint to show now. Stats are mixed in between the tips once there
+    /// is a finished run to talk about.
+    pub fn current(&self, profile: &Profile, best: u32) -> String {
+        let mut hints: Vec<String> = TIPS.iter().map(|tip| tip.to_string()).collect();
+        if profile.runs() > 0 {
+            let stats = [
+                format!("Your best so far: {best}"),
+                format!("Runs played: {}", profile.runs()),
+                format!("Food eaten across all runs: {}", profile.food()),
+            ];
+            for (index, stat) in stats.into_iter().enumerate() {
+                hints.insert(index * 3 + 1, stat);
+            }
+        }
+        hints.swap_remove(self.index % hints.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_players_only_see_tips() {
+        let profile = Profile::default();
+        let mut hints = Hints::default();
+        for _ in 0..TIPS.len() * 2 {
+            assert!(hints.current(&profile, 0).starts_with("Tip:"));
+            hints.advance();
+        }
+    }
+
+    #[test]
+    fn stats_join_the_rotation_after_a_run() {
+        let profile: Profile =
+            serde_json::from_str(r#"{"runs":3,"food":4,"best_score":12,"titles":{}}"#).unwrap();
+        let mut hints = Hints::default();
+        let shown: Vec<String> = (0..TIPS.len() + 3)
+            .map(|_| {
+                let hint = hints.current(&profile, 12);
+                hints.advance();
+                hint
+            })
+            .collect();
+        assert_eq!(shown[0], TIPS[0]);
+        assert_eq!(shown[1], "Your best so far: 12");
+        assert!(shown.contains(&"Food eaten across all runs: 4".to_string()));
+    }
+}
@@ -0,0 +1,99 @@
+//! file: juice.rs
+//! author: Jacob Xie
+//! date: 2026/10/16 15:03:21 Friday
+//! brief:
+
+use std::time::{Duration, Instant};
+
+/// How often the frame timer redraws while an effect is playing.
+pub const FRAME: Duration = Duration::from_millis(16);
+/// Combo multiplier from which eating shakes the board.
+pub const BIG_COMBO: u32 = 3;
+const FLASH_ALPHA: f32 = 0.45;
+
+/// A brief board shake with a fading color flash, played on death and on big
+/// combos. Runs on wall-clock time so it stays smooth whatever the tick rate.
+#[derive(Clone, Copy)]
+pub struct Juice {
+    started: Instant,
+    duration: Duration,
+    strength: f32,
+    color: u32,
+}
+
+impl Juice {
+    pub fn death() -> Self {
+        Self {
+            started: Instant::now(),
+            duration: Duration::from_millis(450),
+            strength: 9.0,
+            color: 0xef4444,
+        }
+    }
+
+    pub fn combo(multiplier: u32) -> Self {
+        Self {
+            started: Instant::now(),
+            duration: Duration::from_millis(250),
+            strength: multiplier as f32,
+            color: 0xfbbf24,
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.started.elapsed() < self.duration
+    }
+
+    /// How much of the effect is left, from 1.0 when it starts down to 0.0.
+    fn fade(&self) -> f32 {
+        1.0 - (self.started.elapsed().as_secs_f32() / self.duration.as_secs_f32()).min(1.0)
+    }
+
+    /// Board offset in pixels for the current frame: a wobble that settles as
+    /// the effect fades.
+    pub fn offset(&self) -> (f32, f32) {
+        let t = self.started.elapsed().as_secs_f32();
+        let amplitude = self.strength * self.fade();
+        ((t * 90.0).sin() * amplitude, (t * 70.0).cos() * amplitude)
+    }
+
+    /// The flash overlay as an RGBA color for the current frame.
+    pub fn flash(&self) -> u32 {
+        let alpha = (FLASH_ALPHA * self.fade() * 255.0) as u32;
+        self.color << 8 | alpha
+    }
+}
+
+/// A brief glow over the whole snake, played when the speed moves up a tier.
+#[derive(Clone, Copy)]
+pub struct Pulse {
+    started: Instant,
+}
+
+impl Pulse {
+    const DURATION: Duration = Duration::from_millis(600);
+    const COLOR: u32 = 0xfef08a;
+
+    pub fn speed_up() -> Self {
+        Self {
+            started: Instant::now(),
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.started.elapsed() < Self::DURATION
+    }
+
+    /// `color` blended toward the pulse color: it swells in and fades out
+    /// over the pulse.
+    pub fn tint(&self, color: u32) -> u32 {
+        let t = (self.started.elapsed().as_secs_f32() / Self::DURATION.as_secs_f32()).min(1.0);
+        let amount = (t * std::f32::consts::PI).sin() * 0.8;
+        let channel = |shift: u32| {
+            let from = (color >> shift & 0xff) as f32;
+            let to = (Self::COLOR >> shift & 0xff) as f32;
+            ((from + (to - from) * amount).round() as u32) << shift
+        };
+        channel(16) | channel(8) | channel(0)
+    }
+}
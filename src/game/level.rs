@@ -0,0 +1,344 @@
+//! file: level.rs
+//! author: Jacob Xie
+//! date: 2026/10/16 14:29:30 Friday
+//! brief:
+
+use std::{fs, io, path::PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use super::{Cell, Direction, Spawn, Tile, TileMap, storage};
+
+/// Folder under the data directory holding level files, one `<name>.txt` each.
+const LEVELS_DIR: &str = "levels";
+
+const PILLARS: &str = "\
+........................
+........................
+..##................##..
+..##................##..
+........................
+........................
+......1........2........
+........................
+.....###......###.......
+........................
+........................
+........................
+.....###......###.......
+........................
+......2........1........
+........................
+..##................##..
+..##................##..
+........................
+........................";
+
+const BOX: &str = "\
+###########..###########
+#......................#
+#......................#
+#......................#
+#.....1................#
+#......................#
+#......................#
+#.......########.......#
+#......................#
+#......................#
+2......................2
+#......................#
+#......................#
+#.......########.......#
+#......................#
+#......................#
+#................1.....#
+#......................#
+#......................#
+###########..###########";
+
+const BELTS: &str = "\
+........................
+........................
+...RRRRRRRR.............
+........................
+....D..............U....
+....D..............U....
+....D..............U....
+....D..............U....
+....D..............U....
+........................
+........................
+........................
+....D..............U....
+....D..............U....
+....D..............U....
+....D..............U....
+....D..............U....
+.............LLLLLLLL...
+........................
+........................";
+
+const GATES: &str = "\
+........................
+........................
+........................
+........................
+........................
+........................
+#####d############u#####
+........................
+........................
+........................
+........................
+........................
+........................
+#####u############d#####
+........................
+........................
+........................
+........................
+........................
+........................";
+
+/// A fixed board layout. In the text form `#` is a wall, `.` is empty, each
+/// digit marks one end of a portal pair, `U`, `D`, `L` or `R` is a conveyor
+/// pushing that way and `u`, `d`, `l` or `r` is a gate letting the snake
+/// through only that way. An optional spawn is drawn as
+/// the head (`^`, `v`, `<` or `>`, pointing the way it heads) followed by a
+/// straight trail of `o` body cells.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Level {
+    name: String,
+    tiles: TileMap,
+    spawn: Option<Spawn>,
+}
+
+impl Level {
+    pub fn parse(name: &str, source: &str) -> Result<Self, String> {
+        let rows: Vec<&str> = source
+            .lines()
+            .map(str::trim)
+            .filter(|row| !row.is_empty())
+            .collect();
+        let height = rows.len() as i32;
+        let width = rows.first().map_or(0, |row| row.chars().count()) as i32;
+        if width == 0 {
+            return Err(format!("level {name} is empty"));
+        }
+
+        let mut tiles = TileMap::new(width, height);
+        let mut portal_ends = [0u8; 10];
+        let mut head = None;
+        let mut body = Vec::new();
+        for (y, row) in rows.iter().enumerate() {
+            if row.chars().count() as i32 != width {
+                return Err(format!("level {name}: row {y} is not {width} cells wide"));
+            }
+            for (x, ch) in row.chars().enumerate() {
+                let cell = Cell {
+                    x: x as i32,
+                    y: y as i32,
+                };
+                let tile = match ch {
+                    '.' => Tile::Empty,
+                    '^' | 'v' | '<' | '>' => {
+                        if head.is_some() {
+                            return Err(format!("level {name}: more than one snake head"));
+                        }
+                        let direction = match ch {
+                            '^' => Direction::Up,
+                            'v' => Direction::Down,
+                            '<' => Direction::Left,
+                            _ => Direction::Right,
+                        };
+                        head = Some((cell, direction));
+                        Tile::Empty
+                    }
+                    'o' => {
+                        body.push(cell);
+                        Tile::Empty
+                    }
+                    '#' => Tile::Wall,
+                    'U' => Tile::Conveyor(Direction::Up),
+                    'D' => Tile::Conveyor(Direction::Down),
+                    'L' => Tile::Conveyor(Direction::Left),
+                    'R' => Tile::Conveyor(Direction::Right),
+                    'u' => Tile::Gate(Direction::Up),
+                    'd' => Tile::Gate(Direction::Down),
+                    'l' => Tile::Gate(Direction::Left),
+                    'r' => Tile::Gate(Direction::Right),
+                    '0'..='9' => {
+                        let id = ch as u8 - b'0';
+                        portal_ends[id as usize] += 1;
+                        Tile::Portal(id)
+                    }
+                    other => {
+                        return Err(format!(
+                            "level {name}: unknown tile {other:?} at ({x}, {y})"
+                        ));
+                    }
+                };
+                tiles.set(cell, tile);
+            }
+        }
+
+        if let Some(id) = portal_ends.iter().position(|&ends| ends != 0 && ends != 2) {
+            return Err(format!(
+                "level {name}: portal {id} must appear exactly twice"
+            ));
+        }
+
+        let spawn = match head {
+            Some((head, direction)) => {
+                let back = direction.opposite();
+                let length = 1 + std::iter::successors(Some(head.offset(back)), |cell| {
+                    Some(cell.offset(back))
+                })
+                .take_while(|cell| body.contains(cell))
+                .count();
+                if length != body.len() + 1 {
+                    return Err(format!(
+                        "level {name}: body cells must trail straight behind the head"
+                    ));
+                }
+                let spawn = Spawn {
+                    head,
+                    length,
+                    direction,
+                };
+                spawn
+                    .validate(&tiles)
+                    .map_err(|err| format!("level {name}: {err}"))?;
+                Some(spawn)
+            }
+            None if !body.is_empty() => {
+                return Err(format!("level {name}: body cells without a head"));
+            }
+            None => None,
+        };
+
+        Ok(Self {
+            name: name.to_string(),
+            tiles,
+            spawn,
+        })
+    }
+
+    pub fn new(name: &str, tiles: TileMap) -> Self {
+        Self {
+            name: name.to_string(),
+            tiles,
+            spawn: None,
+        }
+    }
+
+    pub fn with_spawn(mut self, spawn: Option<Spawn>) -> Self {
+        self.spawn = spawn;
+        self
+    }
+
+    /// The level in the text form accepted by [`Level::parse`].
+    pub fn to_text(&self) -> String {
+        (0..self.tiles.height())
+            .map(|y| {
+                (0..self.tiles.width())
+                    .map(|x| {
+                        let cell = Cell { x, y };
+                        match self.spawn {
+                            Some(spawn) if spawn.head == cell => match spawn.direction {
+                                Direction::Up => '^',
+                                Direction::Down => 'v',
+                                Direction::Left => '<',
+                                Direction::Right => '>',
+                            },
+                            Some(spawn) if spawn.cells().any(|body| body == cell) => 'o',
+                            _ => match self.tiles.get(cell) {
+                                Tile::Empty => '.',
+                                Tile::Wall => '#',
+                                Tile::Portal(id) => char::from(b'0' + id),
+                                Tile::Conveyor(Direction::Up) => 'U',
+                                Tile::Conveyor(Direction::Down) => 'D',
+                                Tile::Conveyor(Direction::Left) => 'L',
+                                Tile::Conveyor(Direction::Right) => 'R',
+                                Tile::Gate(Direction::Up) => 'u',
+                                Tile::Gate(Direction::Down) => 'd',
+                                Tile::Gate(Direction::Left) => 'l',
+                                Tile::Gate(Direction::Right) => 'r',
+                            },
+                        }
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    pub fn builtin() -> Vec<Level> {
+        [
+            ("Pillars", PILLARS),
+            ("Box", BOX),
+            ("Belts", BELTS),
+            ("Gates", GATES),
+        ]
+        .into_iter()
+        .map(|(name, source)| Self::parse(name, source).expect("builtin level is valid"))
+        .collect()
+    }
+
+    /// Level files installed under the data directory's `levels` folder,
+    /// named after the file. Files that fail to parse are skipped.
+    pub fn installed() -> Vec<Level> {
+        let Ok(entries) = fs::read_dir(storage::shared_path(LEVELS_DIR)) else {
+            return Vec::new();
+        };
+        let mut paths: Vec<PathBuf> = entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "txt"))
+            .collect();
+        paths.sort();
+        paths
+            .into_iter()
+            .filter_map(|path| {
+                let name = path.file_stem()?.to_string_lossy().into_owned();
+                let parsed = fs::read_to_string(&path)
+                    .map_err(|err| err.to_string())
+                    .and_then(|source| Self::parse(&name, &source));
+                parsed
+                    .inspect_err(|err| tracing::warn!("Failed to load level {name}: {err}"))
+                    .ok()
+            })
+            .collect()
+    }
+
+    /// Where the level file for `name` lives.
+    pub fn file_path(name: &str) -> PathBuf {
+        storage::shared_path(LEVELS_DIR).join(format!("{name}.txt"))
+    }
+
+    /// Writes the level where [`Level::installed`] finds it, never replacing
+    /// an existing file.
+    pub fn install(&self) -> io::Result<()> {
+        let path = Self::file_path(&self.name);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(path)
+            .and_then(|mut file| io::Write::write_all(&mut file, self.to_text().as_bytes()))
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn tiles(&self) -> &TileMap {
+        &self.tiles
+    }
+
+    pub fn spawn(&self) -> Option<Spawn> {
+        self.spawn
+    }
+}
@@ -4,29 +4,182 @@
 //! brief:
 
 use std::{
-    collections::{HashSet, VecDeque},
-    time::Duration,
+    cell::RefCell,
+    collections::{HashMap, VecDeque},
+    rc::Rc,
+    time::{Duration, Instant},
 };
 
 use gpui::{
-    Context, FocusHandle, Focusable, Render, Window, actions, div, prelude::*, px, rgb, rgba,
+    Animation, AnimationExt, AnyElement, App, BorderStyle, Bounds, ClickEvent, ClipboardItem,
+    Context, Div, ElementId, EventEmitter, FocusHandle, Focusable, KeyContext, KeyDownEvent,
+    Keystroke, MouseButton, MouseDownEvent, MouseMoveEvent, Pixels, Point, Render, SharedString,
+    Stateful, Task, Timer, Window, actions, canvas, div, ease_out_quint, fill, img, outline, point,
+    prelude::*, px, rgb, rgba, size,
 };
-use rand::{Rng, SeedableRng, rngs::StdRng};
 
+mod accessibility;
+mod adaptive;
+mod archive;
+mod audio;
+mod autopilot;
+mod board_size;
+mod bot;
+mod camera;
 mod cell;
+mod combo;
+mod config;
+mod controls;
+mod daily;
+mod death;
 mod direction;
+mod drills;
+mod editor;
+mod engine;
+mod event;
+mod food;
+mod frame;
+mod hints;
+mod juice;
+mod level;
+mod modal;
+mod mutation;
+mod net;
+mod occupancy;
+mod pace;
+mod pacing;
+mod palette;
+mod pellet;
+mod perf;
+mod placement;
+mod players;
+mod position;
+mod powerup;
+mod practice;
+mod privacy;
+mod proximity;
+mod replay;
+mod retention;
+mod rules;
+mod save;
+mod screen;
+mod scripting;
+mod segment;
+mod simulation;
+mod skin;
+mod spawn;
+mod spectator;
+mod speed;
+mod speedrun;
+mod stats;
 mod status;
+mod steering;
+mod storage;
+mod summary;
+mod telemetry;
+mod theme;
+mod tiles;
+mod training;
+mod tutorial;
+mod versus;
+mod weather;
 
+pub use accessibility::Accessibility;
+pub use adaptive::{AdaptiveTracker, Difficulty};
+pub use archive::ReplayArchive;
+pub use audio::{Audio, Cue};
+pub use board_size::BoardSize;
+pub use bot::{BotCommand, BotInput, BotLink};
+pub use camera::{Camera, VIEW_HEIGHT, VIEW_WIDTH};
 pub use cell::Cell;
+pub use combo::Combo;
+pub use config::{BoardLayout, GameConfig};
+pub use controls::ControlProfile;
+pub use daily::{DailyChallenge, DailyHistory, DailyResult};
+pub use death::DeathCause;
 pub use direction::Direction;
+pub use drills::{Drill, DrillRecords, DrillRun};
+pub use editor::{Brush, Editor};
+pub use engine::Engine;
+pub use event::GameEvent;
+pub use food::{FoodKind, FoodState, GRAVITY_EVERY, MIN_LENGTH, SHRINK_BY};
+pub use frame::{CellView, Frame};
+pub use hints::{HINT_EVERY, Hints};
+pub use juice::{BIG_COMBO, FRAME, Juice, Pulse};
+pub use level::Level;
+pub use modal::{Modal, OpenModal};
+pub use mutation::{MAX_EXTRA_FOOD, MAX_SPEED_BIAS, Mutation};
+pub use net::{DEFAULT_PORT, Role};
+pub use occupancy::Occupancy;
+pub use pace::{PaceRecords, PaceReference};
+pub use pacing::TickPacer;
+pub use palette::Palette;
+pub use pellet::{PELLET_LIFETIME, PELLETS_PER_POINT, Pellet};
+pub use perf::{FRAME_BUDGET, FrameMeter};
+pub use placement::WindowPlacement;
+pub use players::Roster;
+pub use position::Position;
+pub use powerup::{ActiveEffect, Inventory, PICKUP_LIFETIME, POWER_UP_EVERY, Pickup, PowerUp};
+pub use practice::PracticeDrill;
+pub use privacy::Privacy;
+pub use proximity::{DANGER_MOVES, PING_EVERY, Proximity};
+pub use replay::{Input, Mark, Replay, ReplayPlayer, Timeline};
+pub use retention::Retention;
+pub use rules::Collisions;
+pub use save::SaveGame;
+pub use screen::Screen;
+pub use scripting::Mutators;
+pub use segment::{GLOW_BONUS, SegmentEffect};
+pub use simulation::{Policy, SimulationResult, simulate};
+pub use skin::{Skin, Sprite};
+pub use spawn::Spawn;
+pub use spectator::Spectator;
+pub use speed::{SpeedCurve, SpeedSetting};
+pub use speedrun::{CHECKPOINT_EVERY, SPEEDRUN_GOAL, SPLIT_EVERY, SplitRecords};
+pub use stats::{Profile, RunStats, Title};
 pub use status::GameStatus;
+pub use steering::Turn;
+pub use summary::RunSummary;
+pub use telemetry::init_logging;
+pub use theme::{GameMode, ThemeOverrides};
+pub use tiles::{Tile, TileMap};
+pub use training::Training;
+pub use tutorial::Tutorial;
+pub use versus::{Versus, VersusStatus};
+pub use weather::{RAIN_VISIBILITY, WIND_EVERY, Weather, WeatherEvent};
 
 pub const GRID_WIDTH: i32 = 24;
 pub const GRID_HEIGHT: i32 = 20;
+/// Board size of the large option, scrolled through a camera.
+pub const LARGE_WIDTH: i32 = 120;
+pub const LARGE_HEIGHT: i32 = 100;
+/// Board size of the giant option, shown whole as a renderer stress test.
+pub const GIANT_WIDTH: i32 = 100;
+pub const GIANT_HEIGHT: i32 = 80;
 pub const CELL_SIZE: f32 = 26.0;
 pub const BASE_TICK_MS: u64 = 150;
 pub const MIN_TICK_MS: u64 = 70;
 pub const SPEED_STEP_MS: u64 = 4;
+/// Points per speed level.
+pub const SCORE_PER_LEVEL: u32 = 4;
+/// Scores plotted by the speed curve preview in settings.
+const SPEED_PREVIEW_SCORE: u32 = 120;
+
+const EVENT_LOG_LEN: usize = 8;
+/// Space between board cells while grid lines are on.
+const GRID_GAP: f32 = 4.0;
+/// Side of one board cell on the mini-map.
+const MINIMAP_CELL: f32 = 1.5;
+/// Kept replays listed on the profile page, newest first.
+const RECENT_REPLAYS: usize = 5;
+/// How long the Ready screen sits untouched before the demo game starts.
+const ATTRACT_IDLE: Duration = Duration::from_secs(10);
+/// Side of a cell on the editor grid, smaller than in play so large boards fit.
+const EDITOR_CELL: f32 = 18.0;
+/// Width of the run timeline on the summary screen.
+const TIMELINE_WIDTH: f32 = 360.0;
+/// Ticks between autosaves of a live run, so a crash loses little of it.
+const AUTOSAVE_EVERY: u64 = 100;
 
 actions!(
     snake,
@@ -34,257 +187,3045 @@ actions!(
         MoveUp,
         MoveDown,
         MoveLeft,
+        TurnLeft,
+        TurnRight,
         MoveRight,
         TogglePause,
         RestartGame,
-        QuitGame
+        WatchReplay,
+        MainMenu,
+        CycleLayout,
+        ToggleWeather,
+        ToggleSpeedrun,
+        ToggleSelfCut,
+        ToggleGravity,
+        ToggleMirror,
+        ToggleAdaptive,
+        CycleTheme,
+        ToggleEditor,
+        ToggleBoardSize,
+        CyclePace,
+        CycleSpeedCurve,
+        TuneSpeedCurve,
+        TogglePracticeMode,
+        PracticeFaster,
+        PracticeSlower,
+        ToggleFreeze,
+        StepTick,
+        ToggleWallImmunity,
+        ExportPosition,
+        ImportPosition,
+        ShowDaily,
+        ShowDrills,
+        OpenSpectator,
+        ShowProfile,
+        ShowLobby,
+        StartTutorial,
+        StartDaily,
+        PracticePosition,
+        SaveRun,
+        ActivateSlot1,
+        ActivateSlot2,
+        ResumeRun,
+        RequestQuit,
+        DismissResume,
+        CancelQuit,
+        QuitGame,
+        ForceQuit
     ]
 );
 
 pub struct SnakeGame {
-    board_width: i32,
-    board_height: i32,
-    snake: VecDeque<Cell>,
-    direction: Direction,
-    next_direction: Direction,
-    food: Cell,
-    rng: StdRng,
-    state: GameStatus,
-    score: u32,
+    engine: Engine,
+    layouts: Vec<BoardLayout>,
+    layout_index: usize,
+    screen: Screen,
+    summary: Option<RunSummary>,
+    replay: Option<ReplayPlayer>,
+    attract: Option<Engine>,
+    /// The level being drawn, kept while it is play-tested.
+    editor: Option<Editor>,
+    last_activity: Instant,
+    /// Set when losing focus paused the run, until the window is back in front.
+    needs_attention: bool,
+    /// The title last given to the OS window, so it is only set on change.
+    window_title: String,
+    /// Where the window is, saved on quit so the next launch opens there.
+    placement: Option<WindowPlacement>,
+    roster: Roster,
     high_score: u32,
+    /// Best score when the current run started, the mark a new record has to beat.
+    run_best: u32,
+    split_records: SplitRecords,
+    daily_history: DailyHistory,
+    profile: Profile,
+    replay_archive: ReplayArchive,
+    pace_reference: PaceReference,
+    pace_records: PaceRecords,
+    /// Scores the live run has reached and when, raced by later runs.
+    pace_timeline: Vec<(Duration, u32)>,
+    /// Recent results adaptive mode tunes free-play runs from.
+    adaptive: AdaptiveTracker,
+    /// Board accents the player picked per mode.
+    themes: ThemeOverrides,
+    retention: Retention,
+    privacy: Privacy,
+    /// Bytes under the data directory, refreshed whenever retention runs.
+    storage_used: u64,
+    /// The free-play board to return to once a daily challenge or tutorial
+    /// run is over.
+    free_play: Option<GameConfig>,
+    practice: Option<PracticeDrill>,
+    /// An exercise from the drills library being played.
+    drill: Option<DrillRun>,
+    drill_records: DrillRecords,
+    /// Practice mode, which unlike a drill is an open-ended unscored run.
+    training: Option<Training>,
+    tutorial: Option<Tutorial>,
+    versus: Option<Versus>,
+    /// Address typed on the lobby screen, used to join a versus match.
+    net_address: String,
+    event_log: VecDeque<String>,
+    juice: Option<Juice>,
+    /// Scripts from the scripts folder that tweak free-play runs.
+    mutators: Mutators,
+    /// An external program playing the live run, when one is attached.
+    bot: Option<BotLink>,
+    /// Tips and stats rotated on the Ready screen.
+    hints: Hints,
+    /// Rotates the hints while a run waits to start, dropped once it does.
+    hint_timer: Option<Task<()>>,
+    /// Glow over the snake after the speed moves up a tier.
+    pulse: Option<Pulse>,
+    audio: Audio,
     focus_handle: FocusHandle,
-    base_tick_ms: u64,
-    min_tick_ms: u64,
     cell_px: f32,
+    camera: Camera,
+    /// Size of free-play boards.
+    board_size: BoardSize,
+    /// Recent frame times, shared with the giant board's paint callback.
+    frame_meter: Rc<RefCell<FrameMeter>>,
+    controls: ControlProfile,
+    accessibility: Accessibility,
+    skins: Vec<Skin>,
+    skin_index: usize,
+    modal: Option<OpenModal>,
+    has_save: bool,
+    /// Whether the save on disk holds the live run.
+    run_saved: bool,
 }
 
 impl SnakeGame {
     pub fn new(cx: &mut Context<Self>) -> Self {
         let focus_handle = cx.focus_handle();
-        let mut rng = StdRng::from_entropy();
-        let board_width = GRID_WIDTH;
-        let board_height = GRID_HEIGHT;
-        let snake = Self::build_initial_snake(board_width, board_height);
-        let food = Self::random_food(&snake, &mut rng, board_width, board_height);
-
-        Self {
-            board_width,
-            board_height,
-            snake,
-            direction: Direction::Right,
-            next_direction: Direction::Right,
-            food,
-            rng,
-            state: GameStatus::Ready,
-            score: 0,
-            high_score: 0,
+        cx.subscribe_self(Self::log_event).detach();
+        cx.subscribe_self(Self::record_high_score).detach();
+        cx.subscribe_self(Self::record_profile).detach();
+        cx.subscribe_self(Self::record_splits).detach();
+        cx.subscribe_self(Self::record_daily).detach();
+        cx.subscribe_self(Self::clear_finished_save).detach();
+        cx.subscribe_self(Self::archive_replay).detach();
+        cx.subscribe_self(Self::track_pace).detach();
+        cx.subscribe_self(Self::record_pace).detach();
+        cx.subscribe_self(Self::record_adaptive).detach();
+        cx.subscribe_self(Self::run_mutators).detach();
+        cx.subscribe_self(Self::play_juice).detach();
+        cx.subscribe_self(Self::play_cues).detach();
+        cx.subscribe_self(Self::advance_tutorial).detach();
+        cx.on_app_quit(|this, _| {
+            this.autosave();
+            if let Some(Err(err)) = this.placement.as_ref().map(WindowPlacement::store) {
+                tracing::warn!("Failed to save window placement: {err}");
+            }
+            async {}
+        })
+        .detach();
+        let layouts = BoardLayout::available();
+        let config = GameConfig::new(layouts[0].clone());
+        let profile = Profile::load();
+
+        let mut game = Self {
+            engine: Engine::new(&config, rand::random()),
+            layouts,
+            layout_index: 0,
+            screen: Screen::Play,
+            summary: None,
+            replay: None,
+            attract: None,
+            editor: None,
+            last_activity: Instant::now(),
+            needs_attention: false,
+            window_title: String::new(),
+            placement: None,
+            roster: Roster::load(),
+            high_score: profile.best_score(false),
+            run_best: 0,
+            split_records: SplitRecords::load(),
+            daily_history: DailyHistory::load(),
+            profile,
+            replay_archive: ReplayArchive::load(),
+            pace_reference: PaceReference::load(),
+            pace_records: PaceRecords::load(),
+            pace_timeline: Vec::new(),
+            adaptive: AdaptiveTracker::load(),
+            themes: ThemeOverrides::load(),
+            retention: Retention::load(),
+            privacy: Privacy::load(),
+            storage_used: 0,
+            free_play: None,
+            practice: None,
+            drill: None,
+            drill_records: DrillRecords::load(),
+            training: None,
+            tutorial: None,
+            versus: None,
+            net_address: format!("127.0.0.1:{DEFAULT_PORT}"),
+            event_log: VecDeque::with_capacity(EVENT_LOG_LEN),
+            juice: None,
+            mutators: Mutators::load(),
+            bot: None,
+            hints: Hints::default(),
+            hint_timer: None,
+            pulse: None,
+            audio: Audio::open(),
             focus_handle,
-            base_tick_ms: BASE_TICK_MS,
-            min_tick_ms: MIN_TICK_MS,
             cell_px: CELL_SIZE,
+            camera: Camera::default(),
+            board_size: BoardSize::default(),
+            frame_meter: Rc::default(),
+            controls: ControlProfile::load(),
+            accessibility: Accessibility::load(),
+            skins: Skin::available(),
+            skin_index: 0,
+            modal: None,
+            has_save: SaveGame::exists(),
+            run_saved: false,
+        };
+        game.apply_retention();
+        game.apply_adaptive();
+        if SaveGame::interrupted() {
+            game.open_modal(Modal::ResumePrompt);
         }
+        game
     }
 
-    fn build_initial_snake(width: i32, height: i32) -> VecDeque<Cell> {
-        let mut body = VecDeque::new();
-        let start_x = width / 2;
-        let start_y = height / 2;
-        for offset in 0..4 {
-            body.push_back(Cell {
-                x: start_x - offset,
-                y: start_y,
-            });
+    /// The engine currently shown on the board: the replay while one is playing,
+    /// the attract-mode demo while idle, the live run otherwise.
+    fn displayed_engine(&self) -> &Engine {
+        if let Some(local) = self.versus.as_ref().and_then(Versus::local) {
+            return local;
+        }
+        if self.screen == Screen::Summary
+            && let Some(preview) = self
+                .summary
+                .as_ref()
+                .and_then(|summary| summary.timeline.preview())
+        {
+            return preview;
+        }
+        match (&self.replay, &self.attract) {
+            (Some(player), _) => player.engine(),
+            (None, Some(attract)) => attract,
+            (None, None) => &self.engine,
         }
-        body
     }
 
-    fn random_food(snake: &VecDeque<Cell>, rng: &mut StdRng, width: i32, height: i32) -> Cell {
-        loop {
-            let cell = Cell {
-                x: rng.gen_range(0..width),
-                y: rng.gen_range(0..height),
-            };
-            if !snake.contains(&cell) {
-                return cell;
+    /// Any key press or click counts as activity and dismisses the demo game.
+    fn note_activity(&mut self, cx: &mut Context<Self>) {
+        self.last_activity = Instant::now();
+        if self.attract.take().is_some() {
+            cx.notify();
+        }
+    }
+
+    /// Drives the autopilot demo behind the Ready screen once it has been idle
+    /// long enough, returning whether the board changed.
+    fn tick_attract(&mut self) -> bool {
+        let attract = match &mut self.attract {
+            Some(attract) => attract,
+            None if self.last_activity.elapsed() >= ATTRACT_IDLE => {
+                let mut attract = Engine::new(self.engine.config(), rand::random());
+                attract.start();
+                self.attract.insert(attract)
             }
+            None => return false,
+        };
+        if let Some(direction) = autopilot::plan(attract) {
+            attract.queue_direction(direction);
+        }
+        attract.tick();
+        if attract.state() == GameStatus::GameOver {
+            self.attract = None;
         }
+        true
     }
 
     pub fn tick_delay(&self) -> Duration {
-        let speedup = (self.score / 4) as u64 * SPEED_STEP_MS;
-        let ms = self
-            .base_tick_ms
-            .saturating_sub(speedup)
-            .max(self.min_tick_ms);
-        Duration::from_millis(ms)
+        if let Some(delay) = self.versus.as_ref().and_then(Versus::tick_delay) {
+            return delay;
+        }
+        let delay = self.displayed_engine().tick_delay();
+        match &self.training {
+            Some(training) => training.scale(delay),
+            None => delay,
+        }
     }
 
-    fn board_contains(&self, cell: &Cell) -> bool {
-        (0..self.board_width).contains(&cell.x) && (0..self.board_height).contains(&cell.y)
+    fn reset(&mut self) {
+        if self.screen == Screen::Editor {
+            // Leaving the editor goes back to the chosen layout.
+            self.editor = None;
+            self.free_play = Some(self.layout_config());
+        }
+        // A daily challenge is a single attempt, so restarting goes back to free play.
+        let free_play = self
+            .free_play
+            .take()
+            .or_else(|| self.engine.config().daily.map(|_| self.layout_config()));
+        match free_play {
+            Some(config) => self.engine = Engine::new(&config, rand::random()),
+            None => self.engine.reset(),
+        }
+        self.screen = Screen::Play;
+        self.summary = None;
+        self.replay = None;
+        self.practice = None;
+        self.drill = None;
+        self.tutorial = None;
+        self.versus = None;
+        self.apply_adaptive();
+        if let Some(training) = &self.training {
+            training.apply(&mut self.engine);
+        }
     }
 
-    fn queue_direction(&mut self, direction: Direction) {
-        if matches!(self.state, GameStatus::GameOver | GameStatus::Ready) {
+    /// Tunes the waiting run to the tracked difficulty, or back to the normal
+    /// curve when adaptive mode is off. Daily challenges and training drills
+    /// are always played as set.
+    fn apply_adaptive(&mut self) {
+        let config = self.engine.config();
+        let wanted = (self.adaptive.enabled
+            && config.daily.is_none()
+            && self.training.is_none()
+            && self.editor.is_none())
+        .then(|| self.adaptive.difficulty());
+        if config.adaptive != wanted && self.engine.state() == GameStatus::Ready {
+            let mut config = config.clone();
+            config.adaptive = wanted;
+            self.engine = Engine::new(&config, rand::random());
+        }
+    }
+
+    pub fn handle_turn(&mut self, direction: Direction, cx: &mut Context<Self>) {
+        if let Some(versus) = &mut self.versus {
+            versus.queue(Input::Turn(direction));
             return;
         }
-        if direction.is_opposite(self.direction) && self.snake.len() > 1 {
+        if self.screen == Screen::Summary
+            && self.modal.is_none()
+            && let Some(summary) = &mut self.summary
+        {
+            Self::scrub(&mut summary.timeline, direction);
+            cx.notify();
+            return;
+        }
+        if self.screen != Screen::Play || self.modal.is_some() {
             return;
         }
-        self.next_direction = direction;
+        self.engine.queue_direction(direction);
+        cx.notify();
     }
 
-    fn toggle_pause(&mut self) {
-        self.state = match self.state {
-            GameStatus::Running => GameStatus::Paused,
-            GameStatus::Paused => GameStatus::Running,
-            other => other,
+    /// The two-key scheme: a turn is taken from the heading the snake will
+    /// have after its queued turns, so quick taps chain. Off the live board
+    /// the keys act as plain left and right.
+    pub fn handle_steer(&mut self, turn: Turn, cx: &mut Context<Self>) {
+        let engine = self
+            .versus
+            .as_ref()
+            .and_then(Versus::local)
+            .unwrap_or(&self.engine);
+        let direction = match self.screen {
+            Screen::Play | Screen::Versus => turn.from(engine.heading()),
+            _ => turn.direction(),
         };
+        self.handle_turn(direction, cx);
     }
 
-    fn reset(&mut self) {
-        self.snake = Self::build_initial_snake(self.board_width, self.board_height);
-        self.direction = Direction::Right;
-        self.next_direction = Direction::Right;
-        self.state = GameStatus::Ready;
-        self.score = 0;
-        self.food = self.random_empty_cell();
-    }
-
-    fn random_empty_cell(&mut self) -> Cell {
-        Self::random_food(
-            &self.snake,
-            &mut self.rng,
-            self.board_width,
-            self.board_height,
-        )
+    /// Left and right step the summary's preview a tick at a time; up and
+    /// down jump to the previous or next marked moment.
+    fn scrub(timeline: &mut Timeline, direction: Direction) {
+        match direction {
+            Direction::Left => timeline.seek(timeline.position().saturating_sub(1)),
+            Direction::Right => timeline.seek(timeline.position() + 1),
+            Direction::Up => timeline.jump(false),
+            Direction::Down => timeline.jump(true),
+        }
     }
 
-    pub fn handle_turn(&mut self, direction: Direction, cx: &mut Context<Self>) {
-        self.queue_direction(direction);
+    pub fn handle_restart(&mut self, cx: &mut Context<Self>) {
+        if self.screen == Screen::Versus {
+            return;
+        }
+        // Enter on a menu opened from the Ready screen starts the game.
+        match self.modal {
+            Some(open) if open.modal != Modal::QuitPrompt && !open.resume => self.modal = None,
+            Some(_) => return,
+            None => {}
+        }
+        if self.screen == Screen::Editor {
+            self.handle_playtest(cx);
+            return;
+        }
+        if self
+            .practice
+            .as_ref()
+            .is_some_and(PracticeDrill::is_complete)
+            || self.drill.as_ref().is_some_and(|run| run.time().is_some())
+        {
+            self.reset();
+            self.start_run();
+            cx.notify();
+            return;
+        }
+        match self.engine.state() {
+            GameStatus::Ready | GameStatus::Paused => self.start_run(),
+            GameStatus::Running | GameStatus::GameOver => {
+                self.reset();
+                self.start_run();
+            }
+        }
         cx.notify();
     }
 
-    pub fn handle_restart(&mut self, cx: &mut Context<Self>) {
-        match self.state {
-            GameStatus::Ready => self.state = GameStatus::Running,
-            GameStatus::Running => {
-                self.reset();
-                self.state = GameStatus::Running;
+    /// Starts or resumes the live run, noting the best score to beat when it
+    /// is a fresh one.
+    fn start_run(&mut self) {
+        if self.engine.state() == GameStatus::Ready {
+            self.run_best = self.high_score;
+            self.run_saved = false;
+            self.pace_timeline.clear();
+            self.hint_timer = None;
+            tracing::info!(
+                seed = self.engine.seed(),
+                "Run started: {}",
+                self.engine.config().layout.label()
+            );
+        }
+        self.engine.start();
+    }
+
+    /// Pauses a running game when the window loses focus and flags it for
+    /// attention; coming back to the window clears the flag.
+    pub fn set_window_active(&mut self, active: bool, cx: &mut Context<Self>) {
+        if active {
+            self.needs_attention = false;
+        } else if self.screen == Screen::Play && self.engine.state() == GameStatus::Running {
+            self.handle_toggle_pause(cx);
+            self.needs_attention = self.engine.state() == GameStatus::Paused;
+        }
+        cx.notify();
+    }
+
+    pub fn note_window_bounds(&mut self, window: &Window, cx: &App) {
+        self.placement = Some(WindowPlacement::capture(window, cx));
+    }
+
+    /// The OS window title, e.g. "Snake — 23 pts (Paused)".
+    fn title_text(&self) -> String {
+        let title = match self.screen {
+            Screen::Play if self.engine.state() == GameStatus::Ready => "Snake".to_string(),
+            Screen::Play => format!(
+                "Snake — {} pts{}",
+                self.engine.score(),
+                match self.engine.state() {
+                    GameStatus::Paused => " (Paused)",
+                    GameStatus::GameOver => " (Game over)",
+                    _ => "",
+                }
+            ),
+            Screen::Summary => format!("Snake — {} pts (Game over)", self.engine.score()),
+            Screen::Replay => "Snake — Replay".to_string(),
+            Screen::Versus => "Snake — Versus".to_string(),
+            Screen::Editor => "Snake — Editor".to_string(),
+        };
+        if self.needs_attention {
+            format!("● {title}")
+        } else {
+            title
+        }
+    }
+
+    pub fn handle_toggle_pause(&mut self, cx: &mut Context<Self>) {
+        if self.modal.is_some()
+            || self
+                .practice
+                .as_ref()
+                .is_some_and(PracticeDrill::is_complete)
+        {
+            return;
+        }
+        if matches!(
+            self.engine.state(),
+            GameStatus::Running | GameStatus::Paused
+        ) {
+            self.engine.toggle_pause();
+            tracing::info!(
+                tick = self.engine.ticks(),
+                "Run {}",
+                if self.engine.state() == GameStatus::Paused {
+                    "paused"
+                } else {
+                    "resumed"
+                }
+            );
+            if let Some(tutorial) = &mut self.tutorial {
+                tutorial.observe_status(self.engine.state());
             }
-            GameStatus::Paused => self.state = GameStatus::Running,
-            GameStatus::GameOver => {
-                self.reset();
-                self.state = GameStatus::Running;
+            cx.notify();
+        }
+    }
+
+    pub fn handle_watch_replay(&mut self, cx: &mut Context<Self>) {
+        if self.modal.is_some() || self.practice.is_some() {
+            return;
+        }
+        if let Some(summary) = &self.summary {
+            self.replay = Some(ReplayPlayer::new(summary.replay.clone()));
+            self.screen = Screen::Replay;
+            cx.notify();
+        }
+    }
+
+    pub fn handle_main_menu(&mut self, cx: &mut Context<Self>) {
+        if self.modal.is_some() {
+            return;
+        }
+        if self.screen != Screen::Play
+            || self.engine.state() == GameStatus::GameOver
+            || self.practice.is_some()
+        {
+            self.reset();
+            cx.notify();
+        }
+    }
+
+    pub fn handle_cycle_layout(&mut self, cx: &mut Context<Self>) {
+        if self.modal.is_some()
+            || self.screen != Screen::Play
+            || self.engine.state() != GameStatus::Ready
+        {
+            return;
+        }
+        self.layout_index = (self.layout_index + 1) % self.layouts.len();
+        let mut config = self.layout_config();
+        config.weather = self.engine.config().weather;
+        config.goal_length = self.engine.config().goal_length;
+        config.self_cut = self.engine.config().self_cut;
+        config.gravity = self.engine.config().gravity;
+        config.mirror = self.engine.config().mirror;
+        config.collisions = self.engine.config().collisions;
+        config.speed = self.engine.config().speed;
+        config.adaptive = self.engine.config().adaptive;
+        self.engine = Engine::new(&config, rand::random());
+        cx.notify();
+    }
+
+    pub fn handle_toggle_gravity(&mut self, cx: &mut Context<Self>) {
+        if self.modal.is_some()
+            || self.screen != Screen::Play
+            || self.engine.state() != GameStatus::Ready
+        {
+            return;
+        }
+        let mut config = self.engine.config().clone();
+        config.gravity = !config.gravity;
+        self.engine = Engine::new(&config, rand::random());
+        cx.notify();
+    }
+
+    /// Flips how the board is drawn. Mirrored runs keep their own best score.
+    pub fn handle_toggle_mirror(&mut self, cx: &mut Context<Self>) {
+        if self.modal.is_some()
+            || self.screen != Screen::Play
+            || self.engine.state() != GameStatus::Ready
+        {
+            return;
+        }
+        let mut config = self.engine.config().clone();
+        config.mirror = !config.mirror;
+        self.engine = Engine::new(&config, rand::random());
+        self.high_score = self.profile.best_score(config.mirror);
+        cx.notify();
+    }
+
+    /// Turns adaptive difficulty on or off for the next run.
+    pub fn handle_toggle_adaptive(&mut self, cx: &mut Context<Self>) {
+        if self.modal.is_some()
+            || self.screen != Screen::Play
+            || self.engine.state() != GameStatus::Ready
+        {
+            return;
+        }
+        self.adaptive.enabled = !self.adaptive.enabled;
+        if let Err(err) = self.adaptive.store() {
+            tracing::warn!("Failed to save adaptive difficulty: {err}");
+        }
+        self.apply_adaptive();
+        cx.notify();
+    }
+
+    /// The kind of run the board is showing, which picks its theme.
+    fn game_mode(&self) -> GameMode {
+        if self.versus.is_some() {
+            GameMode::Versus
+        } else if self.tutorial.is_some() {
+            GameMode::Tutorial
+        } else if self.drill.is_some() {
+            GameMode::Drill
+        } else if self.training.is_some() || self.practice.is_some() {
+            GameMode::Practice
+        } else if self.engine.config().daily.is_some() {
+            GameMode::Daily
+        } else if self.engine.config().goal_length.is_some() {
+            GameMode::Speedrun
+        } else {
+            GameMode::FreePlay
+        }
+    }
+
+    /// Moves the current mode on to the next board theme. Only looks change,
+    /// so this works mid-run.
+    pub fn handle_cycle_theme(&mut self, cx: &mut Context<Self>) {
+        if self.modal.is_some() {
+            return;
+        }
+        self.themes.cycle(self.game_mode());
+        if let Err(err) = self.themes.store() {
+            tracing::warn!("Failed to save board themes: {err}");
+        }
+        cx.notify();
+    }
+
+    pub fn handle_toggle_weather(&mut self, cx: &mut Context<Self>) {
+        if self.modal.is_some()
+            || self.screen != Screen::Play
+            || self.engine.state() != GameStatus::Ready
+        {
+            return;
+        }
+        let mut config = self.engine.config().clone();
+        config.weather = !config.weather;
+        self.engine = Engine::new(&config, rand::random());
+        cx.notify();
+    }
+
+    /// A fresh config for the selected free-play layout at the chosen size.
+    fn layout_config(&self) -> GameConfig {
+        let mut config = GameConfig::new(self.layouts[self.layout_index].clone());
+        let (width, height) = self.board_size.dimensions();
+        config.resize(width, height);
+        config
+    }
+
+    pub fn handle_toggle_board_size(&mut self, cx: &mut Context<Self>) {
+        if self.modal.is_some()
+            || self.screen != Screen::Play
+            || self.engine.state() != GameStatus::Ready
+        {
+            return;
+        }
+        self.board_size = self.board_size.next();
+        let mut config = self.engine.config().clone();
+        let (width, height) = self.board_size.dimensions();
+        config.resize(width, height);
+        self.frame_meter.borrow_mut().clear();
+        self.engine = Engine::new(&config, rand::random());
+        cx.notify();
+    }
+
+    pub fn handle_toggle_self_cut(&mut self, cx: &mut Context<Self>) {
+        if self.modal.is_some()
+            || self.screen != Screen::Play
+            || self.engine.state() != GameStatus::Ready
+        {
+            return;
+        }
+        let mut config = self.engine.config().clone();
+        config.self_cut = !config.self_cut;
+        self.engine = Engine::new(&config, rand::random());
+        cx.notify();
+    }
+
+    /// Flips whether running into `cause` ends the run or just blocks the move.
+    pub fn handle_toggle_collision(&mut self, cause: DeathCause, cx: &mut Context<Self>) {
+        if self.modal.is_some()
+            || self.screen != Screen::Play
+            || self.engine.state() != GameStatus::Ready
+        {
+            return;
+        }
+        let mut config = self.engine.config().clone();
+        config.collisions = config.collisions.toggled(cause);
+        self.engine = Engine::new(&config, rand::random());
+        cx.notify();
+    }
+
+    pub fn handle_cycle_speed_curve(&mut self, cx: &mut Context<Self>) {
+        self.update_speed(SpeedSetting::next_kind, cx);
+    }
+
+    pub fn handle_tune_speed_curve(&mut self, cx: &mut Context<Self>) {
+        self.update_speed(SpeedSetting::tune, cx);
+    }
+
+    fn update_speed(&mut self, change: fn(SpeedSetting) -> SpeedSetting, cx: &mut Context<Self>) {
+        if self.modal.is_some()
+            || self.screen != Screen::Play
+            || self.engine.state() != GameStatus::Ready
+        {
+            return;
+        }
+        let mut config = self.engine.config().clone();
+        config.speed = change(config.speed);
+        self.engine = Engine::new(&config, rand::random());
+        cx.notify();
+    }
+
+    pub fn handle_toggle_speedrun(&mut self, cx: &mut Context<Self>) {
+        if self.modal.is_some()
+            || self.screen != Screen::Play
+            || self.engine.state() != GameStatus::Ready
+        {
+            return;
+        }
+        let mut config = self.engine.config().clone();
+        config.goal_length = match config.goal_length {
+            Some(_) => None,
+            None => Some(SPEEDRUN_GOAL),
+        };
+        self.engine = Engine::new(&config, rand::random());
+        cx.notify();
+    }
+
+    /// Opens the level editor from the Ready screen, returns to it from a
+    /// play-test, and closes it from the editor itself.
+    pub fn handle_toggle_editor(&mut self, cx: &mut Context<Self>) {
+        if self.modal.is_some() {
+            return;
+        }
+        match self.screen {
+            Screen::Editor => self.reset(),
+            Screen::Play if self.editor.is_some() => {
+                self.engine.reset();
+                self.screen = Screen::Editor;
             }
+            Screen::Play
+                if self.engine.state() == GameStatus::Ready
+                    && self.practice.is_none()
+                    && self.training.is_none()
+                    && self.tutorial.is_none()
+                    && self.engine.config().daily.is_none() =>
+            {
+                let config = self.engine.config();
+                self.editor = Some(Editor::new(
+                    config.board_width.clamp(editor::MIN_SIDE, editor::MAX_SIDE),
+                    config
+                        .board_height
+                        .clamp(editor::MIN_SIDE, editor::MAX_SIDE),
+                ));
+                self.screen = Screen::Editor;
+            }
+            _ => return,
         }
         cx.notify();
     }
 
-    pub fn handle_toggle_pause(&mut self, cx: &mut Context<Self>) {
-        if matches!(self.state, GameStatus::Running | GameStatus::Paused) {
-            self.toggle_pause();
+    /// Starts a run on the level as drawn. Play-tests are not scored and end
+    /// back in the editor.
+    fn handle_playtest(&mut self, cx: &mut Context<Self>) {
+        let Some(editor) = &mut self.editor else {
+            return;
+        };
+        match editor.level("Play-test") {
+            Ok(level) => {
+                self.engine =
+                    Engine::new(&GameConfig::new(BoardLayout::Level(level)), rand::random());
+                self.screen = Screen::Play;
+                self.start_run();
+            }
+            Err(err) => editor.set_message(err),
+        }
+        cx.notify();
+    }
+
+    fn handle_paint(&mut self, cell: Cell, dragging: bool, cx: &mut Context<Self>) {
+        if self.screen != Screen::Editor {
+            return;
+        }
+        if let Some(editor) = &mut self.editor
+            && (!dragging || editor.brush().drags())
+            && editor.paint(cell)
+        {
             cx.notify();
         }
     }
 
-    fn status_text(&self) -> (&'static str, u32) {
-        match self.state {
-            GameStatus::Ready => ("Ready", 0x93c5fd),
-            GameStatus::Running => ("Running", 0x34d399),
-            GameStatus::Paused => ("Paused", 0xfbbf24),
-            GameStatus::GameOver => ("Game Over", 0xf87171),
+    fn handle_set_brush(&mut self, brush: Brush, cx: &mut Context<Self>) {
+        if let Some(editor) = &mut self.editor {
+            editor.set_brush(brush);
+            cx.notify();
+        }
+    }
+
+    fn handle_resize_editor(&mut self, dx: i32, dy: i32, cx: &mut Context<Self>) {
+        if let Some(editor) = &mut self.editor {
+            let tiles = editor.tiles();
+            editor.resize(tiles.width() + dx, tiles.height() + dy);
+            cx.notify();
+        }
+    }
+
+    /// Saves the drawing as a level file and adds it to the layout list.
+    fn handle_export_level(&mut self, cx: &mut Context<Self>) {
+        let Some(editor) = &mut self.editor else {
+            return;
+        };
+        match editor.export() {
+            Ok(level) => {
+                tracing::info!("Exported level {}", level.name());
+                editor.set_message(format!("Saved as {} - pick it with Tab", level.name()));
+                self.layouts.push(BoardLayout::Level(level));
+            }
+            Err(err) => editor.set_message(format!("Export failed: {err}")),
+        }
+        cx.notify();
+    }
+
+    pub fn handle_toggle_practice_mode(&mut self, cx: &mut Context<Self>) {
+        if self.modal.is_some() || self.screen != Screen::Play || self.practice.is_some() {
+            return;
+        }
+        if self.training.take().is_some() {
+            self.engine.reset();
+        } else if self.engine.state() == GameStatus::Ready && self.tutorial.is_none() {
+            let training = Training::new();
+            training.apply(&mut self.engine);
+            self.training = Some(training);
+        } else {
+            return;
+        }
+        tracing::info!(on = self.training.is_some(), "Practice mode toggled");
+        cx.notify();
+    }
+
+    fn update_training(
+        &mut self,
+        update: impl FnOnce(&mut Training, &mut Engine),
+        cx: &mut Context<Self>,
+    ) {
+        if self.modal.is_some() || self.screen != Screen::Play {
+            return;
+        }
+        if let Some(training) = &mut self.training {
+            update(training, &mut self.engine);
+            cx.notify();
+        }
+    }
+
+    pub fn handle_practice_faster(&mut self, cx: &mut Context<Self>) {
+        self.update_training(|training, _| training.faster(), cx);
+    }
+
+    pub fn handle_practice_slower(&mut self, cx: &mut Context<Self>) {
+        self.update_training(|training, _| training.slower(), cx);
+    }
+
+    pub fn handle_toggle_freeze(&mut self, cx: &mut Context<Self>) {
+        self.update_training(
+            |training, engine| training.set_frozen(engine, !training.frozen()),
+            cx,
+        );
+    }
+
+    pub fn handle_toggle_wall_immunity(&mut self, cx: &mut Context<Self>) {
+        self.update_training(|training, engine| training.toggle_walls(engine), cx);
+    }
+
+    /// Freezes practice mode, or advances a frozen run by one tick.
+    pub fn handle_step_tick(&mut self, cx: &mut Context<Self>) {
+        if self.modal.is_some() || self.screen != Screen::Play {
+            return;
+        }
+        let Some(training) = &mut self.training else {
+            return;
+        };
+        if !training.frozen() {
+            training.set_frozen(&mut self.engine, true);
+            cx.notify();
+        } else if self.engine.step() {
+            self.after_live_tick(cx);
+        }
+    }
+
+    /// Drills the lead-up to the last death, restarting it until the player survives.
+    pub fn handle_practice(&mut self, cx: &mut Context<Self>) {
+        if self.modal.is_some() || self.screen != Screen::Summary {
+            return;
+        }
+        let Some(mut drill) = self
+            .summary
+            .as_ref()
+            .and_then(|summary| PracticeDrill::from_replay(&summary.replay))
+        else {
+            return;
+        };
+        self.engine = drill.restore();
+        self.practice = Some(drill);
+        self.screen = Screen::Play;
+        cx.notify();
+    }
+
+    fn modal_is(&self, modal: Modal) -> bool {
+        self.modal.is_some_and(|open| open.modal == modal)
+    }
+
+    /// Opens `modal` over the board, pausing a running game until it closes.
+    fn open_modal(&mut self, modal: Modal) {
+        let resume = self.engine.state() == GameStatus::Running;
+        if resume {
+            self.engine.toggle_pause();
+        }
+        self.modal = Some(OpenModal { modal, resume });
+    }
+
+    /// Closes the open modal, resuming the game if opening it paused it.
+    fn close_modal(&mut self) {
+        if let Some(open) = self.modal.take()
+            && open.resume
+            && self.engine.state() == GameStatus::Paused
+        {
+            self.engine.toggle_pause();
+        }
+    }
+
+    /// Opens `modal`, or closes it if it is the one already open.
+    fn toggle_modal(&mut self, modal: Modal, cx: &mut Context<Self>) {
+        match self.modal {
+            Some(open) if open.modal == modal => self.close_modal(),
+            Some(_) => return,
+            None if self.screen == Screen::Play => self.open_modal(modal),
+            None => return,
+        }
+        cx.notify();
+    }
+
+    pub fn handle_show_daily(&mut self, cx: &mut Context<Self>) {
+        self.toggle_modal(Modal::Daily, cx);
+    }
+
+    pub fn handle_show_profile(&mut self, cx: &mut Context<Self>) {
+        self.toggle_modal(Modal::Profile, cx);
+    }
+
+    pub fn handle_show_drills(&mut self, cx: &mut Context<Self>) {
+        self.toggle_modal(Modal::Drills, cx);
+    }
+
+    /// Starts `drill` from the drills screen; crashing retries it from its
+    /// scripted position.
+    pub fn handle_start_drill(&mut self, drill: Drill, cx: &mut Context<Self>) {
+        if !self.modal.is_none_or(|open| open.modal == Modal::Drills)
+            || self.training.is_some()
+            || self.screen != Screen::Play
+            || self.engine.state() != GameStatus::Ready
+        {
+            return;
+        }
+        if self.engine.config().daily.is_none() {
+            self.free_play = Some(self.engine.config().clone());
+        }
+        self.modal = None;
+        let mut run = DrillRun::new(drill);
+        self.engine = run.restore();
+        self.drill = Some(run);
+        cx.notify();
+    }
+
+    /// Opens the versus lobby, or leaves it while no match is under way.
+    pub fn handle_show_lobby(&mut self, cx: &mut Context<Self>) {
+        if self.modal.is_some() {
+            return;
+        }
+        match self.screen {
+            Screen::Play if self.engine.state() == GameStatus::Ready && self.training.is_none() => {
+                self.screen = Screen::Versus;
+            }
+            Screen::Versus if !self.versus.as_ref().is_some_and(Versus::is_playing) => {
+                self.versus = None;
+                self.screen = Screen::Play;
+            }
+            _ => return,
+        }
+        cx.notify();
+    }
+
+    pub fn handle_open_versus(&mut self, role: Role, cx: &mut Context<Self>) {
+        if self.screen != Screen::Versus || !self.can_edit_address() {
+            return;
+        }
+        let address = match role {
+            Role::Host => {
+                let port = self
+                    .net_address
+                    .rsplit_once(':')
+                    .and_then(|(_, port)| port.parse().ok())
+                    .unwrap_or(DEFAULT_PORT);
+                format!("0.0.0.0:{port}")
+            }
+            Role::Guest => self.net_address.clone(),
+        };
+        let mut config = self.engine.config().clone();
+        config.goal_length = None;
+        config.daily = None;
+        self.versus = Some(Versus::open(
+            role,
+            address,
+            config,
+            cx.background_executor(),
+        ));
+        cx.notify();
+    }
+
+    /// The address can be edited until a connection attempt is under way.
+    fn can_edit_address(&self) -> bool {
+        self.modal.is_none()
+            && self
+                .versus
+                .as_ref()
+                .is_none_or(|versus| matches!(versus.status(), VersusStatus::Disconnected(_)))
+    }
+
+    /// Edits the lobby's address field, returning whether the keystroke was
+    /// consumed. Runs ahead of key bindings so digits reach the field.
+    pub fn type_address(&mut self, keystroke: &Keystroke, cx: &mut Context<Self>) -> bool {
+        if self.screen != Screen::Versus
+            || !self.can_edit_address()
+            || keystroke.modifiers.modified()
+        {
+            return false;
         }
+        match keystroke.key.as_str() {
+            "backspace" => {
+                self.net_address.pop();
+            }
+            "enter" => self.handle_open_versus(Role::Guest, cx),
+            _ => match keystroke.key_char.as_deref() {
+                Some(text)
+                    if text
+                        .chars()
+                        .all(|c| c.is_ascii_digit() || c == '.' || c == ':') =>
+                {
+                    self.net_address.push_str(text);
+                }
+                _ => return false,
+            },
+        }
+        cx.notify();
+        true
+    }
+
+    /// Starts the guided tutorial on an open board.
+    pub fn handle_start_tutorial(&mut self, cx: &mut Context<Self>) {
+        if self.modal.is_some()
+            || self.training.is_some()
+            || self.screen != Screen::Play
+            || self.engine.state() != GameStatus::Ready
+        {
+            return;
+        }
+        self.free_play = Some(self.engine.config().clone());
+        self.engine = Engine::new(&GameConfig::new(BoardLayout::Open), rand::random());
+        self.engine.start();
+        self.tutorial = Some(Tutorial::new());
+        cx.notify();
+    }
+
+    pub fn handle_start_daily(&mut self, cx: &mut Context<Self>) {
+        let today = DailyChallenge::today();
+        if !self.modal.is_none_or(|open| open.modal == Modal::Daily)
+            || self.training.is_some()
+            || self.screen != Screen::Play
+            || self.engine.state() != GameStatus::Ready
+            || self.daily_history.played(today.day())
+        {
+            return;
+        }
+        if self.engine.config().daily.is_none() {
+            self.free_play = Some(self.engine.config().clone());
+        }
+        self.modal = None;
+        self.engine = Engine::new(&today.config(), today.seed());
+        self.start_run();
+        self.daily_history.record(
+            today.day(),
+            DailyResult {
+                score: 0,
+                length: self.engine.snake().len(),
+                finished: false,
+            },
+        );
+        cx.notify();
+    }
+
+    pub fn handle_activate(&mut self, slot: usize, cx: &mut Context<Self>) {
+        if let Some(versus) = &mut self.versus {
+            versus.queue(Input::Activate(slot));
+            return;
+        }
+        if self.modal.is_some() || self.screen != Screen::Play {
+            return;
+        }
+        self.engine.activate(slot);
+        cx.notify();
+    }
+
+    /// Whether the live run is one that can be saved and resumed later.
+    /// Tutorial and versus runs are not, nor daily challenges, which get a
+    /// single attempt.
+    fn run_in_progress(&self) -> bool {
+        self.screen == Screen::Play
+            && self.editor.is_none()
+            && self.practice.is_none()
+            && self.drill.is_none()
+            && self.training.is_none()
+            && self.tutorial.is_none()
+            && self.versus.is_none()
+            && self.engine.config().daily.is_none()
+            && matches!(
+                self.engine.state(),
+                GameStatus::Running | GameStatus::Paused
+            )
+    }
+
+    fn autosave(&mut self) {
+        if self.run_in_progress() {
+            match SaveGame::store(&self.engine) {
+                Ok(()) => {
+                    self.has_save = true;
+                    self.run_saved = true;
+                }
+                Err(err) => tracing::warn!("Failed to save game: {err}"),
+            }
+        }
+    }
+
+    pub fn handle_save_run(&mut self, cx: &mut Context<Self>) {
+        if self.modal.is_some() || !self.run_in_progress() {
+            return;
+        }
+        if self.engine.state() == GameStatus::Running {
+            self.engine.toggle_pause();
+        }
+        self.autosave();
+        cx.notify();
+    }
+
+    pub fn handle_resume_run(&mut self, cx: &mut Context<Self>) {
+        if self.modal_is(Modal::ResumePrompt) {
+            self.close_modal();
+        }
+        if self.modal.is_some()
+            || self.screen != Screen::Play
+            || self.engine.state() != GameStatus::Ready
+            || !self.has_save
+        {
+            return;
+        }
+        self.has_save = false;
+        self.run_saved = false;
+        match SaveGame::take() {
+            Ok(engine) if engine.config().daily.is_some() => {
+                tracing::warn!("Failed to resume game: daily challenges cannot be resumed");
+            }
+            Ok(engine) => {
+                self.engine = engine;
+                self.run_best = self.high_score;
+                self.pace_timeline.clear();
+                if self.engine.state() == GameStatus::Running {
+                    self.engine.toggle_pause();
+                }
+            }
+            Err(err) => tracing::warn!("Failed to resume game: {err}"),
+        }
+        cx.notify();
+    }
+
+    /// Declines the interrupted run for now; it stays on offer from the menu.
+    pub fn handle_dismiss_resume(&mut self, cx: &mut Context<Self>) {
+        if !self.modal_is(Modal::ResumePrompt) {
+            return;
+        }
+        self.close_modal();
+        if let Err(err) = SaveGame::acknowledge() {
+            tracing::warn!("Failed to clear interrupted run: {err}");
+        }
+        cx.notify();
+    }
+
+    /// Copies a shareable code for the live board to the clipboard.
+    pub fn handle_export_position(&mut self, cx: &mut Context<Self>) {
+        if self.modal.is_some() {
+            return;
+        }
+        let code = Position::capture(&self.engine).encode();
+        cx.write_to_clipboard(ClipboardItem::new_string(code));
+        tracing::info!("Position code copied to clipboard");
+    }
+
+    /// Starts a puzzle from a position code on the clipboard.
+    pub fn handle_import_position(&mut self, cx: &mut Context<Self>) {
+        if self.modal.is_some()
+            || self.screen != Screen::Play
+            || self.engine.state() != GameStatus::Ready
+        {
+            return;
+        }
+        let Some(code) = cx.read_from_clipboard().and_then(|item| item.text()) else {
+            return;
+        };
+        match Position::decode(&code) {
+            Ok(position) => {
+                let mut config = GameConfig::new(BoardLayout::Puzzle(position));
+                config.weather = self.engine.config().weather;
+                config.goal_length = self.engine.config().goal_length;
+                self.engine = Engine::new(&config, rand::random());
+                cx.notify();
+            }
+            Err(err) => tracing::warn!("Failed to import position: {err}"),
+        }
+    }
+
+    pub fn handle_toggle_controls(&mut self, cx: &mut Context<Self>) {
+        self.controls = self.controls.next();
+        if let Err(err) = self.controls.store() {
+            tracing::warn!("Failed to save control profile: {err}");
+        }
+        cx.notify();
+    }
+
+    pub fn handle_cycle_skin(&mut self, cx: &mut Context<Self>) {
+        self.skin_index = (self.skin_index + 1) % self.skins.len();
+        cx.notify();
+    }
+
+    fn update_accessibility(
+        &mut self,
+        update: impl FnOnce(&mut Accessibility),
+        cx: &mut Context<Self>,
+    ) {
+        update(&mut self.accessibility);
+        if let Err(err) = self.accessibility.store() {
+            tracing::warn!("Failed to save accessibility settings: {err}");
+        }
+        cx.notify();
+    }
+
+    fn update_retention(&mut self, update: impl FnOnce(&mut Retention), cx: &mut Context<Self>) {
+        update(&mut self.retention);
+        if let Err(err) = self.retention.store() {
+            tracing::warn!("Failed to save retention settings: {err}");
+        }
+        self.apply_retention();
+        cx.notify();
+    }
+
+    fn update_privacy(&mut self, update: impl FnOnce(&mut Privacy), cx: &mut Context<Self>) {
+        update(&mut self.privacy);
+        if let Err(err) = self.privacy.store() {
+            tracing::warn!("Failed to save privacy settings: {err}");
+        }
+        cx.notify();
+    }
+
+    /// Wipes the data directory and starts over as a fresh first player.
+    /// Only between runs, so nothing in play is written straight back.
+    pub fn handle_delete_local_data(&mut self, cx: &mut Context<Self>) {
+        if self.screen != Screen::Play
+            || self.engine.state() != GameStatus::Ready
+            || self.versus.is_some()
+        {
+            return;
+        }
+        if let Err(err) = storage::wipe() {
+            tracing::warn!("Failed to delete local data: {err}");
+        }
+        // Asking not to be recorded should outlast the wipe.
+        if self.privacy != Privacy::default()
+            && let Err(err) = self.privacy.store()
+        {
+            tracing::warn!("Failed to save privacy settings: {err}");
+        }
+        self.roster = Roster::default();
+        self.roster.select(0);
+        self.mutators = Mutators::load();
+        self.pace_timeline.clear();
+        self.load_player();
+        tracing::info!("Deleted all local data");
+        cx.notify();
+    }
+
+    /// Switches to another player, swapping in their records and settings.
+    /// Only allowed between runs, so nothing in play changes hands.
+    pub fn handle_switch_player(&mut self, index: usize, cx: &mut Context<Self>) {
+        if self.screen != Screen::Play
+            || self.engine.state() != GameStatus::Ready
+            || self.versus.is_some()
+            || index == self.roster.active_index()
+        {
+            return;
+        }
+        self.roster.select(index);
+        self.load_player();
+        cx.notify();
+    }
+
+    pub fn handle_add_player(&mut self, cx: &mut Context<Self>) {
+        if self.screen != Screen::Play || self.engine.state() != GameStatus::Ready {
+            return;
+        }
+        let index = self.roster.add_next();
+        self.handle_switch_player(index, cx);
+    }
+
+    /// Reads everything kept per player for the active one.
+    fn load_player(&mut self) {
+        self.profile = Profile::load();
+        self.high_score = self.profile.best_score(self.engine.config().mirror);
+        self.split_records = SplitRecords::load();
+        self.daily_history = DailyHistory::load();
+        self.replay_archive = ReplayArchive::load();
+        self.pace_reference = PaceReference::load();
+        self.pace_records = PaceRecords::load();
+        self.drill_records = DrillRecords::load();
+        self.adaptive = AdaptiveTracker::load();
+        self.themes = ThemeOverrides::load();
+        self.retention = Retention::load();
+        self.controls = ControlProfile::load();
+        self.accessibility = Accessibility::load();
+        self.has_save = SaveGame::exists();
+        self.summary = None;
+        self.apply_retention();
+        self.apply_adaptive();
+    }
+
+    /// Prunes replays and daily history to the retention limits and refreshes
+    /// the storage readout.
+    fn apply_retention(&mut self) {
+        self.replay_archive.retain(self.retention.keep_replays);
+        self.daily_history.prune_to(self.retention.history_cap);
+        self.storage_used = storage::used_bytes(&storage::data_dir());
+    }
+
+    /// Plays a kept replay from the profile page while no run is under way.
+    pub fn handle_watch_archived(&mut self, index: usize, cx: &mut Context<Self>) {
+        if self.engine.state() != GameStatus::Ready || self.practice.is_some() {
+            return;
+        }
+        let Some(archived) = self.replay_archive.replays().nth(index) else {
+            return;
+        };
+        self.replay = Some(ReplayPlayer::new(archived.replay.clone()));
+        self.close_modal();
+        self.screen = Screen::Replay;
+        cx.notify();
+    }
+
+    /// Opens the quit confirmation over whatever else is showing.
+    pub fn handle_request_quit(&mut self, cx: &mut Context<Self>) {
+        if self.modal_is(Modal::QuitPrompt) {
+            return;
+        }
+        self.close_modal();
+        self.open_modal(Modal::QuitPrompt);
+        cx.notify();
+    }
+
+    pub fn handle_cancel_quit(&mut self, cx: &mut Context<Self>) {
+        if self.modal_is(Modal::QuitPrompt) {
+            self.close_modal();
+            cx.notify();
+        }
+    }
+
+    fn key_context(&self) -> KeyContext {
+        let mut context = KeyContext::default();
+        context.add("gpui-snake");
+        context.set("controls", self.controls.key_context());
+        if self.modal_is(Modal::QuitPrompt) {
+            context.add("quit_prompt");
+        }
+        if self.modal_is(Modal::ResumePrompt) {
+            context.add("resume_prompt");
+        }
+        context
+    }
+
+    fn status_text(&self) -> (&'static str, u32) {
+        if self.screen == Screen::Replay {
+            return ("Replay", 0xc4b5fd);
+        }
+        if let Some(versus) = &self.versus {
+            return match versus.status() {
+                VersusStatus::Connecting => ("Connecting", 0x93c5fd),
+                VersusStatus::Playing => ("Versus", 0x34d399),
+                VersusStatus::Over(outcome) => (outcome.label(), 0xfde68a),
+                VersusStatus::Disconnected(_) => ("Disconnected", 0xf87171),
+            };
+        }
+        if self.attract.is_some() {
+            return ("Demo", 0x94a3b8);
+        }
+        match self.engine.state() {
+            GameStatus::Ready => ("Ready", 0x93c5fd),
+            GameStatus::Running => ("Running", 0x34d399),
+            GameStatus::Paused => ("Paused", 0xfbbf24),
+            GameStatus::GameOver if self.engine.completed() => ("Finished", 0xfde68a),
+            GameStatus::GameOver => ("Game Over", 0xf87171),
+        }
+    }
+
+    /// Shows the summary of the run that just ended and lets the `GameOver`
+    /// subscribers record it.
+    fn finish_run(&mut self, cx: &mut Context<Self>) {
+        self.summary = Some(RunSummary::from_engine(
+            &self.engine,
+            self.run_best,
+            &self.roster.active().name,
+        ));
+        tracing::info!(
+            tick = self.engine.ticks(),
+            score = self.engine.score(),
+            length = self.engine.snake().len(),
+            "Run finished"
+        );
+        self.screen = Screen::Summary;
+        cx.emit(GameEvent::GameOver);
+    }
+
+    /// Re-emits the latest tick's engine events to the entity's subscribers.
+    fn emit_events(&self, cx: &mut Context<Self>) {
+        for event in self.engine.events() {
+            cx.emit(*event);
+        }
+    }
+
+    fn log_event(&mut self, event: &GameEvent, _: &mut Context<Self>) {
+        let Some(line) = event.describe() else {
+            return;
+        };
+        tracing::debug!(tick = self.engine.ticks(), "{line}");
+        if self.event_log.len() == EVENT_LOG_LEN {
+            self.event_log.pop_front();
+        }
+        self.event_log.push_back(line);
+    }
+
+    fn record_high_score(&mut self, event: &GameEvent, _: &mut Context<Self>) {
+        if let (GameEvent::GameOver, Some(summary)) = (event, &self.summary) {
+            self.high_score = self.run_best.max(summary.score);
+        }
+    }
+
+    fn record_profile(&mut self, event: &GameEvent, _: &mut Context<Self>) {
+        if let (GameEvent::GameOver, Some(summary)) = (event, &self.summary) {
+            self.profile.record(
+                &summary.stats,
+                summary.score,
+                &summary.titles,
+                self.engine.config().mirror,
+            );
+        }
+    }
+
+    fn record_splits(&mut self, event: &GameEvent, _: &mut Context<Self>) {
+        if let (GameEvent::GameOver, Some(summary)) = (event, &mut self.summary)
+            && summary.final_time.is_some()
+        {
+            summary.personal_best = self.split_records.record(
+                self.engine.config(),
+                self.engine.splits(),
+                self.engine.checkpoints(),
+            );
+        }
+    }
+
+    fn record_daily(&mut self, event: &GameEvent, _: &mut Context<Self>) {
+        if let (GameEvent::GameOver, Some(summary)) = (event, &self.summary)
+            && let Some(day) = self.engine.config().daily
+        {
+            self.daily_history.record(
+                day,
+                DailyResult {
+                    score: summary.score,
+                    length: summary.length,
+                    finished: true,
+                },
+            );
+        }
+    }
+
+    fn track_pace(&mut self, event: &GameEvent, _: &mut Context<Self>) {
+        if let GameEvent::FoodEaten { score, .. } | GameEvent::PelletEaten { score, .. } = *event {
+            self.pace_timeline.push((self.engine.elapsed(), score));
+        }
+    }
+
+    fn record_pace(&mut self, event: &GameEvent, _: &mut Context<Self>) {
+        if let GameEvent::GameOver = event {
+            self.pace_records
+                .record(self.engine.config(), &self.pace_timeline);
+        }
+    }
+
+    /// Whether mutator scripts get to change the live run. Only free play
+    /// and speedruns take them; challenges, drills and lessons are played as
+    /// made.
+    fn mutators_apply(&self) -> bool {
+        !self.mutators.is_empty()
+            && self.screen == Screen::Play
+            && self.editor.is_none()
+            && matches!(self.game_mode(), GameMode::FreePlay | GameMode::Speedrun)
+    }
+
+    fn run_mutators(&mut self, event: &GameEvent, _: &mut Context<Self>) {
+        if !self.mutators_apply() {
+            return;
+        }
+        for mutation in self.mutators.react(event, &self.engine) {
+            tracing::debug!(tick = self.engine.ticks(), ?mutation, "Mutator applied");
+            self.engine.mutate(mutation);
+        }
+    }
+
+    /// Only runs that were themselves tuned feed the tracker, so daily
+    /// challenges and drills never skew it.
+    fn record_adaptive(&mut self, event: &GameEvent, _: &mut Context<Self>) {
+        if let (GameEvent::GameOver, Some(summary)) = (event, &self.summary)
+            && self.engine.config().adaptive.is_some()
+        {
+            self.adaptive.record(summary.score);
+        }
+    }
+
+    pub fn handle_cycle_pace(&mut self, cx: &mut Context<Self>) {
+        if self.modal.is_some() {
+            return;
+        }
+        self.pace_reference = self.pace_reference.next();
+        if let Err(err) = self.pace_reference.store() {
+            tracing::warn!("Failed to save pace reference: {err}");
+        }
+        cx.notify();
+    }
+
+    fn archive_replay(&mut self, event: &GameEvent, _: &mut Context<Self>) {
+        if let (GameEvent::GameOver, Some(summary)) = (event, &self.summary)
+            && self.privacy.replays
+        {
+            self.replay_archive
+                .add(summary, self.retention.keep_replays);
+            self.apply_retention();
+        }
+    }
+
+    /// The run is over, so a save taken earlier in it must not bring it back.
+    fn clear_finished_save(&mut self, event: &GameEvent, _: &mut Context<Self>) {
+        if !matches!(event, GameEvent::GameOver) || !self.run_saved {
+            return;
+        }
+        if let Err(err) = SaveGame::clear() {
+            tracing::warn!("Failed to clear saved game: {err}");
+        }
+        self.has_save = false;
+        self.run_saved = false;
+    }
+
+    fn play_juice(&mut self, event: &GameEvent, _: &mut Context<Self>) {
+        if !self.accessibility.screen_effects {
+            return;
+        }
+        match *event {
+            GameEvent::Died { .. } => self.juice = Some(Juice::death()),
+            GameEvent::FoodEaten { multiplier, .. } if multiplier >= BIG_COMBO => {
+                self.juice = Some(Juice::combo(multiplier))
+            }
+            GameEvent::SpeedUp { .. } => self.pulse = Some(Pulse::speed_up()),
+            _ => {}
+        }
+    }
+
+    fn play_cues(&mut self, event: &GameEvent, _: &mut Context<Self>) {
+        match event {
+            GameEvent::SpeedUp { .. } => self.audio.play(Cue::Riser),
+            GameEvent::TickAdvanced
+                if self.accessibility.proximity_audio && self.screen == Screen::Play =>
+            {
+                self.play_proximity()
+            }
+            _ => {}
+        }
+    }
+
+    /// Sounds out the board around the head after a tick: a buzz while a
+    /// crash is a few moves ahead, otherwise a regular ping toward the food.
+    fn play_proximity(&self) {
+        let Some(proximity) = Proximity::measure(&self.engine) else {
+            return;
+        };
+        if let Some(moves) = proximity.danger {
+            self.audio.play(Cue::Danger { moves });
+        } else if self.engine.ticks().is_multiple_of(PING_EVERY) {
+            self.audio.play(Cue::FoodPing {
+                closeness: proximity.food_closeness(&self.engine),
+                pan: proximity.food_pan,
+            });
+        }
+    }
+
+    fn advance_tutorial(&mut self, event: &GameEvent, _: &mut Context<Self>) {
+        if let Some(tutorial) = &mut self.tutorial {
+            tutorial.observe(event);
+        }
+    }
+
+    /// Starts rotating the Ready screen hints, unless they already are.
+    fn start_hint_timer(&mut self, cx: &mut Context<Self>) {
+        if self.hint_timer.is_some() {
+            return;
+        }
+        self.hint_timer = Some(cx.spawn(async move |this, cx| {
+            loop {
+                Timer::after(HINT_EVERY).await;
+                let waiting = this.update(cx, |game, cx| game.rotate_hint(cx));
+                if !matches!(waiting, Ok(true)) {
+                    break;
+                }
+            }
+        }));
+    }
+
+    /// Moves on to the next hint, or stops the timer once the run is no
+    /// longer waiting to start.
+    fn rotate_hint(&mut self, cx: &mut Context<Self>) -> bool {
+        if self.screen != Screen::Play || self.engine.state() != GameStatus::Ready {
+            self.hint_timer = None;
+            return false;
+        }
+        self.hints.advance();
+        cx.notify();
+        true
+    }
+
+    /// Redraws between ticks while a screen effect is playing or the camera
+    /// is still catching up with the head.
+    pub fn animate_frame(&mut self, cx: &mut Context<Self>) {
+        let mut camera = self.camera;
+        let camera_moved = camera.follow(self.displayed_engine());
+        self.camera = camera;
+        if self.pulse.is_some_and(|pulse| !pulse.is_active()) {
+            self.pulse = None;
+        }
+        if let Some(juice) = self.juice
+            && !juice.is_active()
+        {
+            self.juice = None;
+        } else if self.juice.is_none() && self.pulse.is_none() && !camera_moved {
+            return;
+        }
+        cx.notify();
+    }
+
+    pub fn tick(&mut self, cx: &mut Context<Self>) {
+        self.drive_bot(cx);
+        if self.modal.is_some() {
+            return;
+        }
+        match self.screen {
+            Screen::Play if self.engine.state() == GameStatus::Ready => {
+                self.start_hint_timer(cx);
+                if self.tick_attract() {
+                    cx.notify();
+                }
+            }
+            Screen::Play => {
+                if self.engine.tick() {
+                    self.after_live_tick(cx);
+                }
+            }
+            Screen::Replay => {
+                let finished = self.replay.as_mut().is_none_or(|player| player.step());
+                if finished {
+                    self.replay = None;
+                    // A kept replay is watched from the menu, so it goes back there.
+                    self.screen = if self.summary.is_some() {
+                        Screen::Summary
+                    } else {
+                        Screen::Play
+                    };
+                }
+                cx.notify();
+            }
+            Screen::Versus => {
+                if self.versus.as_mut().is_some_and(Versus::tick) {
+                    cx.notify();
+                }
+            }
+            Screen::Summary | Screen::Editor => {}
+        }
+        if let Some(bot) = &mut self.bot {
+            bot.publish(&self.engine);
+        }
+    }
+
+    /// Hands the live run to an external bot, which then plays it through
+    /// the same handlers as the keyboard.
+    pub fn attach_bot(&mut self, bot: BotLink) {
+        self.bot = Some(bot);
+    }
+
+    fn drive_bot(&mut self, cx: &mut Context<Self>) {
+        let Some(bot) = &self.bot else {
+            return;
+        };
+        for command in bot.drain() {
+            match command {
+                BotCommand::Turn(direction) => self.handle_turn(direction, cx),
+                BotCommand::Start
+                    if matches!(
+                        self.engine.state(),
+                        GameStatus::Ready | GameStatus::GameOver
+                    ) =>
+                {
+                    self.handle_restart(cx)
+                }
+                BotCommand::Start => {}
+                BotCommand::Pause => self.handle_toggle_pause(cx),
+            }
+        }
+    }
+
+    /// Follows up a tick of the live engine: drills, the tutorial and
+    /// practice mode restart on a crash, and a scored run is wrapped up.
+    fn after_live_tick(&mut self, cx: &mut Context<Self>) {
+        self.emit_events(cx);
+        if let Some(drill) = &mut self.practice {
+            if drill.check(&self.engine) {
+                self.engine = drill.restore();
+            } else if drill.is_complete() {
+                self.engine.toggle_pause();
+            }
+            cx.notify();
+            return;
+        }
+        if let Some(run) = &mut self.drill {
+            let passed = run.time().is_some();
+            if run.check(&self.engine) {
+                self.engine = run.restore();
+            } else if !passed && let Some(time) = run.time() {
+                self.engine.toggle_pause();
+                self.drill_records.record(run.drill(), time);
+            }
+            cx.notify();
+            return;
+        }
+        if let Some(training) = &self.training {
+            // Nothing in practice mode is recorded, so a crash just goes again.
+            if self.engine.state() == GameStatus::GameOver {
+                self.engine.reset();
+                training.apply(&mut self.engine);
+                self.engine.start();
+            }
+            cx.notify();
+            return;
+        }
+        if self.editor.is_some() {
+            // A play-test goes straight back to the drawing when it ends.
+            if self.engine.state() == GameStatus::GameOver {
+                self.screen = Screen::Editor;
+            }
+            cx.notify();
+            return;
+        }
+        if self.tutorial.is_some() {
+            // Crashing is part of learning: start over on the same step.
+            if self.engine.state() == GameStatus::GameOver {
+                self.engine.reset();
+                self.engine.start();
+            }
+            cx.notify();
+            return;
+        }
+        if self.engine.state() == GameStatus::GameOver {
+            self.finish_run(cx);
+        } else if self.engine.ticks().is_multiple_of(AUTOSAVE_EVERY) {
+            self.autosave();
+        }
+        cx.notify();
+    }
+
+    fn overlay(background: u32) -> Div {
+        div()
+            .absolute()
+            .top(px(0.))
+            .bottom(px(0.))
+            .left(px(0.))
+            .right(px(0.))
+            .flex()
+            .items_center()
+            .justify_center()
+            .bg(rgba(background))
+    }
+
+    fn button(id: impl Into<ElementId>, label: impl Into<SharedString>) -> Stateful<Div> {
+        div()
+            .id(id)
+            .px_3()
+            .py_2()
+            .rounded_md()
+            .bg(rgb(0x334155))
+            .cursor_pointer()
+            .hover(|style| style.bg(rgb(0x475569)))
+            .child(label.into())
+    }
+
+    fn render_inventory(engine: &Engine) -> Div {
+        let slots = engine
+            .inventory()
+            .slots()
+            .iter()
+            .enumerate()
+            .map(|(index, slot)| {
+                let (label, color) = match slot {
+                    Some(power_up) => (power_up.label(), power_up.color()),
+                    None => ("empty", 0x64748b),
+                };
+                div()
+                    .px_3()
+                    .py_1()
+                    .rounded_md()
+                    .bg(rgb(0x1e293b))
+                    .text_color(rgb(color))
+                    .child(format!("{}: {}", index + 1, label))
+            });
+        let effects = engine.effects().iter().map(|effect| {
+            div()
+                .text_color(rgb(effect.power_up.color()))
+                .child(format!(
+                    "{} {}",
+                    effect.power_up.label(),
+                    effect.remaining_ticks
+                ))
+        });
+
+        div()
+            .flex()
+            .items_center()
+            .gap_3()
+            .text_sm()
+            .child(div().text_color(rgb(0x94a3b8)).child("Power-ups"))
+            .children(slots)
+            .children(effects)
+    }
+
+    /// The combo multiplier with a bar draining over the window left to extend
+    /// it. Pulses in on every meal that raises the multiplier.
+    fn render_combo(engine: &Engine) -> Option<AnyElement> {
+        let combo = engine.combo();
+        if combo.multiplier() < 2 {
+            return None;
+        }
+        let remaining = combo.remaining(engine.ticks());
+        Some(
+            div()
+                .flex()
+                .items_center()
+                .gap_3()
+                .child(
+                    div()
+                        .text_xl()
+                        .text_color(rgb(0xfbbf24))
+                        .child(format!("Combo x{}", combo.multiplier())),
+                )
+                .child(
+                    div()
+                        .w(px(120.0))
+                        .h(px(8.0))
+                        .rounded_md()
+                        .bg(rgb(0x1e293b))
+                        .child(
+                            div()
+                                .h_full()
+                                .w(px(120.0 * remaining))
+                                .rounded_md()
+                                .bg(rgb(0xfbbf24)),
+                        ),
+                )
+                .with_animation(
+                    ("combo", combo.last_food_tick().unwrap_or_default()),
+                    Animation::new(Duration::from_millis(300)).with_easing(ease_out_quint()),
+                    |this, delta| this.opacity(0.4 + 0.6 * delta),
+                )
+                .into_any_element(),
+        )
+    }
+
+    /// Speedrun timer and splits, with the live run raced against the
+    /// personal-best ghost at each checkpoint.
+    fn render_splits(engine: &Engine, best: &[Duration], ghost: &[Duration]) -> Option<Div> {
+        let goal = engine.config().goal_length?;
+        let reached = engine.splits();
+        let count = goal.div_ceil(SPLIT_EVERY);
+        // Behind the ghost as soon as it passes a checkpoint not yet reached,
+        // otherwise as far ahead or behind as at the latest checkpoint.
+        let checkpoints = engine.checkpoints();
+        let ghost = match (
+            ghost.get(checkpoints.len()),
+            checkpoints.len().checked_sub(1),
+        ) {
+            (Some(&pb), _) if engine.elapsed() > pb => Some((engine.elapsed(), pb)),
+            (_, Some(index)) => ghost.get(index).map(|&pb| (checkpoints[index], pb)),
+            _ => None,
+        };
+        Some(
+            div()
+                .flex()
+                .flex_wrap()
+                .items_center()
+                .gap_3()
+                .text_sm()
+                .child(
+                    div()
+                        .text_xl()
+                        .text_color(rgb(0xfde68a))
+                        .child(speedrun::timer_text(engine.elapsed())),
+                )
+                .when_some(ghost, |this, (time, pb)| {
+                    let (text, color) = if time <= pb {
+                        ("Ahead of PB", 0x34d399)
+                    } else {
+                        ("Behind PB", 0xf87171)
+                    };
+                    this.child(
+                        div()
+                            .text_lg()
+                            .text_color(rgb(color))
+                            .child(format!("{text} {}s", speedrun::delta_text(time, pb))),
+                    )
+                })
+                .children((0..count).map(|index| {
+                    let length = ((index + 1) * SPLIT_EVERY).min(goal);
+                    let (text, color) = match (reached.get(index), best.get(index)) {
+                        (Some(&split), Some(&pb)) => (
+                            format!(
+                                "{length}: {} ({})",
+                                speedrun::timer_text(split),
+                                speedrun::delta_text(split, pb)
+                            ),
+                            if split <= pb { 0x34d399 } else { 0xf87171 },
+                        ),
+                        (Some(&split), None) => (
+                            format!("{length}: {}", speedrun::timer_text(split)),
+                            0xf8fafc,
+                        ),
+                        (None, Some(&pb)) => (
+                            format!("{length}: PB {}", speedrun::timer_text(pb)),
+                            0x64748b,
+                        ),
+                        (None, None) => (format!("{length}: -"), 0x64748b),
+                    };
+                    div()
+                        .px_2()
+                        .py_1()
+                        .rounded_md()
+                        .bg(rgb(0x1e293b))
+                        .text_color(rgb(color))
+                        .child(text)
+                })),
+        )
+    }
+
+    fn render_daily(&self, cx: &mut Context<Self>) -> Div {
+        let today = DailyChallenge::today();
+        let result = self.daily_history.get(today.day());
+        let first_day = today.day().saturating_sub(27);
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_3()
+            .p_6()
+            .rounded_xl()
+            .bg(rgb(0x0f172a))
+            .shadow_lg()
+            .text_color(rgb(0xf8fafc))
+            .child(
+                div()
+                    .text_2xl()
+                    .child(format!("Daily challenge {}", daily::date_text(today.day()))),
+            )
+            .child(
+                div()
+                    .text_sm()
+                    .text_color(rgb(0x94a3b8))
+                    .child(today.describe()),
+            )
+            .child(match result {
+                Some(result) if result.finished => div().child(format!(
+                    "Today's score: {} (length {})",
+                    result.score, result.length
+                )),
+                Some(_) => div()
+                    .text_color(rgb(0xfbbf24))
+                    .child("Today's attempt was abandoned"),
+                None if self.engine.state() == GameStatus::Ready => div().child(
+                    Self::button("start-daily", "Play today's challenge (T)").on_click(
+                        cx.listener(|this, _: &ClickEvent, _, cx| this.handle_start_daily(cx)),
+                    ),
+                ),
+                None => div()
+                    .text_color(rgb(0x94a3b8))
+                    .child("Finish the current run to play today's challenge"),
+            })
+            .child(
+                div()
+                    .flex()
+                    .flex_col()
+                    .gap_1()
+                    .text_xs()
+                    .children((0..4).map(|week| {
+                        div().flex().gap_1().children((0..7).map(|weekday| {
+                            let day = first_day + week * 7 + weekday;
+                            let (background, label) = match self.daily_history.get(day) {
+                                Some(result) if result.finished => {
+                                    (0x047857, result.score.to_string())
+                                }
+                                Some(_) => (0x92400e, "-".into()),
+                                None => (0x1e293b, daily::date_text(day)[8..].to_string()),
+                            };
+                            div()
+                                .w(px(34.0))
+                                .h(px(28.0))
+                                .flex()
+                                .items_center()
+                                .justify_center()
+                                .rounded_sm()
+                                .bg(rgb(background))
+                                .when(day == today.day(), |cell| {
+                                    cell.border_1().border_color(rgb(0xf8fafc))
+                                })
+                                .child(label)
+                        }))
+                    })),
+            )
+            .child(div().flex().gap_3().mt_2().text_sm().child(
+                Self::button("daily-back", "Back (H)").on_click(
+                    cx.listener(|this, _: &ClickEvent, _, cx| this.handle_show_daily(cx)),
+                ),
+            ))
+    }
+
+    fn render_drills(&self, cx: &mut Context<Self>) -> Div {
+        div()
+            .flex()
+            .flex_col()
+            .gap_3()
+            .p_6()
+            .rounded_xl()
+            .bg(rgb(0x0f172a))
+            .shadow_lg()
+            .text_color(rgb(0xf8fafc))
+            .child(div().text_2xl().child("Drills"))
+            .child(
+                div()
+                    .text_sm()
+                    .text_color(rgb(0x94a3b8))
+                    .child("Short exercises from a set position - a crash starts the attempt over"),
+            )
+            .children(Drill::ALL.into_iter().enumerate().map(|(index, drill)| {
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .gap_6()
+                    .child(div().flex().flex_col().child(drill.label()).child(
+                        div().text_xs().text_color(rgb(0x94a3b8)).child(format!(
+                            "{} - eat {}",
+                            drill.describe(),
+                            drill.goal()
+                        )),
+                    ))
+                    .child(
+                        div()
+                            .flex()
+                            .items_center()
+                            .gap_3()
+                            .text_sm()
+                            .child(
+                                div().text_color(rgb(0xa5f3fc)).child(
+                                    self.drill_records
+                                        .best(drill)
+                                        .map_or("-".into(), speedrun::timer_text),
+                                ),
+                            )
+                            .when(self.engine.state() == GameStatus::Ready, |this| {
+                                this.child(Self::button(("start-drill", index), "Start").on_click(
+                                    cx.listener(move |this, _: &ClickEvent, _, cx| {
+                                        this.handle_start_drill(drill, cx)
+                                    }),
+                                ))
+                            }),
+                    )
+            }))
+            .child(div().flex().gap_3().mt_2().text_sm().child(
+                Self::button("drills-back", "Back (Shift+D)").on_click(
+                    cx.listener(|this, _: &ClickEvent, _, cx| this.handle_show_drills(cx)),
+                ),
+            ))
+    }
+
+    fn render_lobby(&self, cx: &mut Context<Self>) -> Div {
+        let status = self.versus.as_ref().map(|versus| match versus.status() {
+            VersusStatus::Disconnected(reason) => (format!("Disconnected: {reason}"), 0xf87171),
+            _ if versus.role() == Role::Host => (
+                format!("Waiting for a guest on {}...", versus.address()),
+                0x93c5fd,
+            ),
+            _ => (format!("Connecting to {}...", versus.address()), 0x93c5fd),
+        });
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_3()
+            .p_6()
+            .rounded_xl()
+            .bg(rgb(0x0f172a))
+            .shadow_lg()
+            .text_color(rgb(0xf8fafc))
+            .child(div().text_2xl().child("Versus"))
+            .child(
+                div()
+                    .text_sm()
+                    .text_color(rgb(0x94a3b8))
+                    .child("Host a match, or type the host's address and join"),
+            )
+            .child(
+                div()
+                    .px_3()
+                    .py_2()
+                    .rounded_md()
+                    .bg(rgb(0x1e293b))
+                    .border_1()
+                    .border_color(rgb(0x475569))
+                    .child(format!("{}_", self.net_address)),
+            )
+            .when_some(status, |this, (text, color)| {
+                this.child(div().text_sm().text_color(rgb(color)).child(text))
+            })
+            .child(
+                div()
+                    .flex()
+                    .gap_3()
+                    .mt_2()
+                    .text_sm()
+                    .child(Self::button("versus-host", "Host").on_click(cx.listener(
+                        |this, _: &ClickEvent, _, cx| this.handle_open_versus(Role::Host, cx),
+                    )))
+                    .child(
+                        Self::button("versus-join", "Join (Enter)").on_click(cx.listener(
+                            |this, _: &ClickEvent, _, cx| this.handle_open_versus(Role::Guest, cx),
+                        )),
+                    )
+                    .child(Self::button("versus-back", "Back (V)").on_click(
+                        cx.listener(|this, _: &ClickEvent, _, cx| this.handle_show_lobby(cx)),
+                    )),
+            )
+    }
+
+    /// The board grid as shown: whole when it fits, otherwise clipped to the
+    /// view and shifted by the camera's progress into its first cell.
+    fn render_viewport(&self, engine: &Engine, grid: Div) -> AnyElement {
+        if Camera::fits(engine) {
+            return grid.into_any_element();
+        }
+        let gap = if self.accessibility.grid_lines {
+            GRID_GAP
+        } else {
+            0.0
+        };
+        let pitch = self.cell_px + gap;
+        let (mut offset_x, offset_y) = self.camera.offset();
+        // Mirrored, the columns run right to left, so the part of a cell the
+        // camera has moved past is on the other side.
+        if engine.config().mirror && offset_x > 0.0 {
+            offset_x = 1.0 - offset_x;
+        }
+        div()
+            .relative()
+            .overflow_hidden()
+            .w(px(VIEW_WIDTH as f32 * pitch - gap))
+            .h(px(VIEW_HEIGHT as f32 * pitch - gap))
+            .child(
+                grid.absolute()
+                    .left(px(-offset_x * pitch))
+                    .top(px(-offset_y * pitch)),
+            )
+            .into_any_element()
+    }
+
+    /// The whole board in miniature with the camera's view outlined. Painted
+    /// rather than built from cells, as a large board has thousands of them.
+    fn render_minimap(&self, engine: &Engine) -> impl IntoElement {
+        let palette = self.accessibility.palette;
+        let (width, height) = (engine.board_width(), engine.board_height());
+        let mut cells: Vec<(Cell, u32)> = engine
+            .tiles()
+            .cells()
+            .filter(|(_, tile)| *tile != Tile::Empty)
+            .map(|(cell, tile)| (cell, CellView::Tile(tile).color(palette, false, false)))
+            .collect();
+        // Rain hides the food here just as it does on the board.
+        if engine.weather() != Some(Weather::Rain) {
+            cells.push((engine.food(), palette.food(engine.food_kind())));
+        }
+        cells.extend(
+            engine
+                .snake()
+                .iter()
+                .map(|cell| (*cell, palette.body(false))),
+        );
+        if let Some(head) = engine.snake().front() {
+            cells.push((*head, palette.head()));
+        }
+        let (mut view_x, view_y) = self.camera.position();
+        // The minimap is flipped along with the board it maps.
+        if engine.config().mirror {
+            for (cell, _) in &mut cells {
+                cell.x = width - 1 - cell.x;
+            }
+            view_x = (width - VIEW_WIDTH) as f32 - view_x;
+        }
+        let empty = palette.empty();
+        let area = move |origin: Point<Pixels>, x: f32, y: f32, w: f32, h: f32| {
+            Bounds::new(
+                origin + point(px(x * MINIMAP_CELL), px(y * MINIMAP_CELL)),
+                size(px(w * MINIMAP_CELL), px(h * MINIMAP_CELL)),
+            )
+        };
+        canvas(
+            |_, _, _| {},
+            move |bounds, _, window, _| {
+                window.paint_quad(fill(bounds, rgb(empty)));
+                for (cell, color) in &cells {
+                    let cell = area(bounds.origin, cell.x as f32, cell.y as f32, 1.0, 1.0);
+                    window.paint_quad(fill(cell, rgb(*color)));
+                }
+                let view = area(
+                    bounds.origin,
+                    view_x,
+                    view_y,
+                    VIEW_WIDTH as f32,
+                    VIEW_HEIGHT as f32,
+                );
+                window.paint_quad(outline(view, rgb(0xf8fafc), BorderStyle::Solid));
+            },
+        )
+        .w(px(width as f32 * MINIMAP_CELL))
+        .h(px(height as f32 * MINIMAP_CELL))
+    }
+
+    /// The whole giant board, painted cell by cell onto one canvas so that
+    /// eight thousand cells cost no more layout than one. Sprites and markers
+    /// are left off at this size. Painting records the frame's time.
+    fn render_painted_board(&self, engine: &Engine, started: Instant) -> impl IntoElement {
+        let palette = self.accessibility.palette;
+        let shapes = self.accessibility.shapes;
+        let frame = Frame::capture(engine);
+        let ghosting = frame.ghosting();
+        let pulse = self.pulse;
+        let (width, height) = (engine.board_width(), engine.board_height());
+        let mirror = engine.config().mirror;
+        let gap = if self.accessibility.grid_lines {
+            GRID_GAP
+        } else {
+            0.0
+        };
+        // Shrunk to the area the normal view takes up.
+        let pitch = self.cell_px + gap;
+        let side = ((VIEW_WIDTH as f32 * pitch - gap) / width as f32)
+            .min((VIEW_HEIGHT as f32 * pitch - gap) / height as f32);
+        let inset = if gap > 0.0 { 1.0 } else { 0.0 };
+        let colors: Vec<u32> = (0..height)
+            .flat_map(|y| (0..width).map(move |x| Cell { x, y }))
+            .map(|cell| {
+                let view = frame.view(cell);
+                match (pulse, view) {
+                    (Some(pulse), CellView::Head(_) | CellView::Segment(_)) => {
+                        pulse.tint(view.color(palette, shapes, ghosting))
+                    }
+                    _ => view.color(palette, shapes, ghosting),
+                }
+            })
+            .collect();
+        let meter = self.frame_meter.clone();
+        canvas(
+            |_, _, _| {},
+            move |bounds, _, window, _| {
+                for (index, color) in colors.iter().enumerate() {
+                    let (x, y) = (index as i32 % width, index as i32 / width);
+                    let column = if mirror { width - 1 - x } else { x };
+                    let cell = Bounds::new(
+                        bounds.origin + point(px(column as f32 * side), px(y as f32 * side)),
+                        size(px(side - inset), px(side - inset)),
+                    );
+                    window.paint_quad(fill(cell, rgb(*color)));
+                }
+                meter.borrow_mut().record(started.elapsed());
+            },
+        )
+        .w(px(width as f32 * side))
+        .h(px(height as f32 * side))
+    }
+
+    /// Average frame time against the budget, with a warning once over it.
+    fn render_frame_budget(&self) -> impl IntoElement {
+        let meter = self.frame_meter.borrow();
+        let ms = |frame: Duration| frame.as_secs_f32() * 1000.0;
+        let over = meter.over_budget();
+        div()
+            .text_sm()
+            .text_color(rgb(if over { 0xf87171 } else { 0x94a3b8 }))
+            .child(match (meter.average(), meter.worst()) {
+                (Some(average), Some(worst)) => format!(
+                    "Frame: {:.1} ms avg, {:.1} ms worst / {:.0} ms budget{}",
+                    ms(average),
+                    ms(worst),
+                    ms(FRAME_BUDGET),
+                    if over { " - over budget!" } else { "" }
+                ),
+                _ => format!("Frame: measuring / {:.0} ms budget", ms(FRAME_BUDGET)),
+            })
+    }
+
+    /// A small graph of tick length against score for `speed`, drawn down to
+    /// the engine's floor.
+    fn render_speed_preview(&self, speed: SpeedSetting) -> impl IntoElement {
+        const WIDTH: f32 = 120.0;
+        const HEIGHT: f32 = 36.0;
+        let bar = WIDTH / SPEED_PREVIEW_SCORE as f32;
+        let heights: Vec<f32> = (0..SPEED_PREVIEW_SCORE)
+            .map(|score| {
+                let ms = speed.curve().tick_ms(BASE_TICK_MS, score).max(MIN_TICK_MS);
+                HEIGHT * ms as f32 / BASE_TICK_MS as f32
+            })
+            .collect();
+        let empty = self.accessibility.palette.empty();
+        canvas(
+            |_, _, _| {},
+            move |bounds, _, window, _| {
+                window.paint_quad(fill(bounds, rgb(empty)));
+                for (index, height) in heights.iter().enumerate() {
+                    let origin = bounds.origin + point(px(index as f32 * bar), px(HEIGHT - height));
+                    window.paint_quad(fill(
+                        Bounds::new(origin, size(px(bar), px(*height))),
+                        rgb(0x38bdf8),
+                    ));
+                }
+            },
+        )
+        .w(px(WIDTH))
+        .h(px(HEIGHT))
+    }
+
+    /// The other player's score and a miniature of their board.
+    fn render_opponent(&self, remote: &Engine) -> Div {
+        const MINI_CELL: f32 = 6.0;
+        let food = remote.food();
+
+        div()
+            .flex()
+            .items_center()
+            .gap_4()
+            .text_sm()
+            .child(
+                div()
+                    .flex()
+                    .flex_col()
+                    .child(div().text_color(rgb(0x94a3b8)).child("Opponent"))
+                    .child(format!(
+                        "Score {}, length {}",
+                        remote.score(),
+                        remote.snake().len()
+                    )),
+            )
+            .child(
+                div()
+                    .flex()
+                    .flex_col()
+                    .p_1()
+                    .rounded_sm()
+                    .bg(rgb(0x1e293b))
+                    .children((0..remote.board_height()).map(|y| {
+                        div().flex().children((0..remote.board_width()).map(|x| {
+                            let cell = Cell { x, y };
+                            let color = if remote.is_occupied(cell) {
+                                0x34d399
+                            } else if cell == food {
+                                0xf87171
+                            } else if remote.tiles().get(cell) != Tile::Empty {
+                                0x64748b
+                            } else {
+                                0x0f172a
+                            };
+                            div().size(px(MINI_CELL)).bg(rgb(color))
+                        }))
+                    })),
+            )
+    }
+
+    fn render_profile(&self, cx: &mut Context<Self>) -> Div {
+        let profile = &self.profile;
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_3()
+            .p_6()
+            .rounded_xl()
+            .bg(rgb(0x0f172a))
+            .shadow_lg()
+            .text_color(rgb(0xf8fafc))
+            .child(
+                div()
+                    .text_2xl()
+                    .child(format!("Profile - {}", self.roster.active().name)),
+            )
+            .child(div().text_sm().text_color(rgb(0x94a3b8)).child(format!(
+                "{} runs, {} food eaten, best score {} ({} mirrored)",
+                profile.runs(),
+                profile.food(),
+                profile.best_score(false),
+                profile.best_score(true)
+            )))
+            .children(Title::ALL.into_iter().map(|title| {
+                let count = profile.title_count(title);
+                div()
+                    .flex()
+                    .flex_col()
+                    .when(count == 0, |this| this.opacity(0.5))
+                    .child(div().text_lg().child(title.label()))
+                    .child(
+                        div()
+                            .text_sm()
+                            .text_color(rgb(0x94a3b8))
+                            .child(match count {
+                                0 => format!("{} - not earned yet", title.description()),
+                                count => format!("{} - earned {count}x", title.description()),
+                            }),
+                    )
+            }))
+            .when(self.replay_archive.replays().next().is_some(), |this| {
+                this.child(div().text_lg().mt_2().child("Recent replays"))
+                    .children(
+                        self.replay_archive
+                            .replays()
+                            .enumerate()
+                            .rev()
+                            .take(RECENT_REPLAYS)
+                            .map(|(index, archived)| {
+                                div()
+                                    .id(("archived-replay", index))
+                                    .flex()
+                                    .items_center()
+                                    .justify_between()
+                                    .gap_8()
+                                    .text_sm()
+                                    .child(format!(
+                                        "Score {}, length {}",
+                                        archived.score, archived.length
+                                    ))
+                                    .child(Self::button("watch-archived", "Watch").on_click(
+                                        cx.listener(move |this, _: &ClickEvent, _, cx| {
+                                            this.handle_watch_archived(index, cx)
+                                        }),
+                                    ))
+                            }),
+                    )
+            })
+            .child(div().text_lg().mt_2().child("Players"))
+            .child(
+                div().flex().flex_wrap().gap_2().text_sm().children(
+                    self.roster
+                        .players()
+                        .iter()
+                        .enumerate()
+                        .map(|(index, player)| {
+                            Self::button(("switch-player", index), player.name.clone())
+                                .when(index == self.roster.active_index(), |this| {
+                                    this.bg(rgb(0x1d4ed8))
+                                })
+                                .on_click(cx.listener(move |this, _: &ClickEvent, _, cx| {
+                                    this.handle_switch_player(index, cx)
+                                }))
+                        }),
+                ),
+            )
+            .child(
+                div()
+                    .flex()
+                    .gap_3()
+                    .mt_2()
+                    .text_sm()
+                    .child(Self::button("add-player", "New player").on_click(
+                        cx.listener(|this, _: &ClickEvent, _, cx| this.handle_add_player(cx)),
+                    ))
+                    .child(Self::button("profile-back", "Back (O)").on_click(
+                        cx.listener(|this, _: &ClickEvent, _, cx| this.handle_show_profile(cx)),
+                    )),
+            )
+    }
+
+    fn render_quit_prompt(&self, cx: &mut Context<Self>) -> Div {
+        div()
+            .flex()
+            .flex_col()
+            .items_center()
+            .gap_3()
+            .p_6()
+            .rounded_xl()
+            .bg(rgb(0x0f172a))
+            .shadow_lg()
+            .child(div().text_xl().child("Quit the game?"))
+            .child(
+                div()
+                    .text_sm()
+                    .text_color(rgb(0x94a3b8))
+                    .child("Y to quit, N or Esc to keep playing"),
+            )
+            .child(
+                div()
+                    .flex()
+                    .gap_3()
+                    .text_sm()
+                    .child(Self::button("quit-confirm", "Quit").on_click(|_, _, cx| cx.quit()))
+                    .child(Self::button("quit-cancel", "Cancel").on_click(
+                        cx.listener(|this, _: &ClickEvent, _, cx| this.handle_cancel_quit(cx)),
+                    )),
+            )
+    }
+
+    fn render_editor(&self, editor: &Editor, cx: &mut Context<Self>) -> Div {
+        let palette = self.accessibility.palette;
+        let tiles = editor.tiles();
+        let spawn = editor.spawn();
+        let (width, height) = (tiles.width(), tiles.height());
+        let resize =
+            |id: &'static str, label: &'static str, dx: i32, dy: i32| {
+                Self::button(id, label).on_click(cx.listener(move |this, _: &ClickEvent, _, cx| {
+                    this.handle_resize_editor(dx, dy, cx)
+                }))
+            };
+        let toolbar = div()
+            .flex()
+            .flex_wrap()
+            .items_center()
+            .gap_2()
+            .text_sm()
+            .children(Brush::ALL.into_iter().enumerate().map(|(index, brush)| {
+                Self::button(("editor-brush", index), brush.label())
+                    .when(brush == editor.brush(), |this| this.bg(rgb(0x1d4ed8)))
+                    .on_click(cx.listener(move |this, _: &ClickEvent, _, cx| {
+                        this.handle_set_brush(brush, cx)
+                    }))
+            }))
+            .child(resize("editor-narrower", "W-", -1, 0))
+            .child(resize("editor-wider", "W+", 1, 0))
+            .child(resize("editor-shorter", "H-", 0, -1))
+            .child(resize("editor-taller", "H+", 0, 1))
+            .child(format!("{width}x{height}"))
+            .child(
+                Self::button("editor-playtest", "Play-test (Enter)")
+                    .on_click(cx.listener(|this, _: &ClickEvent, _, cx| this.handle_playtest(cx))),
+            )
+            .child(
+                Self::button("editor-export", "Export").on_click(
+                    cx.listener(|this, _: &ClickEvent, _, cx| this.handle_export_level(cx)),
+                ),
+            )
+            .child(Self::button("editor-close", "Close (Shift+E)").on_click(
+                cx.listener(|this, _: &ClickEvent, _, cx| this.handle_toggle_editor(cx)),
+            ));
+
+        let grid = div()
+            .debug_selector(|| "editor".into())
+            .flex()
+            .flex_col()
+            .gap(px(1.0))
+            .children((0..height).map(|y| {
+                div().flex().gap(px(1.0)).children((0..width).map(|x| {
+                    let cell = Cell { x, y };
+                    let tile = tiles.get(cell);
+                    let (color, glyph) =
+                        match spawn.and_then(|spawn| spawn.cells().position(|body| body == cell)) {
+                            Some(0) => (
+                                palette.head(),
+                                spawn.map(|spawn| match spawn.direction {
+                                    Direction::Up => "↑",
+                                    Direction::Down => "↓",
+                                    Direction::Left => "←",
+                                    Direction::Right => "→",
+                                }),
+                            ),
+                            Some(_) => (palette.body(false), None),
+                            None => (
+                                CellView::Tile(tile).color(palette, false, false),
+                                tile.glyph(),
+                            ),
+                        };
+                    div()
+                        .size(px(EDITOR_CELL))
+                        .flex()
+                        .items_center()
+                        .justify_center()
+                        .text_size(px(EDITOR_CELL * 0.7))
+                        .text_color(rgb(palette.marker()))
+                        .bg(rgb(color))
+                        .children(glyph)
+                        .on_mouse_down(
+                            MouseButton::Left,
+                            cx.listener(move |this, _: &MouseDownEvent, _, cx| {
+                                this.handle_paint(cell, false, cx)
+                            }),
+                        )
+                        .on_mouse_move(cx.listener(move |this, event: &MouseMoveEvent, _, cx| {
+                            if event.pressed_button == Some(MouseButton::Left) {
+                                this.handle_paint(cell, true, cx)
+                            }
+                        }))
+                }))
+            }));
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_3()
+            .child(toolbar)
+            .children(editor.message().map(|message| {
+                div()
+                    .text_sm()
+                    .text_color(rgb(0xfbbf24))
+                    .child(message.to_string())
+            }))
+            .child(grid)
+    }
+
+    fn render_resume_prompt(&self, cx: &mut Context<Self>) -> Div {
+        div()
+            .flex()
+            .flex_col()
+            .items_center()
+            .gap_3()
+            .p_6()
+            .rounded_xl()
+            .bg(rgb(0x0f172a))
+            .shadow_lg()
+            .child(div().text_xl().child("Resume interrupted run?"))
+            .child(div().text_sm().text_color(rgb(0x94a3b8)).child(
+                "The last session closed in the middle of a run. Y to resume, N to leave it",
+            ))
+            .child(
+                div()
+                    .flex()
+                    .gap_3()
+                    .text_sm()
+                    .child(Self::button("resume-confirm", "Resume").on_click(
+                        cx.listener(|this, _: &ClickEvent, _, cx| this.handle_resume_run(cx)),
+                    ))
+                    .child(Self::button("resume-dismiss", "Not now").on_click(
+                        cx.listener(|this, _: &ClickEvent, _, cx| this.handle_dismiss_resume(cx)),
+                    )),
+            )
+    }
+
+    /// The run as a bar with meals and speed changes marked, and the tick
+    /// being previewed.
+    fn render_timeline(timeline: &Timeline) -> Div {
+        let ticks = timeline.ticks().max(1);
+        let offset = |tick: u64| px(TIMELINE_WIDTH * tick as f32 / ticks as f32);
+        let marker = |tick: u64, width: f32, color: u32| {
+            div()
+                .absolute()
+                .top_0()
+                .bottom_0()
+                .left(offset(tick))
+                .w(px(width))
+                .bg(rgb(color))
+        };
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_1()
+            .child(
+                div()
+                    .relative()
+                    .w(px(TIMELINE_WIDTH))
+                    .h(px(14.0))
+                    .rounded_sm()
+                    .bg(rgb(0x1e293b))
+                    .children(timeline.marks().iter().map(|(tick, mark)| {
+                        marker(
+                            *tick,
+                            2.0,
+                            match mark {
+                                Mark::Food => 0x34d399,
+                                Mark::SpeedChange => 0xfbbf24,
+                            },
+                        )
+                    }))
+                    .child(marker(timeline.position(), 3.0, 0xf8fafc)),
+            )
+            .child(div().text_xs().text_color(rgb(0x94a3b8)).child(format!(
+                "Tick {} / {} - Left/Right to scrub, Up/Down to jump between marks",
+                timeline.position(),
+                timeline.ticks()
+            )))
+    }
+
+    fn render_summary(&self, summary: &RunSummary, cx: &mut Context<Self>) -> Div {
+        let stat = |label: &'static str, value: SharedString| {
+            div()
+                .flex()
+                .justify_between()
+                .gap_8()
+                .child(div().text_color(rgb(0x94a3b8)).child(label))
+                .child(value)
+        };
+
+        div()
+            .flex()
+            .flex_col()
+            .gap_3()
+            .p_6()
+            .rounded_xl()
+            .bg(rgb(0x0f172a))
+            .shadow_lg()
+            .text_color(rgb(0xf8fafc))
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_3()
+                    .child(div().text_2xl().child(if summary.final_time.is_some() {
+                        "Goal reached"
+                    } else {
+                        "Game Over"
+                    }))
+                    .when(summary.new_record || summary.personal_best, |this| {
+                        this.child(
+                            div()
+                                .px_2()
+                                .py_1()
+                                .rounded_md()
+                                .bg(rgb(0xfbbf24))
+                                .text_color(rgb(0x020617))
+                                .text_sm()
+                                .child(if summary.personal_best {
+                                    format!("Personal best for {}!", summary.player)
+                                } else {
+                                    format!("New record for {}!", summary.player)
+                                }),
+                        )
+                    }),
+            )
+            .child(stat("Score", summary.score.to_string().into()))
+            .child(stat("Player", summary.player.clone().into()))
+            .child(stat("Length", summary.length.to_string().into()))
+            .child(stat("Duration", summary.duration_text().into()))
+            .when_some(summary.final_time, |this, time| {
+                this.child(stat("Final time", speedrun::timer_text(time).into()))
+            })
+            .child(stat(
+                "Cause",
+                summary.cause.map_or("-", DeathCause::label).into(),
+            ))
+            .child(Self::render_timeline(&summary.timeline))
+            .when(!summary.titles.is_empty(), |this| {
+                this.child(div().flex().flex_wrap().gap_2().text_sm().children(
+                    summary.titles.iter().map(|title| {
+                        div()
+                            .px_2()
+                            .py_1()
+                            .rounded_md()
+                            .bg(rgb(0x4c1d95))
+                            .text_color(rgb(0xede9fe))
+                            .child(title.label())
+                    }),
+                ))
+            })
+            .child(
+                div()
+                    .flex()
+                    .gap_3()
+                    .mt_2()
+                    .text_sm()
+                    .child(Self::button("summary-restart", "Restart").on_click(
+                        cx.listener(|this, _: &ClickEvent, _, cx| this.handle_restart(cx)),
+                    ))
+                    .child(Self::button("summary-replay", "Watch Replay").on_click(
+                        cx.listener(|this, _: &ClickEvent, _, cx| this.handle_watch_replay(cx)),
+                    ))
+                    .when(summary.cause.is_some(), |this| {
+                        this.child(
+                            Self::button("summary-practice", "Practice this position (P)")
+                                .on_click(cx.listener(|this, _: &ClickEvent, _, cx| {
+                                    this.handle_practice(cx)
+                                })),
+                        )
+                    })
+                    .child(Self::button("summary-menu", "Main Menu").on_click(
+                        cx.listener(|this, _: &ClickEvent, _, cx| this.handle_main_menu(cx)),
+                    )),
+            )
     }
+}
 
-    pub fn tick(&mut self, cx: &mut Context<Self>) {
-        if self.state != GameStatus::Running {
-            return;
-        }
-        if let Some(head) = self.snake.front().copied() {
-            self.direction = self.next_direction;
-            let next = head.offset(self.direction);
-
-            if !self.board_contains(&next) {
-                eprintln!(
-                    "Game over (wall): head=({}, {}), next=({}, {}), board=({}, {})",
-                    head.x, head.y, next.x, next.y, self.board_width, self.board_height
-                );
-                self.state = GameStatus::GameOver;
-                cx.notify();
-                return;
-            }
-
-            let ate_food = next == self.food;
-            if !ate_food {
-                self.snake.pop_back();
-            }
-
-            if self.snake.contains(&next) {
-                eprintln!(
-                    "Game over (self): head=({}, {}), next=({}, {}), len={}, board=({}, {})",
-                    head.x,
-                    head.y,
-                    next.x,
-                    next.y,
-                    self.snake.len(),
-                    self.board_width,
-                    self.board_height
-                );
-                self.state = GameStatus::GameOver;
-                cx.notify();
-                return;
-            }
-
-            self.snake.push_front(next);
-            if ate_food {
-                self.score += 1;
-                self.high_score = self.high_score.max(self.score);
-                self.food = self.random_empty_cell();
-            }
-            cx.notify();
-        }
-    }
+fn on_off(enabled: bool) -> &'static str {
+    if enabled { "On" } else { "Off" }
 }
 
 impl Render for SnakeGame {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let frame_started = Instant::now();
+        let title = self.title_text();
+        if title != self.window_title {
+            window.set_window_title(&title);
+            window.set_window_edited(self.needs_attention);
+            self.window_title = title;
+        }
         let (status_text, status_color) = self.status_text();
         let is_focused = self.focus_handle(cx).is_focused(window);
 
-        let snake_lookup: HashSet<Cell> = self.snake.iter().copied().collect();
-        let head = self.snake.front().copied();
+        let engine = self.displayed_engine();
+        let frame = Frame::capture(engine);
+        let skin = &self.skins[self.skin_index];
+        let snake: Vec<Cell> = engine.snake().iter().copied().collect();
+        let body_sprites: HashMap<Cell, Sprite> = snake
+            .iter()
+            .enumerate()
+            .map(|(index, cell)| {
+                let toward =
+                    |other: Option<&Cell>| other.and_then(|other| cell.direction_to(*other));
+                let sprite = if index == 0 {
+                    Sprite::Head(engine.direction())
+                } else {
+                    Sprite::for_segment(toward(snake.get(index - 1)), toward(snake.get(index + 1)))
+                };
+                (*cell, sprite)
+            })
+            .collect();
+        let ghosting = frame.ghosting();
+        let weather = engine.weather();
         let cell_size = px(self.cell_px);
+        let Accessibility {
+            palette,
+            shapes,
+            grid_lines,
+            large_hud,
+            event_log,
+            screen_effects,
+            proximity_audio,
+        } = self.accessibility;
+        let (shake_x, shake_y) = self.juice.map(|juice| juice.offset()).unwrap_or_default();
+        let mode = self.game_mode();
+        let theme = self.themes.theme(mode);
+        // The record is only written at game over; until then a scored run
+        // beating it shows its own score as the best.
+        let best = if self.practice.is_none()
+            && self.drill.is_none()
+            && self.training.is_none()
+            && self.tutorial.is_none()
+        {
+            self.high_score.max(self.engine.score())
+        } else {
+            self.high_score
+        };
+        // Only the live run is raced; replays, demos and drills are not.
+        let pace = (std::ptr::eq(engine, &self.engine)
+            && self.practice.is_none()
+            && self.drill.is_none()
+            && self.training.is_none()
+            && self.tutorial.is_none()
+            && engine.state() != GameStatus::Ready)
+            .then(|| {
+                self.pace_records
+                    .pace(self.pace_reference, engine.config(), engine.elapsed())
+            })
+            .flatten();
+        let glyph = |text: &'static str, color: u32| {
+            div()
+                .text_size(px(self.cell_px * 0.7))
+                .line_height(cell_size)
+                .text_color(rgb(color))
+                .child(text)
+        };
 
+        let mirror = engine.config().mirror;
+        // A giant board is painted instead, so none of its cells are built.
+        let giant = BoardSize::is_giant(engine);
+        let (columns, rows) = if giant {
+            (0..0, 0..0)
+        } else {
+            self.camera.visible(engine)
+        };
+        let columns: Vec<i32> = if mirror {
+            columns.rev().collect()
+        } else {
+            columns.collect()
+        };
         let grid = div()
+            .debug_selector(|| "board".into())
             .flex()
             .flex_col()
-            .gap_1()
-            .children((0..self.board_height).map(|y| {
+            .when(grid_lines, |grid| grid.gap(px(GRID_GAP)))
+            .children(rows.map(|y| {
                 div()
                     .flex()
-                    .gap_1()
-                    .children((0..self.board_width).map(|x| {
+                    .when(grid_lines, |row| row.gap(px(GRID_GAP)))
+                    .children(columns.iter().map(|&x| {
                         let cell = Cell { x, y };
-                        let color = if Some(cell) == head {
-                            rgb(0x34d399)
-                        } else if cell == self.food {
-                            rgb(0xf97316)
-                        } else if snake_lookup.contains(&cell) {
-                            rgb(0x10b981)
-                        } else {
-                            rgb(0x0f172a)
+                        let view = frame.view(cell);
+                        let is_head = matches!(view, CellView::Head(_));
+                        let color = match (self.pulse, view) {
+                            (Some(pulse), CellView::Head(_) | CellView::Segment(_)) => {
+                                pulse.tint(view.color(palette, shapes, ghosting))
+                            }
+                            _ => view.color(palette, shapes, ghosting),
+                        };
+                        let marker = match view {
+                            CellView::Head(_) => shapes.then(|| {
+                                let eye = div()
+                                    .size(px(self.cell_px / 3.0))
+                                    .rounded_full()
+                                    .bg(rgb(palette.marker()));
+                                (palette.marker(), eye)
+                            }),
+                            CellView::Food(kind) => {
+                                let color = palette.food(kind);
+                                match (shapes, kind.glyph()) {
+                                    (false, _) => None,
+                                    (true, Some(text)) => Some((color, glyph(text, color))),
+                                    (true, None) => {
+                                        let dot = div()
+                                            .size(px(self.cell_px * 0.7))
+                                            .rounded_full()
+                                            .bg(rgb(color));
+                                        Some((color, dot))
+                                    }
+                                }
+                            }
+                            // The crack shows in every style, so a second pass is never a guess.
+                            CellView::CrackedFood => {
+                                let color = if shapes {
+                                    palette.food(FoodKind::Tough)
+                                } else {
+                                    palette.marker()
+                                };
+                                Some((color, glyph("✕", color)))
+                            }
+                            CellView::Segment(Some(effect)) => shapes.then(|| {
+                                (palette.marker(), glyph(effect.glyph(), palette.marker()))
+                            }),
+                            CellView::Pickup(power_up) => shapes.then(|| {
+                                (palette.marker(), glyph(power_up.glyph(), palette.marker()))
+                            }),
+                            CellView::Pellet => shapes.then(|| {
+                                let dot = div()
+                                    .size(px(self.cell_px / 3.0))
+                                    .rounded_full()
+                                    .bg(rgb(palette.marker()));
+                                (palette.marker(), dot)
+                            }),
+                            // A conveyor's or gate's arrow is its only sign of direction.
+                            CellView::Tile(tile) => (if mirror { tile.mirrored() } else { tile })
+                                .glyph()
+                                .filter(|_| {
+                                    shapes || matches!(tile, Tile::Conveyor(_) | Tile::Gate(_))
+                                })
+                                .map(|text| (palette.marker(), glyph(text, palette.marker()))),
+                            _ => None,
+                        }
+                        .map(|(color, marker)| {
+                            marker.debug_selector(|| format!("marker {x},{y} #{color:06x}"))
+                        });
+                        let sprite = match view {
+                            CellView::Fog => None,
+                            CellView::Food(kind) => skin.sprite(Sprite::Food(kind)),
+                            _ => body_sprites
+                                .get(&cell)
+                                .and_then(|sprite| skin.sprite(*sprite)),
+                        };
+                        let has_sprite = sprite.is_some();
+                        let (color, content) = match sprite {
+                            Some(image) => {
+                                let (background, ghost) = match view {
+                                    CellView::Head(effect) | CellView::Segment(effect) => (
+                                        effect.map_or(palette.empty(), |effect| {
+                                            palette.segment(effect)
+                                        }),
+                                        ghosting,
+                                    ),
+                                    _ => (palette.empty(), false),
+                                };
+                                let id = image.id();
+                                let image = img(image)
+                                    .size(cell_size)
+                                    .debug_selector(|| format!("sprite {x},{y} {id:x}"))
+                                    .when(ghost, |image| image.opacity(0.6));
+                                (background, Some(image.into_any_element()))
+                            }
+                            None => (color, marker.map(IntoElement::into_any_element)),
                         };
 
-                        div().w(cell_size).h(cell_size).rounded_sm().bg(color)
+                        div()
+                            .w(cell_size)
+                            .h(cell_size)
+                            .flex()
+                            .items_center()
+                            .justify_center()
+                            .when(grid_lines, |cell| cell.rounded_sm())
+                            .when(is_head && shapes && !has_sprite, |cell| {
+                                let direction = engine.direction();
+                                match if mirror {
+                                    direction.mirrored()
+                                } else {
+                                    direction
+                                } {
+                                    Direction::Up => cell.items_start(),
+                                    Direction::Down => cell.items_end(),
+                                    Direction::Left => cell.justify_start(),
+                                    Direction::Right => cell.justify_end(),
+                                }
+                                .p(px(2.0))
+                            })
+                            .bg(rgb(color))
+                            .debug_selector(|| format!("cell {x},{y} #{color:06x}"))
+                            .children(content)
                     }))
             }));
 
-        let instructions = [
-            "Enter to start or restart",
-            "Arrows / WASD to steer",
-            "Space to pause or resume",
-            "Esc to quit",
-        ];
+        let instructions = self.controls.instructions();
 
         div()
             .bg(rgb(0x020617))
@@ -295,7 +3236,11 @@ impl Render for SnakeGame {
             .flex()
             .flex_col()
             .track_focus(&self.focus_handle(cx))
-            .key_context("gpui-snake")
+            .key_context(self.key_context())
+            .capture_key_down(cx.listener(|this, _: &KeyDownEvent, _, cx| this.note_activity(cx)))
+            .on_any_mouse_down(
+                cx.listener(|this, _: &MouseDownEvent, _, cx| this.note_activity(cx)),
+            )
             .on_action(cx.listener(|this, _: &MoveUp, _, cx| this.handle_turn(Direction::Up, cx)))
             .on_action(
                 cx.listener(|this, _: &MoveDown, _, cx| this.handle_turn(Direction::Down, cx)),
@@ -306,29 +3251,172 @@ impl Render for SnakeGame {
             .on_action(
                 cx.listener(|this, _: &MoveRight, _, cx| this.handle_turn(Direction::Right, cx)),
             )
+            .on_action(cx.listener(|this, _: &TurnLeft, _, cx| this.handle_steer(Turn::Left, cx)))
+            .on_action(cx.listener(|this, _: &TurnRight, _, cx| this.handle_steer(Turn::Right, cx)))
             .on_action(cx.listener(|this, _: &RestartGame, _, cx| this.handle_restart(cx)))
             .on_action(cx.listener(|this, _: &TogglePause, _, cx| this.handle_toggle_pause(cx)))
+            .on_action(cx.listener(|this, _: &WatchReplay, _, cx| this.handle_watch_replay(cx)))
+            .on_action(cx.listener(|this, _: &MainMenu, _, cx| this.handle_main_menu(cx)))
+            .on_action(cx.listener(|this, _: &CycleLayout, _, cx| this.handle_cycle_layout(cx)))
+            .on_action(cx.listener(|this, _: &ToggleWeather, _, cx| this.handle_toggle_weather(cx)))
+            .on_action(
+                cx.listener(|this, _: &ToggleSelfCut, _, cx| this.handle_toggle_self_cut(cx)),
+            )
+            .on_action(cx.listener(|this, _: &ToggleGravity, _, cx| this.handle_toggle_gravity(cx)))
+            .on_action(cx.listener(|this, _: &ToggleMirror, _, cx| this.handle_toggle_mirror(cx)))
+            .on_action(
+                cx.listener(|this, _: &ToggleAdaptive, _, cx| this.handle_toggle_adaptive(cx)),
+            )
+            .on_action(cx.listener(|this, _: &CycleTheme, _, cx| this.handle_cycle_theme(cx)))
+            .on_action(cx.listener(|this, _: &ToggleEditor, _, cx| this.handle_toggle_editor(cx)))
+            .on_action(
+                cx.listener(|this, _: &ToggleBoardSize, _, cx| this.handle_toggle_board_size(cx)),
+            )
+            .on_action(cx.listener(|this, _: &CyclePace, _, cx| this.handle_cycle_pace(cx)))
+            .on_action(
+                cx.listener(|this, _: &CycleSpeedCurve, _, cx| this.handle_cycle_speed_curve(cx)),
+            )
+            .on_action(
+                cx.listener(|this, _: &TuneSpeedCurve, _, cx| this.handle_tune_speed_curve(cx)),
+            )
+            .on_action(cx.listener(|this, _: &TogglePracticeMode, _, cx| {
+                this.handle_toggle_practice_mode(cx)
+            }))
+            .on_action(
+                cx.listener(|this, _: &PracticeFaster, _, cx| this.handle_practice_faster(cx)),
+            )
+            .on_action(
+                cx.listener(|this, _: &PracticeSlower, _, cx| this.handle_practice_slower(cx)),
+            )
+            .on_action(cx.listener(|this, _: &ToggleFreeze, _, cx| this.handle_toggle_freeze(cx)))
+            .on_action(cx.listener(|this, _: &StepTick, _, cx| this.handle_step_tick(cx)))
+            .on_action(cx.listener(|this, _: &ToggleWallImmunity, _, cx| {
+                this.handle_toggle_wall_immunity(cx)
+            }))
+            .on_action(
+                cx.listener(|this, _: &ToggleSpeedrun, _, cx| this.handle_toggle_speedrun(cx)),
+            )
+            .on_action(
+                cx.listener(|this, _: &ExportPosition, _, cx| this.handle_export_position(cx)),
+            )
+            .on_action(cx.listener(|this, _: &ShowDaily, _, cx| this.handle_show_daily(cx)))
+            .on_action(cx.listener(|this, _: &ShowProfile, _, cx| this.handle_show_profile(cx)))
+            .on_action(cx.listener(|this, _: &ShowDrills, _, cx| this.handle_show_drills(cx)))
+            .on_action(cx.listener(|this, _: &ShowLobby, _, cx| this.handle_show_lobby(cx)))
+            .on_action(cx.listener(|this, _: &StartTutorial, _, cx| this.handle_start_tutorial(cx)))
+            .on_action(cx.listener(|this, _: &PracticePosition, _, cx| this.handle_practice(cx)))
+            .on_action(cx.listener(|this, _: &StartDaily, _, cx| this.handle_start_daily(cx)))
+            .on_action(
+                cx.listener(|this, _: &ImportPosition, _, cx| this.handle_import_position(cx)),
+            )
+            .on_action(cx.listener(|this, _: &SaveRun, _, cx| this.handle_save_run(cx)))
+            .on_action(cx.listener(|this, _: &ActivateSlot1, _, cx| this.handle_activate(0, cx)))
+            .on_action(cx.listener(|this, _: &ActivateSlot2, _, cx| this.handle_activate(1, cx)))
+            .on_action(cx.listener(|this, _: &ResumeRun, _, cx| this.handle_resume_run(cx)))
+            .on_action(cx.listener(|this, _: &RequestQuit, _, cx| this.handle_request_quit(cx)))
+            .on_action(cx.listener(|this, _: &DismissResume, _, cx| this.handle_dismiss_resume(cx)))
+            .on_action(cx.listener(|this, _: &CancelQuit, _, cx| this.handle_cancel_quit(cx)))
             .child(
                 div()
                     .flex()
                     .gap_4()
                     .items_center()
-                    .child(div().text_3xl().child(format!("Score: {}", self.score)))
                     .child(
                         div()
-                            .text_xl()
+                            .map(|this| {
+                                if large_hud {
+                                    this.text_size(px(48.0))
+                                } else {
+                                    this.text_3xl()
+                                }
+                            })
+                            .child(format!("Score: {}", engine.score())),
+                    )
+                    .when_some(pace, |this, pace| {
+                        let ahead = engine.score() >= pace;
+                        this.child(
+                            div()
+                                .map(|this| {
+                                    if large_hud {
+                                        this.text_3xl()
+                                    } else {
+                                        this.text_xl()
+                                    }
+                                })
+                                .text_color(rgb(if ahead { 0x34d399 } else { 0xf87171 }))
+                                .child(format!(
+                                    "Pace: {pace} ({}{})",
+                                    if ahead { "+" } else { "-" },
+                                    engine.score().abs_diff(pace)
+                                )),
+                        )
+                    })
+                    .child(
+                        div()
+                            .map(|this| {
+                                if large_hud {
+                                    this.text_3xl()
+                                } else {
+                                    this.text_xl()
+                                }
+                            })
                             .text_color(rgb(0xa5f3fc))
-                            .child(format!("Best: {}", self.high_score)),
+                            .child(format!("Best: {best} ({})", self.roster.active().name)),
                     )
+                    .when(mode != GameMode::FreePlay, |this| {
+                        this.child(
+                            div()
+                                .px_2()
+                                .rounded_md()
+                                .border_1()
+                                .border_color(rgb(theme.accent()))
+                                .text_sm()
+                                .text_color(rgb(theme.accent()))
+                                .child(mode.label()),
+                        )
+                    })
+                    .when(giant, |this| this.child(self.render_frame_budget()))
+                    .when(self.mutators_apply(), |this| {
+                        this.child(div().text_sm().text_color(rgb(0xfbbf24)).child(format!(
+                            "Mutators: {}",
+                            self.mutators.names().collect::<Vec<_>>().join(", ")
+                        )))
+                    })
+                    .when_some(engine.config().adaptive, |this, difficulty| {
+                        this.child(
+                            div()
+                                .map(|this| {
+                                    if large_hud {
+                                        this.text_xl()
+                                    } else {
+                                        this.text_base()
+                                    }
+                                })
+                                .text_color(rgb(0xfbbf24))
+                                .child(difficulty.label()),
+                        )
+                    })
                     .child(
                         div()
-                            .text_lg()
+                            .map(|this| {
+                                if large_hud {
+                                    this.text_2xl()
+                                } else {
+                                    this.text_lg()
+                                }
+                            })
                             .text_color(rgb(status_color))
                             .child(status_text),
                     )
                     .child(
                         div()
-                            .text_sm()
+                            .map(|this| {
+                                if large_hud {
+                                    this.text_lg()
+                                } else {
+                                    this.text_sm()
+                                }
+                            })
                             .text_color(rgb(0x94a3b8))
                             .child(if is_focused {
                                 "Focused"
@@ -338,17 +3426,162 @@ impl Render for SnakeGame {
                     )
                     .child(
                         div()
-                            .text_sm()
+                            .map(|this| {
+                                if large_hud {
+                                    this.text_lg()
+                                } else {
+                                    this.text_sm()
+                                }
+                            })
                             .child(format!("Tick: {}ms", self.tick_delay().as_millis())),
                     ),
             )
+            .child(Self::render_inventory(engine))
+            .children(
+                self.versus
+                    .as_ref()
+                    .and_then(Versus::remote)
+                    .map(|remote| self.render_opponent(remote)),
+            )
+            .children(Self::render_combo(engine))
+            .when_some(self.practice.as_ref(), |this, drill| {
+                this.child(div().text_sm().text_color(rgb(0xfbbf24)).child(format!(
+                    "Practice drill - attempt {}, survive to tick {} (now {})",
+                    drill.attempts(),
+                    drill.target_tick(),
+                    engine.ticks()
+                )))
+            })
+            .when_some(self.drill.as_ref(), |this, run| {
+                let drill = run.drill();
+                this.child(div().text_sm().text_color(rgb(0xfbbf24)).child(format!(
+                    "{} - attempt {}, eaten {}/{}{}",
+                    drill.label(),
+                    run.attempts(),
+                    engine.stats().eaten.min(drill.goal()),
+                    drill.goal(),
+                    self.drill_records
+                        .best(drill)
+                        .map(|best| format!(", best {}", speedrun::timer_text(best)))
+                        .unwrap_or_default()
+                )))
+            })
+            .when_some(self.training.as_ref(), |this, training| {
+                this.child(
+                    div()
+                        .text_sm()
+                        .text_color(rgb(0xfbbf24))
+                        .child(training.status_text()),
+                )
+            })
+            .children(Self::render_splits(
+                engine,
+                self.split_records.best(engine.config()),
+                self.split_records.ghost(engine.config()),
+            ))
+            .when_some(weather, |this, weather| {
+                this.child(
+                    div()
+                        .px_3()
+                        .py_2()
+                        .rounded_md()
+                        .bg(rgb(0x1e3a8a))
+                        .text_color(rgb(0xdbeafe))
+                        .child(weather.banner()),
+                )
+            })
             .child({
-                let overlay_text = match self.state {
-                    GameStatus::Ready => Some("Press Enter to start"),
-                    GameStatus::Paused => Some("Paused"),
-                    GameStatus::GameOver => Some("Game Over! press Enter"),
-                    GameStatus::Running => None,
+                let overlay_text: Option<SharedString> = match (self.screen, engine.state()) {
+                    _ if self.modal.is_some() => None,
+                    (Screen::Editor, _) => None,
+                    (Screen::Replay, _) => Some("Replay - Enter to restart, M for menu".into()),
+                    (Screen::Versus, _) => match self.versus.as_ref().map(Versus::status) {
+                        Some(VersusStatus::Playing)
+                            if self.versus.as_ref().is_some_and(Versus::is_stalled) =>
+                        {
+                            Some("Waiting for opponent...".into())
+                        }
+                        Some(VersusStatus::Over(outcome)) => {
+                            Some(format!("{} - M to leave", outcome.label()).into())
+                        }
+                        _ => None,
+                    },
+                    (_, GameStatus::Paused) if let Some(run) = &self.drill => {
+                        Some(match run.time() {
+                            Some(time) => format!(
+                                "{} passed in {}{} - Enter for a new game",
+                                run.drill().label(),
+                                speedrun::timer_text(time),
+                                if self.drill_records.best(run.drill()) == Some(time) {
+                                    ", a best time"
+                                } else {
+                                    ""
+                                }
+                            )
+                            .into(),
+                            None => format!(
+                                "{} attempt {} - Space to go",
+                                run.drill().label(),
+                                run.attempts()
+                            )
+                            .into(),
+                        })
+                    }
+                    (_, GameStatus::Paused) if let Some(drill) = &self.practice => {
+                        Some(if drill.is_complete() {
+                            format!(
+                                "Drill passed after {} attempts - Enter for a new game",
+                                drill.attempts()
+                            )
+                            .into()
+                        } else {
+                            format!("Practice attempt {} - Space to go", drill.attempts()).into()
+                        })
+                    }
+                    (_, GameStatus::Running)
+                        if self.training.as_ref().is_some_and(Training::frozen) =>
+                    {
+                        Some("Frozen - . to step, / to resume".into())
+                    }
+                    (_, GameStatus::Ready) => Some("Press Enter to start".into()),
+                    (_, GameStatus::Paused) => Some("Paused".into()),
+                    _ => None,
                 };
+                let summary = self
+                    .summary
+                    .as_ref()
+                    .filter(|_| self.screen == Screen::Summary)
+                    .map(|summary| self.render_summary(summary, cx));
+                let daily = self.modal_is(Modal::Daily).then(|| self.render_daily(cx));
+                let profile = self
+                    .modal_is(Modal::Profile)
+                    .then(|| self.render_profile(cx));
+                let drills = self.modal_is(Modal::Drills).then(|| self.render_drills(cx));
+                let lobby = (self.screen == Screen::Versus
+                    && self.modal.is_none()
+                    && self.versus.as_ref().and_then(Versus::local).is_none())
+                .then(|| self.render_lobby(cx));
+                let quit_prompt = self
+                    .modal_is(Modal::QuitPrompt)
+                    .then(|| self.render_quit_prompt(cx));
+                let resume_prompt = self
+                    .modal_is(Modal::ResumePrompt)
+                    .then(|| self.render_resume_prompt(cx));
+                let hint = (self.screen == Screen::Play
+                    && self.modal.is_none()
+                    && engine.state() == GameStatus::Ready)
+                    .then(|| self.hints.current(&self.profile, best));
+                let resume =
+                    (self.has_save
+                        && self.screen == Screen::Play
+                        && engine.state() == GameStatus::Ready)
+                        .then(|| {
+                            Self::button("resume-run", "Resume last game (C)")
+                                .text_base()
+                                .on_click(cx.listener(|this, _: &ClickEvent, _, cx| {
+                                    this.handle_resume_run(cx)
+                                }))
+                        });
 
                 div()
                     .p_4()
@@ -356,32 +3589,119 @@ impl Render for SnakeGame {
                     .bg(rgb(0x111827))
                     .shadow_lg()
                     .relative()
-                    .child(div().p_2().rounded_lg().bg(rgb(0x1f2937)).child(grid))
+                    .left(px(shake_x))
+                    .top(px(shake_y))
+                    .child(
+                        div()
+                            .p_2()
+                            .rounded_lg()
+                            .border_2()
+                            .border_color(rgb(theme.accent()))
+                            .bg(rgb(palette.board()))
+                            .when(self.attract.is_some(), |this| this.opacity(0.45))
+                            .map(|this| match &self.editor {
+                                Some(editor) if self.screen == Screen::Editor => {
+                                    this.child(self.render_editor(editor, cx))
+                                }
+                                _ if giant => {
+                                    this.child(self.render_painted_board(engine, frame_started))
+                                }
+                                _ => this.child(self.render_viewport(engine, grid)),
+                            }),
+                    )
+                    .when(!Camera::fits(engine), |this| {
+                        this.child(
+                            div()
+                                .absolute()
+                                .top(px(24.0))
+                                .right(px(24.0))
+                                .p_1()
+                                .rounded_sm()
+                                .bg(rgb(palette.board()))
+                                .opacity(0.85)
+                                .child(self.render_minimap(engine)),
+                        )
+                    })
+                    .when_some(self.juice, |this, juice| {
+                        this.child(Self::overlay(juice.flash()))
+                    })
                     .when_some(overlay_text, |this, message| {
+                        this.child(
+                            Self::overlay(0x020617A6)
+                                .flex_col()
+                                .gap_3()
+                                .text_xl()
+                                .text_color(rgb(0xf8fafc))
+                                .child(message)
+                                .when_some(hint, |this, hint| {
+                                    this.child(
+                                        div().text_sm().text_color(rgb(0xcbd5f5)).child(hint),
+                                    )
+                                })
+                                .when_some(resume, |this, button| this.child(button)),
+                        )
+                    })
+                    .when_some(self.tutorial.as_ref(), |this, tutorial| {
                         this.child(
                             div()
                                 .absolute()
-                                .top(px(0.))
-                                .bottom(px(0.))
-                                .left(px(0.))
-                                .right(px(0.))
+                                .top(px(16.0))
+                                .left(px(16.0))
+                                .right(px(16.0))
                                 .flex()
+                                .flex_col()
                                 .items_center()
-                                .justify_center()
-                                .bg(rgba(0x020617A6))
-                                .text_xl()
+                                .gap_1()
+                                .p_3()
+                                .rounded_lg()
+                                .bg(rgba(0x1e3a8ae6))
                                 .text_color(rgb(0xf8fafc))
-                                .child(message),
+                                .when_some(tutorial.progress(), |this, progress| {
+                                    this.child(
+                                        div().text_xs().text_color(rgb(0xbfdbfe)).child(progress),
+                                    )
+                                })
+                                .child(div().text_lg().child(tutorial.prompt())),
                         )
                     })
+                    .when_some(summary, |this, panel| {
+                        this.child(Self::overlay(0x020617CC).child(panel))
+                    })
+                    .when_some(daily, |this, panel| {
+                        this.child(Self::overlay(0x020617CC).child(panel))
+                    })
+                    .when_some(profile, |this, panel| {
+                        this.child(Self::overlay(0x020617CC).child(panel))
+                    })
+                    .when_some(drills, |this, panel| {
+                        this.child(Self::overlay(0x020617CC).child(panel))
+                    })
+                    .when_some(lobby, |this, panel| {
+                        this.child(Self::overlay(0x020617CC).child(panel))
+                    })
+                    .when_some(quit_prompt, |this, panel| {
+                        this.child(Self::overlay(0x020617CC).child(panel))
+                    })
+                    .when_some(resume_prompt, |this, panel| {
+                        this.child(Self::overlay(0x020617CC).child(panel))
+                    })
             })
             .child(
                 div()
                     .flex()
                     .flex_wrap()
                     .gap_3()
-                    .text_sm()
+                    .map(|this| {
+                        if large_hud {
+                            this.text_lg()
+                        } else {
+                            this.text_sm()
+                        }
+                    })
                     .text_color(rgb(0xcbd5f5))
+                    .when(self.controls.is_mirrored(), |row| {
+                        row.flex_row_reverse().justify_end()
+                    })
                     .children(instructions.into_iter().map(|text| {
                         div()
                             .px_3()
@@ -389,39 +3709,493 @@ impl Render for SnakeGame {
                             .rounded_md()
                             .bg(rgb(0x1e293b))
                             .child(text)
-                    })),
+                    }))
+                    .child(
+                        Self::button(
+                            "toggle-controls",
+                            format!("Layout: {}", self.controls.label()),
+                        )
+                        .on_click(cx.listener(
+                            |this, _: &ClickEvent, _, cx| this.handle_toggle_controls(cx),
+                        )),
+                    )
+                    .child(
+                        Self::button(
+                            "cycle-board",
+                            format!("Board (Tab): {}", self.engine.config().layout.label()),
+                        )
+                        .on_click(
+                            cx.listener(|this, _: &ClickEvent, _, cx| this.handle_cycle_layout(cx)),
+                        ),
+                    )
+                    .child(
+                        Self::button(
+                            "cycle-skin",
+                            format!("Skin: {}", self.skins[self.skin_index].name()),
+                        )
+                        .on_click(
+                            cx.listener(|this, _: &ClickEvent, _, cx| this.handle_cycle_skin(cx)),
+                        ),
+                    )
+                    .child(
+                        Self::button(
+                            "toggle-weather",
+                            format!("Weather (E): {}", on_off(self.engine.config().weather)),
+                        )
+                        .on_click(
+                            cx.listener(|this, _: &ClickEvent, _, cx| {
+                                this.handle_toggle_weather(cx)
+                            }),
+                        ),
+                    )
+                    .child(
+                        Self::button(
+                            "toggle-gravity",
+                            format!("Gravity (Q): {}", on_off(self.engine.config().gravity)),
+                        )
+                        .on_click(
+                            cx.listener(|this, _: &ClickEvent, _, cx| {
+                                this.handle_toggle_gravity(cx)
+                            }),
+                        ),
+                    )
+                    .child(
+                        Self::button(
+                            "toggle-mirror",
+                            format!("Mirror (Shift+M): {}", on_off(self.engine.config().mirror)),
+                        )
+                        .on_click(
+                            cx.listener(|this, _: &ClickEvent, _, cx| {
+                                this.handle_toggle_mirror(cx)
+                            }),
+                        ),
+                    )
+                    .child(
+                        Self::button(
+                            "toggle-adaptive",
+                            format!("Adaptive (Shift+A): {}", on_off(self.adaptive.enabled)),
+                        )
+                        .on_click(cx.listener(
+                            |this, _: &ClickEvent, _, cx| this.handle_toggle_adaptive(cx),
+                        )),
+                    )
+                    .child(
+                        Self::button(
+                            "cycle-theme",
+                            format!(
+                                "Theme (Shift+T): {} {}",
+                                theme.label(),
+                                if self.themes.is_overridden(mode) {
+                                    "(custom)".to_string()
+                                } else {
+                                    format!("({} default)", mode.label())
+                                }
+                            ),
+                        )
+                        .on_click(
+                            cx.listener(|this, _: &ClickEvent, _, cx| this.handle_cycle_theme(cx)),
+                        ),
+                    )
+                    .child(
+                        Self::button(
+                            "toggle-board-size",
+                            format!("Size (B): {}", self.board_size.label()),
+                        )
+                        .on_click(cx.listener(
+                            |this, _: &ClickEvent, _, cx| this.handle_toggle_board_size(cx),
+                        )),
+                    )
+                    .child(
+                        Self::button(
+                            "toggle-self-cut",
+                            format!("Self-cut (X): {}", on_off(self.engine.config().self_cut)),
+                        )
+                        .on_click(cx.listener(
+                            |this, _: &ClickEvent, _, cx| this.handle_toggle_self_cut(cx),
+                        )),
+                    )
+                    .children(
+                        DeathCause::ALL
+                            .into_iter()
+                            .enumerate()
+                            .map(|(index, cause)| {
+                                let fatal = self.engine.config().collisions.is_fatal(cause);
+                                Self::button(
+                                    ("toggle-collision", index),
+                                    format!("Fatal {}: {}", cause.collider(), on_off(fatal)),
+                                )
+                                .on_click(cx.listener(
+                                    move |this, _: &ClickEvent, _, cx| {
+                                        this.handle_toggle_collision(cause, cx)
+                                    },
+                                ))
+                            }),
+                    )
+                    .child(
+                        Self::button(
+                            "toggle-practice-mode",
+                            format!(
+                                "Practice mode (Shift+P): {}",
+                                on_off(self.training.is_some())
+                            ),
+                        )
+                        .on_click(cx.listener(
+                            |this, _: &ClickEvent, _, cx| this.handle_toggle_practice_mode(cx),
+                        )),
+                    )
+                    .child(Self::button("toggle-editor", "Editor (Shift+E)").on_click(
+                        cx.listener(|this, _: &ClickEvent, _, cx| this.handle_toggle_editor(cx)),
+                    ))
+                    .child(
+                        Self::button(
+                            "cycle-pace",
+                            format!("Pace (F): {}", self.pace_reference.label()),
+                        )
+                        .on_click(
+                            cx.listener(|this, _: &ClickEvent, _, cx| this.handle_cycle_pace(cx)),
+                        ),
+                    )
+                    .child(
+                        Self::button(
+                            "toggle-speedrun",
+                            match self.engine.config().goal_length {
+                                Some(goal) => format!("Speedrun (G): length {goal}"),
+                                None => "Speedrun (G): Off".into(),
+                            },
+                        )
+                        .on_click(cx.listener(
+                            |this, _: &ClickEvent, _, cx| this.handle_toggle_speedrun(cx),
+                        )),
+                    )
+                    .child(Self::button("show-daily", "Daily (H)").on_click(
+                        cx.listener(|this, _: &ClickEvent, _, cx| this.handle_show_daily(cx)),
+                    ))
+                    .child(Self::button("show-profile", "Profile (O)").on_click(
+                        cx.listener(|this, _: &ClickEvent, _, cx| this.handle_show_profile(cx)),
+                    ))
+                    .child(Self::button("show-drills", "Drills (Shift+D)").on_click(
+                        cx.listener(|this, _: &ClickEvent, _, cx| this.handle_show_drills(cx)),
+                    ))
+                    .child(Self::button("show-lobby", "Versus (V)").on_click(
+                        cx.listener(|this, _: &ClickEvent, _, cx| this.handle_show_lobby(cx)),
+                    ))
+                    .child(Self::button("start-tutorial", "Tutorial (U)").on_click(
+                        cx.listener(|this, _: &ClickEvent, _, cx| this.handle_start_tutorial(cx)),
+                    )),
+            )
+            .child(
+                div()
+                    .flex()
+                    .flex_wrap()
+                    .items_center()
+                    .gap_3()
+                    .map(|this| {
+                        if large_hud {
+                            this.text_lg()
+                        } else {
+                            this.text_sm()
+                        }
+                    })
+                    .text_color(rgb(0xcbd5f5))
+                    .child(div().text_color(rgb(0x94a3b8)).child("Accessibility"))
+                    .child(
+                        Self::button("cycle-palette", format!("Palette: {}", palette.label()))
+                            .on_click(cx.listener(|this, _: &ClickEvent, _, cx| {
+                                this.update_accessibility(
+                                    |settings| settings.palette = settings.palette.next(),
+                                    cx,
+                                )
+                            })),
+                    )
+                    .child(
+                        Self::button("toggle-shapes", format!("Shapes: {}", on_off(shapes)))
+                            .on_click(cx.listener(|this, _: &ClickEvent, _, cx| {
+                                this.update_accessibility(
+                                    |settings| settings.shapes = !settings.shapes,
+                                    cx,
+                                )
+                            })),
+                    )
+                    .child(
+                        Self::button(
+                            "toggle-grid-lines",
+                            format!("Grid lines: {}", on_off(grid_lines)),
+                        )
+                        .on_click(cx.listener(
+                            |this, _: &ClickEvent, _, cx| {
+                                this.update_accessibility(
+                                    |settings| settings.grid_lines = !settings.grid_lines,
+                                    cx,
+                                )
+                            },
+                        )),
+                    )
+                    .child(
+                        Self::button(
+                            "toggle-large-hud",
+                            format!("Large HUD: {}", on_off(large_hud)),
+                        )
+                        .on_click(cx.listener(
+                            |this, _: &ClickEvent, _, cx| {
+                                this.update_accessibility(
+                                    |settings| settings.large_hud = !settings.large_hud,
+                                    cx,
+                                )
+                            },
+                        )),
+                    )
+                    .child(
+                        Self::button(
+                            "toggle-event-log",
+                            format!("Event log: {}", on_off(event_log)),
+                        )
+                        .on_click(cx.listener(
+                            |this, _: &ClickEvent, _, cx| {
+                                this.update_accessibility(
+                                    |settings| settings.event_log = !settings.event_log,
+                                    cx,
+                                )
+                            },
+                        )),
+                    )
+                    .child(
+                        Self::button(
+                            "toggle-screen-effects",
+                            format!("Shake & flash: {}", on_off(screen_effects)),
+                        )
+                        .on_click(cx.listener(
+                            |this, _: &ClickEvent, _, cx| {
+                                this.update_accessibility(
+                                    |settings| settings.screen_effects = !settings.screen_effects,
+                                    cx,
+                                )
+                            },
+                        )),
+                    )
+                    .child(
+                        Self::button(
+                            "toggle-proximity-audio",
+                            format!("Proximity audio: {}", on_off(proximity_audio)),
+                        )
+                        .on_click(cx.listener(
+                            |this, _: &ClickEvent, _, cx| {
+                                this.update_accessibility(
+                                    |settings| settings.proximity_audio = !settings.proximity_audio,
+                                    cx,
+                                )
+                            },
+                        )),
+                    ),
+            )
+            .child(
+                div()
+                    .flex()
+                    .flex_wrap()
+                    .items_center()
+                    .gap_3()
+                    .map(|this| {
+                        if large_hud {
+                            this.text_lg()
+                        } else {
+                            this.text_sm()
+                        }
+                    })
+                    .text_color(rgb(0xcbd5f5))
+                    .child(div().text_color(rgb(0x94a3b8)).child("Speed"))
+                    .child(
+                        Self::button(
+                            "cycle-speed-curve",
+                            format!("Curve (Z): {}", engine.config().speed.label()),
+                        )
+                        .on_click(cx.listener(
+                            |this, _: &ClickEvent, _, cx| this.handle_cycle_speed_curve(cx),
+                        )),
+                    )
+                    .when_some(engine.config().speed.params_text(), |this, params| {
+                        this.child(
+                            Self::button("tune-speed-curve", format!("Tune (Shift+Z): {params}"))
+                                .on_click(cx.listener(|this, _: &ClickEvent, _, cx| {
+                                    this.handle_tune_speed_curve(cx)
+                                })),
+                        )
+                    })
+                    .child(self.render_speed_preview(engine.config().speed)),
+            )
+            .child(
+                div()
+                    .flex()
+                    .flex_wrap()
+                    .items_center()
+                    .gap_3()
+                    .map(|this| {
+                        if large_hud {
+                            this.text_lg()
+                        } else {
+                            this.text_sm()
+                        }
+                    })
+                    .text_color(rgb(0xcbd5f5))
+                    .child(div().text_color(rgb(0x94a3b8)).child("Storage"))
+                    .child(
+                        Self::button(
+                            "cycle-keep-replays",
+                            match self.retention.keep_replays {
+                                0 => "Keep replays: Off".to_string(),
+                                keep => format!("Keep replays: {keep}"),
+                            },
+                        )
+                        .on_click(cx.listener(
+                            |this, _: &ClickEvent, _, cx| {
+                                this.update_retention(
+                                    |retention| {
+                                        retention.keep_replays = retention.next_keep_replays()
+                                    },
+                                    cx,
+                                )
+                            },
+                        )),
+                    )
+                    .child(
+                        Self::button(
+                            "cycle-history-cap",
+                            format!(
+                                "Daily history cap: {}",
+                                retention::size_text(self.retention.history_cap)
+                            ),
+                        )
+                        .on_click(cx.listener(
+                            |this, _: &ClickEvent, _, cx| {
+                                this.update_retention(
+                                    |retention| {
+                                        retention.history_cap = retention.next_history_cap()
+                                    },
+                                    cx,
+                                )
+                            },
+                        )),
+                    )
+                    .child(format!(
+                        "Storage used: {}",
+                        retention::size_text(self.storage_used)
+                    )),
+            )
+            .child(
+                div()
+                    .flex()
+                    .flex_wrap()
+                    .items_center()
+                    .gap_3()
+                    .map(|this| {
+                        if large_hud {
+                            this.text_lg()
+                        } else {
+                            this.text_sm()
+                        }
+                    })
+                    .text_color(rgb(0xcbd5f5))
+                    .child(div().text_color(rgb(0x94a3b8)).child("Privacy"))
+                    .child(
+                        Self::button(
+                            "toggle-record-replays",
+                            format!("Record replays: {}", on_off(self.privacy.replays)),
+                        )
+                        .on_click(cx.listener(
+                            |this, _: &ClickEvent, _, cx| {
+                                this.update_privacy(
+                                    |privacy| privacy.replays = !privacy.replays,
+                                    cx,
+                                )
+                            },
+                        )),
+                    )
+                    .child(
+                        Self::button(
+                            "toggle-input-traces",
+                            format!(
+                                "Input traces (next launch): {}",
+                                on_off(self.privacy.input_traces)
+                            ),
+                        )
+                        .on_click(cx.listener(
+                            |this, _: &ClickEvent, _, cx| {
+                                this.update_privacy(
+                                    |privacy| privacy.input_traces = !privacy.input_traces,
+                                    cx,
+                                )
+                            },
+                        )),
+                    )
+                    .child(format!("Recording: {}", self.privacy.label()))
+                    .child(
+                        Self::button("delete-local-data", "Delete all local data")
+                            .text_color(rgb(0xfca5a5))
+                            .on_click(cx.listener(|this, _: &ClickEvent, _, cx| {
+                                this.handle_delete_local_data(cx)
+                            })),
+                    ),
             )
             .child({
-                let head = self.snake.front().copied();
+                let head = engine.snake().front().copied();
                 let head_str = head
                     .map(|c| format!("({}, {})", c.x, c.y))
                     .unwrap_or_else(|| "-".into());
                 let left_space = head.map(|c| c.x).unwrap_or_default();
-                let right_space = head.map(|c| self.board_width - c.x - 1).unwrap_or_default();
+                let right_space = head
+                    .map(|c| engine.board_width() - c.x - 1)
+                    .unwrap_or_default();
                 let top_space = head.map(|c| c.y).unwrap_or_default();
                 let bottom_space = head
-                    .map(|c| self.board_height - c.y - 1)
+                    .map(|c| engine.board_height() - c.y - 1)
                     .unwrap_or_default();
 
                 div()
                     .mt_2()
-                    .text_sm()
+                    .map(|this| {
+                        if large_hud {
+                            this.text_lg()
+                        } else {
+                            this.text_sm()
+                        }
+                    })
                     .text_color(rgb(0x93c5fd))
                     .child(format!(
                         "Board: {}x{}, Head: {}, Len: {}, Left: {}, Right: {}, Top: {}, Bottom: {}",
-                        self.board_width,
-                        self.board_height,
+                        engine.board_width(),
+                        engine.board_height(),
                         head_str,
-                        self.snake.len(),
+                        engine.snake().len(),
                         left_space,
                         right_space,
                         top_space,
                         bottom_space
                     ))
             })
+            .when(event_log, |this| {
+                this.child(
+                    div()
+                        .flex()
+                        .flex_col()
+                        .gap_1()
+                        .p_3()
+                        .rounded_md()
+                        .bg(rgb(0x1e293b))
+                        .map(|this| {
+                            if large_hud {
+                                this.text_lg()
+                            } else {
+                                this.text_sm()
+                            }
+                        })
+                        .text_color(rgb(0xe2e8f0))
+                        .child(div().text_color(rgb(0x94a3b8)).child("Event log"))
+                        .children(self.event_log.iter().map(|line| div().child(line.clone()))),
+                )
+            })
     }
 }
 
+impl EventEmitter<GameEvent> for SnakeGame {}
+
 impl Focusable for SnakeGame {
     fn focus_handle(&self, _: &gpui::App) -> FocusHandle {
         self.focus_handle.clone()
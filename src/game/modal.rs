@@ -0,0 +1,24 @@
+//! file: modal.rs
+//! author: Jacob Xie
+//! date: 2026/10/16 15:12:30 Friday
+//! brief:
+
+/// Dialogs and menus drawn over the board. While one is open the simulation
+/// holds still and board input is ignored.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Modal {
+    QuitPrompt,
+    /// Offered at launch when the last session ended in the middle of a run.
+    ResumePrompt,
+    Daily,
+    Profile,
+    Drills,
+}
+
+/// The open modal, remembering whether opening it paused a running game so
+/// closing it can pick the run back up.
+#[derive(Clone, Copy)]
+pub struct OpenModal {
+    pub modal: Modal,
+    pub resume: bool,
+}
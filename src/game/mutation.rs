@@ -0,0 +1,21 @@
+//! file: mutation.rs
+//! author: Jacob Xie
+//! date: 2026/10/16 18:30:44 Friday
+//! brief:
+
+use serde::{Deserialize, Serialize};
+
+/// Most pieces of extra food one mutation can drop.
+pub const MAX_EXTRA_FOOD: u8 = 3;
+/// How far mutators may push the pace either way, in percent of a tick.
+pub const MAX_SPEED_BIAS: i8 = 30;
+
+/// A change a mutator script made to the run. It is recorded with the
+/// player's inputs, so a replay of a scripted run plays back the same.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Mutation {
+    /// Drops this many pellets on free cells.
+    ExtraFood(u8),
+    /// Shifts the pace by this many percent; positive is faster.
+    Speed(i8),
+}
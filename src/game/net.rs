@@ -0,0 +1,132 @@
+//! file: net.rs
+//! author: Jacob Xie
+//! date: 2026/10/16 15:08:58 Friday
+//! brief:
+
+use std::io;
+
+use gpui::{BackgroundExecutor, Task};
+use serde::{Deserialize, Serialize};
+use smol::{
+    channel::{self, Receiver, Sender},
+    io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader},
+    net::{TcpListener, TcpStream},
+};
+
+use super::{GameConfig, Input};
+
+pub const DEFAULT_PORT: u16 = 7878;
+
+/// Longest line read from the other player; anything longer drops the
+/// connection rather than buffering without bound.
+const MAX_LINE: u64 = 1 << 20;
+
+/// What the two instances of a versus match tell each other, sent as one JSON
+/// object per line.
+#[derive(Serialize, Deserialize)]
+pub enum Message {
+    /// Sent by the host once the guest connects: the board and seed both
+    /// sides simulate. Boxed to keep per-tick frames small.
+    Start { config: Box<GameConfig>, seed: u64 },
+    /// A player's inputs for one tick.
+    Frame { tick: u64, inputs: Vec<Input> },
+}
+
+pub enum NetEvent {
+    Connected,
+    Message(Message),
+    Disconnected(String),
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    Host,
+    Guest,
+}
+
+/// A socket to the other player, driven by a task on the background
+/// executor. Dropping the connection closes it.
+pub struct Connection {
+    outgoing: Sender<Message>,
+    incoming: Receiver<NetEvent>,
+    _task: Task<()>,
+}
+
+impl Connection {
+    /// Listens on `address` for a guest (host) or connects to one (guest).
+    pub fn open(role: Role, address: String, executor: &BackgroundExecutor) -> Self {
+        let (outgoing, outgoing_rx) = channel::unbounded();
+        let (incoming_tx, incoming) = channel::unbounded();
+        let task = executor.spawn(async move {
+            let reason = match run(role, &address, outgoing_rx, &incoming_tx).await {
+                Ok(()) => "Opponent left".to_string(),
+                Err(err) => err.to_string(),
+            };
+            tracing::info!("Versus connection closed: {reason}");
+            incoming_tx.send(NetEvent::Disconnected(reason)).await.ok();
+        });
+        Self {
+            outgoing,
+            incoming,
+            _task: task,
+        }
+    }
+
+    pub fn send(&self, message: Message) {
+        if self.outgoing.try_send(message).is_err() {
+            tracing::warn!("Versus connection is closed, dropping message");
+        }
+    }
+
+    /// The next event from the socket task, if one has arrived.
+    pub fn poll(&self) -> Option<NetEvent> {
+        self.incoming.try_recv().ok()
+    }
+}
+
+async fn run(
+    role: Role,
+    address: &str,
+    outgoing: Receiver<Message>,
+    incoming: &Sender<NetEvent>,
+) -> io::Result<()> {
+    let stream = match role {
+        Role::Host => TcpListener::bind(address).await?.accept().await?.0,
+        Role::Guest => TcpStream::connect(address).await?,
+    };
+    stream.set_nodelay(true)?;
+    incoming.send(NetEvent::Connected).await.ok();
+
+    let mut writer = stream.clone();
+    let write = async {
+        while let Ok(message) = outgoing.recv().await {
+            let mut line = serde_json::to_string(&message)?;
+            line.push('\n');
+            writer.write_all(line.as_bytes()).await?;
+        }
+        Ok(())
+    };
+    let read = async {
+        let mut reader = BufReader::new(stream);
+        let mut line = String::new();
+        loop {
+            let bytes = (&mut reader).take(MAX_LINE).read_line(&mut line).await?;
+            if bytes == 0 {
+                break;
+            }
+            if !line.ends_with('\n') && bytes as u64 == MAX_LINE {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("message longer than {MAX_LINE} bytes"),
+                ));
+            }
+            let message = serde_json::from_str(&line)?;
+            if incoming.send(NetEvent::Message(message)).await.is_err() {
+                break;
+            }
+            line.clear();
+        }
+        Ok(())
+    };
+    smol::future::or(write, read).await
+}
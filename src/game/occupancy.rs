@@ -0,0 +1,126 @@
+//! file: occupancy.rs
+//! author: Jacob Xie
+//! date: 2026/10/16 14:28:29 Friday
+//! brief:
+
+use serde::{Deserialize, Serialize};
+
+use super::Cell;
+
+/// Per-cell count of snake segments kept in sync with the body so collision
+/// checks and food placement don't have to scan the whole snake. Counts rather
+/// than flags because a ghosting snake may overlap itself.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Occupancy {
+    width: i32,
+    height: i32,
+    cells: Vec<u8>,
+}
+
+impl Occupancy {
+    pub fn new(width: i32, height: i32) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![0; (width.max(0) * height.max(0)) as usize],
+        }
+    }
+
+    pub fn from_cells<'a>(
+        width: i32,
+        height: i32,
+        cells: impl IntoIterator<Item = &'a Cell>,
+    ) -> Self {
+        let mut occupancy = Self::new(width, height);
+        for cell in cells {
+            occupancy.insert(*cell);
+        }
+        occupancy
+    }
+
+    fn index(&self, cell: Cell) -> Option<usize> {
+        ((0..self.width).contains(&cell.x) && (0..self.height).contains(&cell.y))
+            .then(|| (cell.y * self.width + cell.x) as usize)
+    }
+
+    fn count(&self, cell: Cell) -> u8 {
+        self.index(cell).map_or(0, |index| self.cells[index])
+    }
+
+    pub fn contains(&self, cell: Cell) -> bool {
+        self.count(cell) > 0
+    }
+
+    pub fn insert(&mut self, cell: Cell) {
+        if let Some(index) = self.index(cell) {
+            self.cells[index] = self.cells[index].saturating_add(1);
+        }
+    }
+
+    pub fn remove(&mut self, cell: Cell) {
+        if let Some(index) = self.index(cell) {
+            self.cells[index] = self.cells[index].saturating_sub(1);
+        }
+    }
+
+    /// Cells whose segment count differs between `self` and `other`.
+    pub fn diff(&self, other: &Self) -> Vec<Cell> {
+        (0..self.height)
+            .flat_map(|y| (0..self.width).map(move |x| Cell { x, y }))
+            .filter(|cell| self.count(*cell) != other.count(*cell))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const A: Cell = Cell { x: 1, y: 2 };
+    const B: Cell = Cell { x: 3, y: 0 };
+
+    #[test]
+    fn counts_overlapping_segments() {
+        let mut occupancy = Occupancy::from_cells(4, 4, &[A, B, A]);
+        assert_eq!(occupancy.count(A), 2);
+        assert_eq!(occupancy.count(B), 1);
+        occupancy.remove(A);
+        assert!(occupancy.contains(A));
+        occupancy.remove(A);
+        assert!(!occupancy.contains(A));
+    }
+
+    #[test]
+    fn removing_an_empty_cell_stays_at_zero() {
+        let mut occupancy = Occupancy::new(4, 4);
+        occupancy.remove(A);
+        assert_eq!(occupancy.count(A), 0);
+        occupancy.insert(A);
+        assert_eq!(occupancy.count(A), 1);
+    }
+
+    #[test]
+    fn cells_off_the_board_are_ignored() {
+        let mut occupancy = Occupancy::new(4, 4);
+        let outside = [
+            Cell { x: -1, y: 0 },
+            Cell { x: 4, y: 0 },
+            Cell { x: 0, y: 4 },
+        ];
+        for cell in outside {
+            occupancy.insert(cell);
+            assert!(!occupancy.contains(cell));
+        }
+        assert!(occupancy == Occupancy::new(4, 4));
+    }
+
+    #[test]
+    fn diff_lists_cells_whose_counts_differ() {
+        let rebuilt = Occupancy::from_cells(4, 4, &[A, B]);
+        let mut tracked = rebuilt.clone();
+        assert!(tracked.diff(&rebuilt).is_empty());
+        tracked.insert(A);
+        tracked.remove(B);
+        assert_eq!(tracked.diff(&rebuilt), vec![B, A]);
+    }
+}
@@ -0,0 +1,187 @@
+//! file: pace.rs
+//! author: Jacob Xie
+//! date: 2026/10/16 17:15:54 Friday
+//! brief:
+
+use std::{collections::HashMap, io, time::Duration};
+
+use serde::{Deserialize, Serialize};
+
+use super::{GameConfig, storage};
+
+const PACE_FILE: &str = "pace.json";
+const PACE_RECORDS_FILE: &str = "pace_records.json";
+/// A configured target is paced evenly over this long.
+pub const TARGET_TIME: Duration = Duration::from_secs(180);
+const TARGETS: [u32; 3] = [25, 50, 100];
+
+/// Score over a run: each score reached and the elapsed time it was reached
+/// at, in order.
+pub type Timeline = Vec<(Duration, u32)>;
+
+/// What the live score is raced against in the HUD.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PaceReference {
+    #[default]
+    Off,
+    /// The highest-scoring run on the same board.
+    PersonalBest,
+    /// The highest-scoring daily challenge run.
+    DailyTop,
+    /// A set score, reached evenly over `TARGET_TIME`.
+    Target(u32),
+}
+
+impl PaceReference {
+    pub fn load() -> Self {
+        storage::read_json(&storage::data_path(PACE_FILE)).unwrap_or_default()
+    }
+
+    pub fn store(self) -> io::Result<()> {
+        storage::write_json(&storage::data_path(PACE_FILE), &self)
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            PaceReference::Off => PaceReference::PersonalBest,
+            PaceReference::PersonalBest => PaceReference::DailyTop,
+            PaceReference::DailyTop => PaceReference::Target(TARGETS[0]),
+            PaceReference::Target(target) => match TARGETS.iter().position(|t| *t == target) {
+                Some(index) if index + 1 < TARGETS.len() => {
+                    PaceReference::Target(TARGETS[index + 1])
+                }
+                _ => PaceReference::Off,
+            },
+        }
+    }
+
+    pub fn label(self) -> String {
+        match self {
+            PaceReference::Off => "Off".into(),
+            PaceReference::PersonalBest => "Personal best".into(),
+            PaceReference::DailyTop => "Daily top".into(),
+            PaceReference::Target(target) => format!("{target} in {}m", TARGET_TIME.as_secs() / 60),
+        }
+    }
+}
+
+/// Timelines of the best runs, keyed by board, plus the best daily run.
+#[derive(Default, Serialize, Deserialize)]
+pub struct PaceRecords {
+    best: HashMap<String, Timeline>,
+    #[serde(default)]
+    daily_top: Timeline,
+}
+
+impl PaceRecords {
+    pub fn load() -> Self {
+        storage::read_json(&storage::data_path(PACE_RECORDS_FILE)).unwrap_or_default()
+    }
+
+    fn key(config: &GameConfig) -> String {
+        format!(
+            "{}/{}x{}",
+            config.layout.label(),
+            config.board_width,
+            config.board_height
+        )
+    }
+
+    /// Keeps the run's timeline wherever it beats the stored one's final
+    /// score, and saves the records if anything changed.
+    pub fn record(&mut self, config: &GameConfig, timeline: &[(Duration, u32)]) {
+        let last = |timeline: &[(Duration, u32)]| timeline.last().map_or(0, |(_, score)| *score);
+        let score = last(timeline);
+        let mut changed = false;
+        let best = self.best.entry(Self::key(config)).or_default();
+        if score > last(best) {
+            *best = timeline.to_vec();
+            changed = true;
+        }
+        if config.daily.is_some() && score > last(&self.daily_top) {
+            self.daily_top = timeline.to_vec();
+            changed = true;
+        }
+        if changed
+            && let Err(err) = storage::write_json(&storage::data_path(PACE_RECORDS_FILE), self)
+        {
+            tracing::warn!("Failed to save pace records: {err}");
+        }
+    }
+
+    /// The score `reference` had reached after `elapsed` on this board, or
+    /// `None` when there is nothing to race.
+    pub fn pace(
+        &self,
+        reference: PaceReference,
+        config: &GameConfig,
+        elapsed: Duration,
+    ) -> Option<u32> {
+        match reference {
+            PaceReference::Off => None,
+            PaceReference::PersonalBest => self
+                .best
+                .get(&Self::key(config))
+                .map(|timeline| score_at(timeline, elapsed)),
+            PaceReference::DailyTop => {
+                (!self.daily_top.is_empty()).then(|| score_at(&self.daily_top, elapsed))
+            }
+            PaceReference::Target(target) => {
+                let share = elapsed.as_secs_f64() / TARGET_TIME.as_secs_f64();
+                Some((f64::from(target) * share.min(1.0)) as u32)
+            }
+        }
+    }
+}
+
+fn score_at(timeline: &[(Duration, u32)], elapsed: Duration) -> u32 {
+    let reached = timeline.partition_point(|(time, _)| *time <= elapsed);
+    reached.checked_sub(1).map_or(0, |index| timeline[index].1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::BoardLayout;
+
+    fn secs(secs: u64) -> Duration {
+        Duration::from_secs(secs)
+    }
+
+    #[test]
+    fn a_timeline_holds_each_score_until_the_next() {
+        let timeline = [(secs(2), 1), (secs(5), 3), (secs(9), 4)];
+        assert_eq!(score_at(&timeline, secs(1)), 0);
+        assert_eq!(score_at(&timeline, secs(2)), 1);
+        assert_eq!(score_at(&timeline, secs(8)), 3);
+        assert_eq!(score_at(&timeline, secs(60)), 4);
+    }
+
+    #[test]
+    fn a_target_is_paced_evenly() {
+        let records = PaceRecords::default();
+        let config = GameConfig::new(BoardLayout::Open);
+        let pace = |elapsed| records.pace(PaceReference::Target(50), &config, elapsed);
+        assert_eq!(pace(Duration::ZERO), Some(0));
+        assert_eq!(pace(TARGET_TIME / 2), Some(25));
+        assert_eq!(pace(TARGET_TIME * 2), Some(50));
+        assert_eq!(
+            records.pace(PaceReference::PersonalBest, &config, secs(5)),
+            None
+        );
+    }
+
+    #[test]
+    fn references_cycle_through_the_targets() {
+        let mut reference = PaceReference::Off;
+        let mut seen = Vec::new();
+        loop {
+            reference = reference.next();
+            if reference == PaceReference::Off {
+                break;
+            }
+            seen.push(reference);
+        }
+        assert_eq!(seen.len(), 2 + TARGETS.len());
+    }
+}
@@ -0,0 +1,75 @@
+//! file: pacing.rs
+//! author: Jacob Xie
+//! date: 2026/10/16 14:31:51 Friday
+//! brief:
+
+use std::time::{Duration, Instant};
+
+/// Average lateness (ms) above which the loop starts compensating.
+const JITTER_THRESHOLD_MS: f32 = 12.0;
+const SMOOTHING_TICKS: u32 = 30;
+const JITTER_DECAY: f32 = 0.8;
+
+/// Watches how late timer wake-ups arrive and, while the system is under
+/// load, shortens the following waits so ticks keep an even cadence.
+pub struct TickPacer {
+    started: Option<Instant>,
+    expected: Duration,
+    planned: Duration,
+    jitter_ms: f32,
+    lateness: Duration,
+    smoothing_ticks: u32,
+}
+
+impl TickPacer {
+    pub fn new() -> Self {
+        Self {
+            started: None,
+            expected: Duration::ZERO,
+            planned: Duration::ZERO,
+            jitter_ms: 0.0,
+            lateness: Duration::ZERO,
+            smoothing_ticks: 0,
+        }
+    }
+
+    /// Starts timing a wait for `delay`, returning how long to actually sleep.
+    pub fn begin(&mut self, delay: Duration) -> Duration {
+        self.started = Some(Instant::now());
+        self.expected = delay;
+        self.planned = if self.smoothing_ticks > 0 {
+            delay.saturating_sub(self.lateness.min(delay / 2))
+        } else {
+            delay
+        };
+        self.planned
+    }
+
+    /// Records the wake-up for the wait started by [`TickPacer::begin`].
+    pub fn finish(&mut self) {
+        let Some(started) = self.started.take() else {
+            return;
+        };
+        let lateness = started.elapsed().saturating_sub(self.planned);
+        self.jitter_ms =
+            self.jitter_ms * JITTER_DECAY + lateness.as_secs_f32() * 1000.0 * (1.0 - JITTER_DECAY);
+
+        if self.jitter_ms > JITTER_THRESHOLD_MS {
+            if self.smoothing_ticks == 0 {
+                tracing::debug!(
+                    "Timer jitter: avg lateness {:.1}ms on {}ms ticks, smoothing",
+                    self.jitter_ms,
+                    self.expected.as_millis()
+                );
+            }
+            self.lateness = lateness;
+            self.smoothing_ticks = SMOOTHING_TICKS;
+        } else if self.smoothing_ticks > 0 {
+            self.lateness = lateness;
+            self.smoothing_ticks -= 1;
+            if self.smoothing_ticks == 0 {
+                tracing::debug!("Timer jitter settled: avg lateness {:.1}ms", self.jitter_ms);
+            }
+        }
+    }
+}
@@ -0,0 +1,173 @@
+//! file: palette.rs
+//! author: Jacob Xie
+//! date: 2026/10/16 14:46:08 Friday
+//! brief:
+
+use serde::{Deserialize, Serialize};
+
+use super::{FoodKind, PowerUp, SegmentEffect};
+
+const PORTAL_COLORS: [u32; 4] = [0x8b5cf6, 0x06b6d4, 0xec4899, 0xeab308];
+/// Darkened Okabe-Ito colors. The full-strength ones already mark food,
+/// segments and pickups, so portals are told apart from those by lightness.
+const COLORBLIND_PORTALS: [u32; 4] = [0x7a3d5c, 0x00563f, 0x8a5f00, 0x1d5a80];
+const HIGH_CONTRAST_PORTALS: [u32; 4] = [0x8000ff, 0x008080, 0x804000, 0xff80c0];
+
+/// Board colors. Every cell color drawn on the grid comes from here.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Palette {
+    #[default]
+    Standard,
+    Colorblind,
+    HighContrast,
+}
+
+impl Palette {
+    pub fn next(self) -> Self {
+        match self {
+            Palette::Standard => Palette::Colorblind,
+            Palette::Colorblind => Palette::HighContrast,
+            Palette::HighContrast => Palette::Standard,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Palette::Standard => "Standard",
+            Palette::Colorblind => "Colorblind-safe",
+            Palette::HighContrast => "High contrast",
+        }
+    }
+
+    /// Background behind the cells, visible as grid lines between them.
+    pub fn board(self) -> u32 {
+        match self {
+            Palette::Standard | Palette::Colorblind => 0x1f2937,
+            Palette::HighContrast => 0x404040,
+        }
+    }
+
+    pub fn empty(self) -> u32 {
+        match self {
+            Palette::Standard | Palette::Colorblind => 0x0f172a,
+            Palette::HighContrast => 0x000000,
+        }
+    }
+
+    /// Cells hidden by rain.
+    pub fn fog(self) -> u32 {
+        match self {
+            Palette::Standard | Palette::Colorblind => 0x020617,
+            Palette::HighContrast => 0x000000,
+        }
+    }
+
+    pub fn wall(self) -> u32 {
+        match self {
+            Palette::Standard => 0x64748b,
+            Palette::Colorblind => 0x999999,
+            Palette::HighContrast => 0xffffff,
+        }
+    }
+
+    pub fn conveyor(self) -> u32 {
+        match self {
+            Palette::Standard => 0x0e7490,
+            Palette::Colorblind => 0x56b4e9,
+            Palette::HighContrast => 0x00aaff,
+        }
+    }
+
+    pub fn gate(self) -> u32 {
+        match self {
+            Palette::Standard => 0xf59e0b,
+            Palette::Colorblind => 0xe69f00,
+            Palette::HighContrast => 0xffff00,
+        }
+    }
+
+    pub fn portal(self, id: u8) -> u32 {
+        let colors = match self {
+            Palette::Standard => &PORTAL_COLORS,
+            Palette::Colorblind => &COLORBLIND_PORTALS,
+            Palette::HighContrast => &HIGH_CONTRAST_PORTALS,
+        };
+        colors[id as usize % colors.len()]
+    }
+
+    pub fn head(self) -> u32 {
+        match self {
+            Palette::Standard => 0x34d399,
+            Palette::Colorblind => 0x56b4e9,
+            Palette::HighContrast => 0xffff00,
+        }
+    }
+
+    pub fn body(self, ghosting: bool) -> u32 {
+        match (self, ghosting) {
+            (Palette::Standard, false) => 0x10b981,
+            (Palette::Standard, true) => 0x5eead4,
+            (Palette::Colorblind, false) => 0x0072b2,
+            (Palette::Colorblind, true) => 0x9ecae1,
+            (Palette::HighContrast, false) => 0x00ff00,
+            (Palette::HighContrast, true) => 0x808080,
+        }
+    }
+
+    /// Markers drawn on top of a filled cell, such as the head's eye.
+    pub fn marker(self) -> u32 {
+        match self {
+            Palette::Standard | Palette::Colorblind => 0x020617,
+            Palette::HighContrast => 0x000000,
+        }
+    }
+
+    pub fn food(self, kind: FoodKind) -> u32 {
+        match (self, kind) {
+            (Palette::Standard, _) => kind.color(),
+            (Palette::Colorblind, FoodKind::Plain) => 0xd55e00,
+            (Palette::Colorblind, FoodKind::Armored) => 0xcc79a7,
+            (Palette::Colorblind, FoodKind::Glowing) => 0xf0e442,
+            (Palette::Colorblind, FoodKind::Tough) => 0x0072b2,
+            (Palette::Colorblind, FoodKind::Heavy) => 0xa0522d,
+            (Palette::Colorblind, FoodKind::Shrink) => 0xb3e0ff,
+            (Palette::HighContrast, FoodKind::Plain) => 0xff0000,
+            (Palette::HighContrast, FoodKind::Armored) => 0x00ffff,
+            (Palette::HighContrast, FoodKind::Glowing) => 0xff8800,
+            (Palette::HighContrast, FoodKind::Tough) => 0xff00ff,
+            (Palette::HighContrast, FoodKind::Heavy) => 0xff4000,
+            (Palette::HighContrast, FoodKind::Shrink) => 0x80ffff,
+        }
+    }
+
+    pub fn segment(self, effect: SegmentEffect) -> u32 {
+        match (self, effect) {
+            (Palette::Standard, _) => effect.color(),
+            (Palette::Colorblind, SegmentEffect::Armored) => 0xcc79a7,
+            (Palette::Colorblind, SegmentEffect::Glowing) => 0xf0e442,
+            (Palette::HighContrast, SegmentEffect::Armored) => 0x00ffff,
+            (Palette::HighContrast, SegmentEffect::Glowing) => 0xff8800,
+        }
+    }
+
+    /// Segments cut off in self-cut mode.
+    pub fn pellet(self) -> u32 {
+        match self {
+            Palette::Standard => 0xa3e635,
+            Palette::Colorblind => 0x8c6d46,
+            Palette::HighContrast => 0x80ff80,
+        }
+    }
+
+    pub fn pickup(self, power_up: PowerUp) -> u32 {
+        match (self, power_up) {
+            (Palette::Standard, _) => power_up.color(),
+            (Palette::Colorblind, PowerUp::SlowMotion) => 0x009e73,
+            (Palette::Colorblind, PowerUp::Ghost) => 0xffffff,
+            (Palette::Colorblind, PowerUp::DoublePoints) => 0xe69f00,
+            (Palette::HighContrast, PowerUp::SlowMotion) => 0x0088ff,
+            (Palette::HighContrast, PowerUp::Ghost) => 0xc0c0c0,
+            (Palette::HighContrast, PowerUp::DoublePoints) => 0xff00ff,
+        }
+    }
+}
@@ -0,0 +1,21 @@
+//! file: pellet.rs
+//! author: Jacob Xie
+//! date: 2026/10/16 17:11:11 Friday
+//! brief:
+
+use serde::{Deserialize, Serialize};
+
+use super::Cell;
+
+/// Pellets eaten per point scored.
+pub const PELLETS_PER_POINT: u32 = 2;
+/// Ticks a pellet stays on the board before it fades.
+pub const PELLET_LIFETIME: u64 = 60;
+
+/// A body segment cut off when a self-cutting snake bites itself, left on the
+/// board to be collected back for part of a point.
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub struct Pellet {
+    pub cell: Cell,
+    pub expires_at: u64,
+}
@@ -0,0 +1,73 @@
+//! file: perf.rs
+//! author: Jacob Xie
+//! date: 2026/10/16 18:43:54 Friday
+//! brief:
+
+use std::{collections::VecDeque, time::Duration};
+
+use super::FRAME;
+
+/// Time a frame may take before the giant board warns about it: one frame
+/// at the rate screen effects are drawn.
+pub const FRAME_BUDGET: Duration = FRAME;
+/// Frames averaged over, so one hitch does not flash the warning.
+const WINDOW: usize = 30;
+
+/// Recent frame times, from the start of a render until the board has been
+/// painted. Filled in from the giant board's paint callback, so it doubles as
+/// a live benchmark of the renderer.
+#[derive(Default)]
+pub struct FrameMeter {
+    frames: VecDeque<Duration>,
+}
+
+impl FrameMeter {
+    pub fn record(&mut self, frame: Duration) {
+        if self.frames.len() == WINDOW {
+            self.frames.pop_front();
+        }
+        self.frames.push_back(frame);
+    }
+
+    pub fn clear(&mut self) {
+        self.frames.clear();
+    }
+
+    pub fn average(&self) -> Option<Duration> {
+        let count = u32::try_from(self.frames.len())
+            .ok()
+            .filter(|count| *count > 0)?;
+        Some(self.frames.iter().sum::<Duration>() / count)
+    }
+
+    pub fn worst(&self) -> Option<Duration> {
+        self.frames.iter().max().copied()
+    }
+
+    pub fn over_budget(&self) -> bool {
+        self.average().is_some_and(|average| average > FRAME_BUDGET)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn one_slow_frame_is_not_a_warning() {
+        let mut meter = FrameMeter::default();
+        assert_eq!(meter.average(), None);
+        for _ in 0..WINDOW - 1 {
+            meter.record(Duration::from_millis(4));
+        }
+        meter.record(Duration::from_millis(40));
+        assert!(!meter.over_budget());
+        assert_eq!(meter.worst(), Some(Duration::from_millis(40)));
+
+        for _ in 0..WINDOW {
+            meter.record(Duration::from_millis(20));
+        }
+        assert!(meter.over_budget());
+        assert_eq!(meter.average(), Some(Duration::from_millis(20)));
+    }
+}
@@ -0,0 +1,80 @@
+//! file: placement.rs
+//! author: Jacob Xie
+//! date: 2026/10/16 17:49:42 Friday
+//! brief:
+
+use std::io;
+
+use gpui::{App, Bounds, DisplayId, Pixels, Size, Window, WindowBounds};
+use serde::{Deserialize, Serialize};
+
+use super::storage;
+
+const PLACEMENT_FILE: &str = "window.json";
+
+/// Where the window sat when the app last closed. The display is remembered by
+/// its stable id, so the window only goes back there while that monitor is
+/// still connected.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct WindowPlacement {
+    display: Option<String>,
+    bounds: Bounds<Pixels>,
+    maximized: bool,
+}
+
+impl WindowPlacement {
+    pub fn load() -> Option<Self> {
+        storage::read_json(&storage::shared_path(PLACEMENT_FILE)).ok()
+    }
+
+    pub fn store(&self) -> io::Result<()> {
+        storage::write_json(&storage::shared_path(PLACEMENT_FILE), self)
+    }
+
+    pub fn capture(window: &Window, cx: &App) -> Self {
+        let bounds = window.window_bounds();
+        Self {
+            display: window
+                .display(cx)
+                .and_then(|display| display.uuid().ok())
+                .map(|uuid| uuid.to_string()),
+            bounds: bounds.get_bounds(),
+            maximized: matches!(bounds, WindowBounds::Maximized(_)),
+        }
+    }
+
+    /// Where to open the main window: back where it was if its display is
+    /// still connected and the window would still show on it, otherwise
+    /// centered on the display the user is working on.
+    pub fn initial_bounds(saved: Option<&Self>, size: Size<Pixels>, cx: &mut App) -> WindowBounds {
+        let restored = saved.filter(|saved| {
+            cx.displays().iter().any(|display| {
+                display.uuid().ok().map(|uuid| uuid.to_string()) == saved.display
+                    && display.bounds().intersects(&saved.bounds)
+            })
+        });
+        match restored {
+            Some(saved) if saved.maximized => WindowBounds::Maximized(saved.bounds),
+            Some(saved) => WindowBounds::Windowed(saved.bounds),
+            None => WindowBounds::Windowed(Bounds::centered(current_display(cx), size, cx)),
+        }
+    }
+}
+
+/// The display under the cursor in the app's active window, falling back to
+/// the display that window is on. With no window open yet there is nothing to
+/// go by, and `None` leaves the choice to the primary display.
+fn current_display(cx: &mut App) -> Option<DisplayId> {
+    let window = cx.active_window()?;
+    window
+        .update(cx, |_, window, cx| {
+            let cursor = window.bounds().origin + window.mouse_position();
+            cx.displays()
+                .into_iter()
+                .find(|display| display.bounds().contains(&cursor))
+                .or_else(|| window.display(cx))
+                .map(|display| display.id())
+        })
+        .ok()
+        .flatten()
+}
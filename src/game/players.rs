@@ -0,0 +1,160 @@
+//! file: players.rs
+//! author: Jacob Xie
+//! date: 2026/10/16 17:30:50 Friday
+//! brief:
+
+use std::io;
+
+use serde::{Deserialize, Serialize};
+
+use super::storage;
+
+const ROSTER_FILE: &str = "players.json";
+const PLAYERS_DIR: &str = "players";
+
+/// A named player, whose records, stats and settings live apart from
+/// everyone else's.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Player {
+    pub name: String,
+    /// Where the player's data lives, relative to the data directory. The
+    /// first player's is the data directory itself, so data saved before
+    /// there were players stays theirs.
+    dir: String,
+}
+
+/// Everyone who has played on this machine and who is playing now.
+#[derive(Serialize, Deserialize)]
+pub struct Roster {
+    players: Vec<Player>,
+    active: usize,
+}
+
+impl Default for Roster {
+    fn default() -> Self {
+        Self {
+            players: vec![Player {
+                name: "Player 1".into(),
+                dir: String::new(),
+            }],
+            active: 0,
+        }
+    }
+}
+
+impl Roster {
+    pub fn load() -> Self {
+        let roster: Self =
+            storage::read_json(&storage::shared_path(ROSTER_FILE)).unwrap_or_default();
+        if roster.players.get(roster.active).is_some() {
+            roster
+        } else {
+            Self::default()
+        }
+    }
+
+    fn store(&self) -> io::Result<()> {
+        storage::write_json(&storage::shared_path(ROSTER_FILE), self)
+    }
+
+    /// Picks the player to start as: `name` if given, created if new,
+    /// otherwise whoever played last.
+    pub fn launch(name: Option<&str>) {
+        let mut roster = Self::load();
+        let index = match name {
+            Some(name) => roster.find_or_add(name),
+            None => roster.active,
+        };
+        roster.select(index);
+    }
+
+    pub fn players(&self) -> &[Player] {
+        &self.players
+    }
+
+    pub fn active(&self) -> &Player {
+        &self.players[self.active]
+    }
+
+    pub fn active_index(&self) -> usize {
+        self.active
+    }
+
+    /// Makes the player at `index` the one whose data is read and written.
+    pub fn select(&mut self, index: usize) {
+        let Some(player) = self.players.get(index) else {
+            return;
+        };
+        self.active = index;
+        storage::set_player_dir(&player.dir);
+        tracing::info!("Playing as {}", player.name);
+        if let Err(err) = self.store() {
+            tracing::warn!("Failed to save players: {err}");
+        }
+    }
+
+    /// Adds a player with the first free "Player N" name, returning its index.
+    pub fn add_next(&mut self) -> usize {
+        let name = (1..)
+            .map(|number| format!("Player {number}"))
+            .find(|name| self.position(name).is_none())
+            .expect("some player number is free");
+        self.add(&name)
+    }
+
+    fn find_or_add(&mut self, name: &str) -> usize {
+        self.position(name).unwrap_or_else(|| self.add(name))
+    }
+
+    fn position(&self, name: &str) -> Option<usize> {
+        self.players
+            .iter()
+            .position(|player| player.name.eq_ignore_ascii_case(name))
+    }
+
+    fn add(&mut self, name: &str) -> usize {
+        let slug: String = name
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() {
+                    c.to_ascii_lowercase()
+                } else {
+                    '-'
+                }
+            })
+            .collect();
+        let dir = (1..)
+            .map(|n| match n {
+                1 => format!("{PLAYERS_DIR}/{slug}"),
+                n => format!("{PLAYERS_DIR}/{slug}-{n}"),
+            })
+            .find(|dir| self.players.iter().all(|player| player.dir != *dir))
+            .expect("some directory is free");
+        self.players.push(Player {
+            name: name.to_string(),
+            dir,
+        });
+        self.players.len() - 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_players_get_free_names_and_their_own_folders() {
+        let mut roster = Roster::default();
+        let second = roster.add_next();
+        assert_eq!(roster.players()[second].name, "Player 2");
+        let third = roster.find_or_add("Al Ice");
+        let fourth = roster.find_or_add("al.ice");
+        assert_eq!(roster.find_or_add("AL ICE"), third);
+        let dirs: Vec<&str> = roster.players().iter().map(|p| p.dir.as_str()).collect();
+        assert_eq!(
+            dirs,
+            ["", "players/player-2", "players/al-ice", "players/al-ice-2"]
+        );
+        assert_eq!(fourth, 3);
+    }
+}
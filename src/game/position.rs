@@ -0,0 +1,202 @@
+//! file: position.rs
+//! author: Jacob Xie
+//! date: 2026/10/16 14:48:04 Friday
+//! brief:
+
+use serde::{Deserialize, Serialize};
+
+use super::{Cell, Direction, Engine, Level, Tile};
+
+const CODE_PREFIX: &str = "SNK1";
+
+/// A snapshot of a board that can be shared as a one-line code and played
+/// again as a puzzle. The code is the level text with rows joined by `/`,
+/// followed by the snake cells from head to tail, the food, the heading and,
+/// on boards whose edges wrap around, a trailing `W`:
+/// `SNK1;<rows>;<x,y:x,y:...>;<x,y>;<U|D|L|R>[;W]`.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Position {
+    level: Level,
+    snake: Vec<Cell>,
+    food: Cell,
+    direction: Direction,
+    #[serde(default)]
+    wrap: bool,
+}
+
+impl Position {
+    pub fn capture(engine: &Engine) -> Self {
+        Self {
+            level: Level::new("Puzzle", engine.tiles().clone()),
+            snake: engine.snake().iter().copied().collect(),
+            food: engine.food(),
+            direction: engine.direction(),
+            wrap: engine.config().wrap,
+        }
+    }
+
+    pub fn level(&self) -> &Level {
+        &self.level
+    }
+
+    pub fn snake(&self) -> &[Cell] {
+        &self.snake
+    }
+
+    pub fn food(&self) -> Cell {
+        self.food
+    }
+
+    pub fn direction(&self) -> Direction {
+        self.direction
+    }
+
+    pub fn wrap(&self) -> bool {
+        self.wrap
+    }
+
+    pub fn encode(&self) -> String {
+        let rows = self.level.to_text().replace('\n', "/");
+        let snake: Vec<String> = self.snake.iter().map(|cell| encode_cell(*cell)).collect();
+        let direction = match self.direction {
+            Direction::Up => 'U',
+            Direction::Down => 'D',
+            Direction::Left => 'L',
+            Direction::Right => 'R',
+        };
+        format!(
+            "{CODE_PREFIX};{rows};{};{};{direction}{}",
+            snake.join(":"),
+            encode_cell(self.food),
+            if self.wrap { ";W" } else { "" }
+        )
+    }
+
+    pub fn decode(code: &str) -> Result<Self, String> {
+        let parts: Vec<&str> = code.trim().split(';').collect();
+        let (prefix, rows, snake, food, direction, wrap) = match parts[..] {
+            [prefix, rows, snake, food, direction] => (prefix, rows, snake, food, direction, false),
+            [prefix, rows, snake, food, direction, "W"] => {
+                (prefix, rows, snake, food, direction, true)
+            }
+            _ => return Err("position code must have 5 sections, or 6 on a wrapping board".into()),
+        };
+        if prefix != CODE_PREFIX {
+            return Err(format!("unknown position code version {prefix:?}"));
+        }
+        let level = Level::parse("Puzzle", &rows.replace('/', "\n"))?;
+        let tiles = level.tiles();
+        let snake = snake
+            .split(':')
+            .map(decode_cell)
+            .collect::<Result<Vec<_>, _>>()?;
+        let food = decode_cell(food)?;
+        let direction = match direction {
+            "U" => Direction::Up,
+            "D" => Direction::Down,
+            "L" => Direction::Left,
+            "R" => Direction::Right,
+            other => return Err(format!("unknown direction {other:?}")),
+        };
+
+        let on_board = |cell: &Cell| {
+            (0..tiles.width()).contains(&cell.x) && (0..tiles.height()).contains(&cell.y)
+        };
+        for cell in snake.iter().chain([&food]) {
+            if !on_board(cell) || tiles.get(*cell) == Tile::Wall {
+                return Err(format!(
+                    "cell ({}, {}) is off the board or in a wall",
+                    cell.x, cell.y
+                ));
+            }
+        }
+        if matches!(tiles.get(food), Tile::Portal(_)) {
+            return Err(format!("food ({}, {}) is on a portal", food.x, food.y));
+        }
+        for (index, cell) in snake.iter().enumerate() {
+            if snake[..index].contains(cell) || *cell == food {
+                return Err(format!("cell ({}, {}) is used twice", cell.x, cell.y));
+            }
+        }
+        for pair in snake.windows(2) {
+            // On a wrapping board the snake may cross an edge between two segments.
+            let distance = |a: i32, b: i32, size: i32| {
+                let apart = (a - b).abs();
+                if wrap { apart.min(size - apart) } else { apart }
+            };
+            let adjacent = distance(pair[0].x, pair[1].x, tiles.width())
+                + distance(pair[0].y, pair[1].y, tiles.height())
+                == 1;
+            let through_portal = matches!(tiles.get(pair[0]), Tile::Portal(_))
+                || matches!(tiles.get(pair[1]), Tile::Portal(_));
+            if !adjacent && !through_portal {
+                return Err(format!(
+                    "snake is broken between ({}, {}) and ({}, {})",
+                    pair[0].x, pair[0].y, pair[1].x, pair[1].y
+                ));
+            }
+        }
+
+        Ok(Self {
+            level,
+            snake,
+            food,
+            direction,
+            wrap,
+        })
+    }
+}
+
+fn encode_cell(cell: Cell) -> String {
+    format!("{},{}", cell.x, cell.y)
+}
+
+fn decode_cell(text: &str) -> Result<Cell, String> {
+    let (x, y) = text
+        .split_once(',')
+        .ok_or_else(|| format!("bad cell {text:?}"))?;
+    let parse = |value: &str| {
+        value
+            .trim()
+            .parse::<i32>()
+            .map_err(|_| format!("bad cell {text:?}"))
+    };
+    Ok(Cell {
+        x: parse(x)?,
+        y: parse(y)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{BoardLayout, GameConfig};
+
+    #[test]
+    fn code_round_trips() {
+        let engine = Engine::new(&GameConfig::new(BoardLayout::Open), 3);
+        let position = Position::capture(&engine);
+        let decoded = Position::decode(&position.encode()).unwrap();
+        assert_eq!(decoded.snake(), position.snake());
+        assert_eq!(decoded.food(), position.food());
+        assert_eq!(decoded.direction(), position.direction());
+        assert!(!decoded.wrap());
+        assert_eq!(decoded.encode(), position.encode());
+    }
+
+    #[test]
+    fn snake_may_cross_the_edge_of_a_wrapping_board() {
+        let code = "SNK1;..../..../....;0,1:3,1:2,1;1,2;R;W";
+        let position = Position::decode(code).unwrap();
+        assert!(position.wrap());
+        assert!(Position::decode(&position.encode()).unwrap().wrap());
+        let err = Position::decode("SNK1;..../..../....;0,1:3,1:2,1;1,2;R").err();
+        assert!(err.is_some_and(|err| err.contains("broken")));
+    }
+
+    #[test]
+    fn food_on_a_portal_is_rejected() {
+        let err = Position::decode("SNK1;1..1/..../....;2,1:3,1;0,0;L").err();
+        assert!(err.is_some_and(|err| err.contains("portal")));
+    }
+}
@@ -0,0 +1,100 @@
+//! file: powerup.rs
+//! author: Jacob Xie
+//! date: 2026/10/16 14:35:55 Friday
+//! brief:
+
+use serde::{Deserialize, Serialize};
+
+use super::Cell;
+
+const INVENTORY_SLOTS: usize = 2;
+/// A power-up is dropped on the board every time the score passes a multiple of this.
+pub const POWER_UP_EVERY: u32 = 5;
+/// Ticks an uncollected power-up stays on the board.
+pub const PICKUP_LIFETIME: u64 = 80;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PowerUp {
+    SlowMotion,
+    Ghost,
+    DoublePoints,
+}
+
+impl PowerUp {
+    pub const ALL: [PowerUp; 3] = [PowerUp::SlowMotion, PowerUp::Ghost, PowerUp::DoublePoints];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            PowerUp::SlowMotion => "Slow-mo",
+            PowerUp::Ghost => "Ghost",
+            PowerUp::DoublePoints => "Double points",
+        }
+    }
+
+    pub fn glyph(self) -> &'static str {
+        match self {
+            PowerUp::SlowMotion => "S",
+            PowerUp::Ghost => "G",
+            PowerUp::DoublePoints => "2",
+        }
+    }
+
+    pub fn color(self) -> u32 {
+        match self {
+            PowerUp::SlowMotion => 0x38bdf8,
+            PowerUp::Ghost => 0xe2e8f0,
+            PowerUp::DoublePoints => 0xfacc15,
+        }
+    }
+
+    pub fn duration_ticks(self) -> u32 {
+        match self {
+            PowerUp::SlowMotion => 40,
+            PowerUp::Ghost => 25,
+            PowerUp::DoublePoints => 50,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub struct Pickup {
+    pub cell: Cell,
+    pub power_up: PowerUp,
+    pub expires_at: u64,
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub struct ActiveEffect {
+    pub power_up: PowerUp,
+    pub remaining_ticks: u32,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct Inventory {
+    slots: [Option<PowerUp>; INVENTORY_SLOTS],
+}
+
+impl Inventory {
+    pub fn slots(&self) -> &[Option<PowerUp>; INVENTORY_SLOTS] {
+        &self.slots
+    }
+
+    /// Stores the power-up in the first free slot, returning `false` when full.
+    pub fn add(&mut self, power_up: PowerUp) -> bool {
+        match self.slots.iter_mut().find(|slot| slot.is_none()) {
+            Some(slot) => {
+                *slot = Some(power_up);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn get(&self, slot: usize) -> Option<PowerUp> {
+        self.slots.get(slot).copied().flatten()
+    }
+
+    pub fn take(&mut self, slot: usize) -> Option<PowerUp> {
+        self.slots.get_mut(slot).and_then(Option::take)
+    }
+}
@@ -0,0 +1,76 @@
+//! file: practice.rs
+//! author: Jacob Xie
+//! date: 2026/10/16 14:52:37 Friday
+//! brief:
+
+use super::{Engine, GameStatus, Replay, ReplayPlayer};
+
+/// How many ticks before the death a drill starts.
+const PRACTICE_REWIND: u64 = 10;
+/// Ticks past the original death the snake has to stay alive to pass the drill.
+const SURVIVE_TICKS: u64 = 5;
+
+/// Replays the lead-up to a death until the player gets through it.
+pub struct PracticeDrill {
+    start: Engine,
+    death_tick: u64,
+    attempts: u32,
+    complete: bool,
+}
+
+impl PracticeDrill {
+    /// Re-simulates the run to find its last tick and rewinds from there.
+    /// Returns `None` for a run that did not end in a death.
+    pub fn from_replay(replay: &Replay) -> Option<Self> {
+        let mut player = ReplayPlayer::new(replay.clone());
+        while !player.step() {}
+        player.engine().death()?;
+        let death_tick = player.engine().ticks();
+
+        let start_tick = death_tick.saturating_sub(PRACTICE_REWIND);
+        let mut player = ReplayPlayer::new(replay.clone());
+        while player.engine().ticks() < start_tick {
+            player.step();
+        }
+        Some(Self {
+            start: player.engine().clone(),
+            death_tick,
+            attempts: 0,
+            complete: false,
+        })
+    }
+
+    /// A fresh copy of the starting position, paused so the player can get ready.
+    pub fn restore(&mut self) -> Engine {
+        self.attempts += 1;
+        let mut engine = self.start.clone();
+        if engine.state() == GameStatus::Running {
+            engine.toggle_pause();
+        }
+        engine
+    }
+
+    pub fn attempts(&self) -> u32 {
+        self.attempts
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.complete
+    }
+
+    pub fn target_tick(&self) -> u64 {
+        self.death_tick + SURVIVE_TICKS
+    }
+
+    /// Checks an attempt after a tick: marks the drill complete once the snake
+    /// has outlived the original death, returns whether the attempt failed.
+    pub fn check(&mut self, engine: &Engine) -> bool {
+        if engine.state() == GameStatus::GameOver {
+            return true;
+        }
+        if engine.ticks() >= self.target_tick() {
+            self.complete = true;
+        }
+        false
+    }
+}
@@ -0,0 +1,72 @@
+//! file: privacy.rs
+//! author: Jacob Xie
+//! date: 2026/10/16 18:37:02 Friday
+//! brief:
+
+use std::io;
+
+use serde::{Deserialize, Serialize};
+
+use super::storage;
+
+const PRIVACY_FILE: &str = "privacy.json";
+
+/// What play data gets written to disk at all. Scores, records and daily
+/// results are always kept; replays and input traces can be turned off.
+/// Shared by every player, as session logs are.
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Privacy {
+    /// Finished runs are archived as replays for the profile page.
+    pub replays: bool,
+    /// Session logs, which trace every input and event as it happens. Read
+    /// once at launch, so a change applies from the next session.
+    pub input_traces: bool,
+}
+
+impl Default for Privacy {
+    fn default() -> Self {
+        Self {
+            replays: true,
+            input_traces: true,
+        }
+    }
+}
+
+impl Privacy {
+    pub fn load() -> Self {
+        storage::read_json(&storage::shared_path(PRIVACY_FILE)).unwrap_or_default()
+    }
+
+    pub fn store(&self) -> io::Result<()> {
+        storage::write_json(&storage::shared_path(PRIVACY_FILE), self)
+    }
+
+    /// A one-line summary of what is recorded.
+    pub fn label(self) -> &'static str {
+        match (self.replays, self.input_traces) {
+            (false, false) => "Scores only",
+            (true, false) => "Scores and replays",
+            (false, true) => "Scores and input traces",
+            (true, true) => "Scores, replays and input traces",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn everything_is_recorded_until_turned_off() {
+        let privacy = Privacy::default();
+        assert_eq!(privacy.label(), "Scores, replays and input traces");
+        let scores_only = Privacy {
+            replays: false,
+            input_traces: false,
+        };
+        assert_eq!(scores_only.label(), "Scores only");
+        let old: Privacy =
+            serde_json::from_str(r#"{"replays":false,"input_traces":true}"#).unwrap();
+        assert!(!old.replays && old.input_traces);
+    }
+}
@@ -0,0 +1,93 @@
+//! file: proximity.rs
+//! author: Jacob Xie
+//! date: 2026/10/16 18:40:54 Friday
+//! brief:
+
+use super::{Engine, GameStatus};
+
+/// Moves ahead within which a crash is warned about.
+pub const DANGER_MOVES: u32 = 3;
+/// Ticks between food pings, so the pings read as a pulse rather than a drone.
+pub const PING_EVERY: u64 = 4;
+
+/// Where the food and the nearest danger are from the head, measured each
+/// tick for playing by ear.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Proximity {
+    /// Cells to the food, counting straight moves only.
+    pub food_distance: u32,
+    /// Where the food sits across the board from the head as seen on
+    /// screen: -1.0 hard left, 1.0 hard right.
+    pub food_pan: f32,
+    /// Moves straight on before the snake would crash, when that is within
+    /// `DANGER_MOVES`.
+    pub danger: Option<u32>,
+}
+
+impl Proximity {
+    /// Measures a running game; `None` while nothing is moving.
+    pub fn measure(engine: &Engine) -> Option<Self> {
+        if engine.state() != GameStatus::Running {
+            return None;
+        }
+        let head = *engine.snake().front()?;
+        let food = engine.food();
+        let food_distance = head.x.abs_diff(food.x) + head.y.abs_diff(food.y);
+        let half_width = (engine.board_width() as f32 / 2.0).max(1.0);
+        let mut food_pan = ((food.x - head.x) as f32 / half_width).clamp(-1.0, 1.0);
+        if engine.config().mirror {
+            food_pan = -food_pan;
+        }
+        let heading = engine.heading();
+        let mut cell = head;
+        let mut danger = None;
+        for moves in 1..=DANGER_MOVES {
+            match engine.resolve_step(cell, heading) {
+                Ok(next) if !engine.is_occupied(next) => cell = next,
+                _ => {
+                    danger = Some(moves);
+                    break;
+                }
+            }
+        }
+        Some(Self {
+            food_distance,
+            food_pan,
+            danger,
+        })
+    }
+
+    /// How near the food is, from 0.0 across the board to 1.0 next to it.
+    pub fn food_closeness(&self, engine: &Engine) -> f32 {
+        let span = (engine.board_width() + engine.board_height()) as f32;
+        (1.0 - self.food_distance as f32 / span).clamp(0.0, 1.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{BoardLayout, GameConfig};
+
+    #[test]
+    fn nothing_is_measured_before_the_start() {
+        let engine = Engine::new(&GameConfig::new(BoardLayout::Open), 5);
+        assert_eq!(Proximity::measure(&engine), None);
+    }
+
+    #[test]
+    fn the_wall_is_heard_coming() {
+        let mut engine = Engine::new(&GameConfig::new(BoardLayout::Open), 5);
+        engine.start();
+        let mut dangers = Vec::new();
+        while let Some(proximity) = Proximity::measure(&engine) {
+            assert!(proximity.food_pan.abs() <= 1.0);
+            assert!(proximity.food_closeness(&engine) <= 1.0);
+            dangers.push(proximity.danger);
+            engine.tick();
+        }
+        assert!(engine.death().is_some());
+        assert_eq!(dangers[0], None);
+        assert_eq!(dangers[dangers.len() - 3..], [Some(3), Some(2), Some(1)]);
+    }
+}
@@ -0,0 +1,292 @@
+//! file: replay.rs
+//! author: Jacob Xie
+//! date: 2026/10/16 14:26:15 Friday
+//! brief:
+
+use serde::{Deserialize, Serialize};
+
+use super::{Direction, Engine, GameConfig, GameEvent, GameStatus, Mutation};
+
+/// Ticks between the snapshots a `Timeline` keeps to seek from.
+const KEYFRAME_EVERY: u64 = 50;
+
+/// A player decision that changes the simulation.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub enum Input {
+    Turn(Direction),
+    Activate(usize),
+    Mutate(Mutation),
+}
+
+/// Everything needed to re-simulate a run: the board, the RNG seed and the
+/// player inputs keyed by the tick they took effect on.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Replay {
+    config: GameConfig,
+    seed: u64,
+    inputs: Vec<(u64, Input)>,
+}
+
+impl Replay {
+    pub fn new(config: GameConfig, seed: u64, inputs: Vec<(u64, Input)>) -> Self {
+        Self {
+            config,
+            seed,
+            inputs,
+        }
+    }
+}
+
+pub struct ReplayPlayer {
+    engine: Engine,
+    replay: Replay,
+    cursor: usize,
+}
+
+impl ReplayPlayer {
+    pub fn new(replay: Replay) -> Self {
+        let mut engine = Engine::new(&replay.config, replay.seed);
+        engine.start();
+        Self {
+            engine,
+            replay,
+            cursor: 0,
+        }
+    }
+
+    pub fn engine(&self) -> &Engine {
+        &self.engine
+    }
+
+    /// Plays back one tick, returning `true` once the recorded run has ended.
+    pub fn step(&mut self) -> bool {
+        advance(&mut self.engine, &self.replay.inputs, &mut self.cursor);
+        self.engine.state() == GameStatus::GameOver
+    }
+}
+
+/// Feeds the inputs due on the engine's current tick, then ticks it.
+fn advance(engine: &mut Engine, inputs: &[(u64, Input)], cursor: &mut usize) {
+    while let Some(&(tick, input)) = inputs.get(*cursor) {
+        if tick != engine.ticks() {
+            break;
+        }
+        match input {
+            Input::Turn(direction) => engine.queue_direction(direction),
+            Input::Activate(slot) => engine.activate(slot),
+            Input::Mutate(mutation) => engine.mutate(mutation),
+        }
+        *cursor += 1;
+    }
+    engine.tick();
+}
+
+/// Something worth marking on a run's timeline.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Mark {
+    Food,
+    SpeedChange,
+}
+
+/// A finished run played through once up front, keeping a snapshot every
+/// `KEYFRAME_EVERY` ticks so any tick can be previewed by replaying from the
+/// nearest one.
+pub struct Timeline {
+    replay: Replay,
+    /// Engine and input cursor at ticks 0, `KEYFRAME_EVERY`, ...
+    keyframes: Vec<(Engine, usize)>,
+    marks: Vec<(u64, Mark)>,
+    ticks: u64,
+    /// The tick being previewed and the board at it; `None` is the end.
+    preview: Option<(u64, Engine)>,
+}
+
+impl Timeline {
+    /// Replays `replay` until it ends, or for at most `ticks` ticks.
+    pub fn new(replay: Replay, ticks: u64) -> Self {
+        let mut engine = Engine::new(&replay.config, replay.seed);
+        engine.start();
+        let mut cursor = 0;
+        let mut keyframes = Vec::new();
+        let mut marks = Vec::new();
+        let mut delay = engine.tick_delay();
+        while engine.ticks() < ticks && engine.state() == GameStatus::Running {
+            if engine.ticks().is_multiple_of(KEYFRAME_EVERY) {
+                keyframes.push((engine.clone(), cursor));
+            }
+            advance(&mut engine, &replay.inputs, &mut cursor);
+            if engine
+                .events()
+                .iter()
+                .any(|event| matches!(event, GameEvent::FoodEaten { .. }))
+            {
+                marks.push((engine.ticks(), Mark::Food));
+            }
+            if engine.tick_delay() != delay {
+                delay = engine.tick_delay();
+                marks.push((engine.ticks(), Mark::SpeedChange));
+            }
+        }
+        Self {
+            replay,
+            keyframes,
+            marks,
+            ticks: engine.ticks(),
+            preview: None,
+        }
+    }
+
+    pub fn ticks(&self) -> u64 {
+        self.ticks
+    }
+
+    pub fn marks(&self) -> &[(u64, Mark)] {
+        &self.marks
+    }
+
+    /// The tick being previewed.
+    pub fn position(&self) -> u64 {
+        self.preview.as_ref().map_or(self.ticks, |(tick, _)| *tick)
+    }
+
+    /// The board at the previewed tick, or `None` at the end of the run,
+    /// where the live board already shows it.
+    pub fn preview(&self) -> Option<&Engine> {
+        self.preview.as_ref().map(|(_, engine)| engine)
+    }
+
+    /// Moves the preview to `tick`, clamped to the run.
+    pub fn seek(&mut self, tick: u64) {
+        let tick = tick.min(self.ticks);
+        if tick == self.ticks {
+            self.preview = None;
+            return;
+        }
+        let index = (tick / KEYFRAME_EVERY) as usize;
+        let Some((engine, cursor)) = self.keyframes.get(index) else {
+            return;
+        };
+        let (mut engine, mut cursor) = (engine.clone(), *cursor);
+        while engine.ticks() < tick {
+            advance(&mut engine, &self.replay.inputs, &mut cursor);
+        }
+        self.preview = Some((tick, engine));
+    }
+
+    /// Moves the preview to the nearest mark before (`forward == false`) or
+    /// after the current tick, or to the start or end when there is none.
+    pub fn jump(&mut self, forward: bool) {
+        let position = self.position();
+        let tick = if forward {
+            self.marks
+                .iter()
+                .map(|(tick, _)| *tick)
+                .find(|tick| *tick > position)
+                .unwrap_or(self.ticks)
+        } else {
+            self.marks
+                .iter()
+                .rev()
+                .map(|(tick, _)| *tick)
+                .find(|tick| *tick < position)
+                .unwrap_or(0)
+        };
+        self.seek(tick);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::BoardLayout;
+
+    /// Weaves up and down while drifting right until the snake hits the wall.
+    fn play(seed: u64) -> Engine {
+        const WEAVE: [Direction; 4] = [
+            Direction::Up,
+            Direction::Right,
+            Direction::Down,
+            Direction::Right,
+        ];
+        let mut engine = Engine::new(&GameConfig::new(BoardLayout::Open), seed);
+        engine.start();
+        for tick in 0..500 {
+            if engine.state() == GameStatus::GameOver {
+                break;
+            }
+            if tick % 3 == 0 {
+                engine.queue_direction(WEAVE[tick / 3 % WEAVE.len()]);
+            }
+            engine.tick();
+        }
+        assert_eq!(engine.state(), GameStatus::GameOver);
+        engine
+    }
+
+    fn play_back(replay: Replay) -> Engine {
+        let mut player = ReplayPlayer::new(replay);
+        for _ in 0..500 {
+            if player.step() {
+                break;
+            }
+        }
+        player.engine
+    }
+
+    #[test]
+    fn a_replay_reproduces_the_run() {
+        for seed in [1, 7, 42] {
+            let played = play(seed);
+            let replayed = play_back(played.replay());
+            assert_eq!(replayed.state(), GameStatus::GameOver);
+            assert_eq!(replayed.ticks(), played.ticks());
+            assert_eq!(replayed.score(), played.score());
+            assert_eq!(replayed.food(), played.food());
+            assert!(replayed.snake().iter().eq(played.snake()));
+            assert!(replayed.death() == played.death());
+        }
+    }
+
+    #[test]
+    fn a_timeline_previews_any_tick_of_the_run() {
+        let played = play(42);
+        let mut timeline = Timeline::new(played.replay(), played.ticks());
+        assert_eq!(timeline.ticks(), played.ticks());
+        assert!(timeline.preview().is_none());
+        let target = played.ticks() - 3;
+        let mut walked = ReplayPlayer::new(played.replay());
+        for _ in 0..target {
+            walked.step();
+        }
+        timeline.seek(target);
+        let preview = timeline.preview().unwrap();
+        assert_eq!(preview.ticks(), target);
+        assert!(preview.snake().iter().eq(walked.engine().snake()));
+        timeline.seek(u64::MAX);
+        assert!(timeline.preview().is_none());
+    }
+
+    #[test]
+    fn a_timeline_marks_each_meal() {
+        let played = play(7);
+        let mut timeline = Timeline::new(played.replay(), played.ticks());
+        let meals = timeline
+            .marks()
+            .iter()
+            .filter(|(_, mark)| *mark == Mark::Food)
+            .count();
+        assert_eq!(meals, played.stats().eaten);
+        timeline.jump(false);
+        assert!(timeline.position() < played.ticks());
+    }
+
+    #[test]
+    fn a_replay_keeps_inputs_in_tick_order() {
+        let played = play(7);
+        let replay = played.replay();
+        assert!(!replay.inputs.is_empty());
+        assert!(replay.inputs.windows(2).all(|pair| pair[0].0 <= pair[1].0));
+        assert!(replay.inputs.iter().all(|&(tick, _)| tick < played.ticks()));
+        assert_eq!(replay.seed, 7);
+    }
+}
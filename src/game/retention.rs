@@ -0,0 +1,90 @@
+//! file: retention.rs
+//! author: Jacob Xie
+//! date: 2026/10/16 17:08:32 Friday
+//! brief:
+
+use std::io;
+
+use serde::{Deserialize, Serialize};
+
+use super::storage;
+
+const RETENTION_FILE: &str = "retention.json";
+const KEEP_REPLAYS: [usize; 4] = [5, 20, 50, 0];
+const HISTORY_CAPS: [u64; 3] = [16 << 10, 64 << 10, 256 << 10];
+
+/// How much play data is kept on disk. Older replays and daily results past
+/// these limits are pruned after every run.
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub struct Retention {
+    /// Most recent replays kept; zero keeps none.
+    pub keep_replays: usize,
+    /// Largest the daily history file may grow, in bytes.
+    pub history_cap: u64,
+}
+
+impl Default for Retention {
+    fn default() -> Self {
+        Self {
+            keep_replays: KEEP_REPLAYS[1],
+            history_cap: HISTORY_CAPS[1],
+        }
+    }
+}
+
+impl Retention {
+    pub fn load() -> Self {
+        storage::read_json(&storage::data_path(RETENTION_FILE)).unwrap_or_default()
+    }
+
+    pub fn store(&self) -> io::Result<()> {
+        storage::write_json(&storage::data_path(RETENTION_FILE), self)
+    }
+
+    pub fn next_keep_replays(self) -> usize {
+        next(&KEEP_REPLAYS, self.keep_replays)
+    }
+
+    pub fn next_history_cap(self) -> u64 {
+        next(&HISTORY_CAPS, self.history_cap)
+    }
+}
+
+/// The option after `current`, or the first one if `current` is not listed.
+fn next<T: Copy + PartialEq>(options: &[T], current: T) -> T {
+    let index = options.iter().position(|option| *option == current);
+    options[index.map_or(0, |index| (index + 1) % options.len())]
+}
+
+/// Byte count in the largest fitting unit, e.g. "12.3 KB".
+pub fn size_text(bytes: u64) -> String {
+    match bytes {
+        0..1_024 => format!("{bytes} B"),
+        1_024..1_048_576 => format!("{:.1} KB", bytes as f64 / 1_024.0),
+        _ => format!("{:.1} MB", bytes as f64 / 1_048_576.0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn options_cycle_and_recover_from_unknown_values() {
+        let retention = Retention::default();
+        assert_eq!(retention.next_keep_replays(), 50);
+        let off = Retention {
+            keep_replays: 0,
+            history_cap: 7,
+        };
+        assert_eq!(off.next_keep_replays(), 5);
+        assert_eq!(off.next_history_cap(), HISTORY_CAPS[0]);
+    }
+
+    #[test]
+    fn sizes_pick_a_unit() {
+        assert_eq!(size_text(512), "512 B");
+        assert_eq!(size_text(64 << 10), "64.0 KB");
+        assert_eq!(size_text(3 << 20), "3.0 MB");
+    }
+}
@@ -0,0 +1,61 @@
+//! file: rules.rs
+//! author: Jacob Xie
+//! date: 2026/10/16 17:39:02 Friday
+//! brief:
+
+use serde::{Deserialize, Serialize};
+
+use super::DeathCause;
+
+/// Which collisions end a run. One that is not fatal blocks the move instead,
+/// leaving the snake where it was for the tick.
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Collisions {
+    pub walls: bool,
+    pub self_collision: bool,
+    pub obstacles: bool,
+}
+
+impl Default for Collisions {
+    fn default() -> Self {
+        Self {
+            walls: true,
+            self_collision: true,
+            obstacles: true,
+        }
+    }
+}
+
+impl Collisions {
+    fn flag(&mut self, cause: DeathCause) -> &mut bool {
+        match cause {
+            DeathCause::Wall => &mut self.walls,
+            DeathCause::SelfCollision => &mut self.self_collision,
+            DeathCause::Obstacle => &mut self.obstacles,
+        }
+    }
+
+    pub fn is_fatal(mut self, cause: DeathCause) -> bool {
+        *self.flag(cause)
+    }
+
+    pub fn toggled(mut self, cause: DeathCause) -> Self {
+        let flag = self.flag(cause);
+        *flag = !*flag;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flags_toggle_independently() {
+        let collisions = Collisions::default().toggled(DeathCause::Wall);
+        assert!(!collisions.is_fatal(DeathCause::Wall));
+        assert!(collisions.is_fatal(DeathCause::SelfCollision));
+        assert!(collisions.is_fatal(DeathCause::Obstacle));
+        assert!(collisions.toggled(DeathCause::Wall) == Collisions::default());
+    }
+}
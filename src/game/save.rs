@@ -0,0 +1,99 @@
+//! file: save.rs
+//! author: Jacob Xie
+//! date: 2026/10/16 14:32:55 Friday
+//! brief:
+
+use std::{fs, io, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+use super::{Engine, storage};
+
+/// Bumped whenever the serialized engine layout changes; older saves are ignored.
+pub const SAVE_VERSION: u32 = 15;
+const SAVE_FILE: &str = "save.json";
+/// Written alongside an autosave of a run still under way and removed once the
+/// run is over or resumed. Finding it at launch means the app was closed, or
+/// crashed, in the middle of a run.
+const DIRTY_FILE: &str = "run.dirty";
+
+#[derive(Serialize, Deserialize)]
+pub struct SaveGame {
+    version: u32,
+    engine: Engine,
+}
+
+impl SaveGame {
+    pub fn exists() -> bool {
+        storage::data_path(SAVE_FILE).exists()
+    }
+
+    pub fn store(engine: &Engine) -> io::Result<()> {
+        let save = SaveGameRef {
+            version: SAVE_VERSION,
+            engine,
+        };
+        storage::write_json(&storage::data_path(SAVE_FILE), &save)?;
+        fs::write(storage::data_path(DIRTY_FILE), "")
+    }
+
+    /// Whether the save holds a run that was cut off rather than left behind
+    /// on purpose.
+    pub fn interrupted() -> bool {
+        Self::exists() && storage::data_path(DIRTY_FILE).exists()
+    }
+
+    /// Drops the interrupted mark, keeping the save itself.
+    pub fn acknowledge() -> io::Result<()> {
+        remove_if_present(&storage::data_path(DIRTY_FILE))
+    }
+
+    /// Loads the saved run and removes it from disk so it can only be resumed
+    /// once. A save that fails to load is removed too, or it would be offered
+    /// again on every launch.
+    pub fn take() -> io::Result<Engine> {
+        let path = storage::data_path(SAVE_FILE);
+        let engine = Self::read(&path);
+        fs::remove_file(&path)?;
+        Self::acknowledge()?;
+        engine
+    }
+
+    /// Drops the save, e.g. once the run it holds has ended.
+    pub fn clear() -> io::Result<()> {
+        remove_if_present(&storage::data_path(SAVE_FILE))?;
+        Self::acknowledge()
+    }
+
+    fn read(path: &Path) -> io::Result<Engine> {
+        // The version is checked on its own first, since an older engine
+        // layout would fail to deserialize before it could be compared.
+        let header: SaveHeader = storage::read_json(path)?;
+        if header.version != SAVE_VERSION {
+            return Err(io::Error::other(format!(
+                "save version {} is not supported (expected {SAVE_VERSION})",
+                header.version
+            )));
+        }
+        let save: SaveGame = storage::read_json(path)?;
+        Ok(save.engine)
+    }
+}
+
+fn remove_if_present(path: &Path) -> io::Result<()> {
+    match fs::remove_file(path) {
+        Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err),
+        _ => Ok(()),
+    }
+}
+
+#[derive(Deserialize)]
+struct SaveHeader {
+    version: u32,
+}
+
+#[derive(Serialize)]
+struct SaveGameRef<'a> {
+    version: u32,
+    engine: &'a Engine,
+}
@@ -0,0 +1,14 @@
+//! file: screen.rs
+//! author: Jacob Xie
+//! date: 2026/10/16 14:25:49 Friday
+//! brief:
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Screen {
+    Play,
+    Summary,
+    Replay,
+    Versus,
+    /// Drawing a level; play-tests run on `Play` and come back here.
+    Editor,
+}
@@ -0,0 +1,261 @@
+//! file: scripting.rs
+//! author: Jacob Xie
+//! date: 2026/10/16 18:31:15 Friday
+//! brief:
+
+use std::{cell::RefCell, fs, path::Path, rc::Rc};
+
+use rhai::{AST, CallFnOptions, Dynamic, Map, Scope, module_resolvers::DummyModuleResolver};
+
+use super::{Engine, GameEvent, MAX_EXTRA_FOOD, MAX_SPEED_BIAS, Mutation, storage};
+
+const SCRIPT_DIR: &str = "scripts";
+/// Work one hook call may do before it is cut off, so a runaway loop cannot
+/// hang the game.
+const MAX_OPERATIONS: u64 = 20_000;
+/// Changes one hook call may ask for; any more are dropped.
+const MAX_MUTATIONS: usize = 4;
+
+/// A mutator script and the name it is shown under.
+struct Script {
+    name: String,
+    ast: AST,
+}
+
+/// Community mutators: Rhai scripts from the scripts folder that react to
+/// game events. Each may define any of `on_tick(game)`, `on_food(game)`,
+/// `on_speed_up(game)` and `on_level_up(game)`, where `game` is a map of
+/// `score`, `tick`, `length`, `tick_ms`, `width` and `height`. The only
+/// way back into the run is `spawn_food(count)` and `change_speed(percent)`,
+/// both held to the bounds in [`Mutation`]; scripts get no files, modules or
+/// `eval`.
+pub struct Mutators {
+    host: rhai::Engine,
+    scripts: Vec<Script>,
+    requested: Rc<RefCell<Vec<Mutation>>>,
+}
+
+impl Mutators {
+    /// Compiles every `.rhai` file in the scripts folder, in name order.
+    /// Scripts that fail to compile are skipped with a warning.
+    pub fn load() -> Self {
+        let dir = storage::shared_path(SCRIPT_DIR);
+        Self::compile(Self::read_dir(&dir))
+    }
+
+    fn read_dir(dir: &Path) -> Vec<(String, String)> {
+        let Ok(entries) = fs::read_dir(dir) else {
+            return Vec::new();
+        };
+        let mut sources: Vec<(String, String)> = entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "rhai"))
+            .filter_map(|path| {
+                let name = path.file_stem()?.to_string_lossy().into_owned();
+                match fs::read_to_string(&path) {
+                    Ok(source) => Some((name, source)),
+                    Err(err) => {
+                        tracing::warn!("Failed to read mutator {}: {err}", path.display());
+                        None
+                    }
+                }
+            })
+            .collect();
+        sources.sort();
+        sources
+    }
+
+    fn compile(sources: Vec<(String, String)>) -> Self {
+        let requested = Rc::new(RefCell::new(Vec::new()));
+        let host = Self::sandbox(&requested);
+        let scripts: Vec<Script> = sources
+            .into_iter()
+            .filter_map(|(name, source)| match host.compile(&source) {
+                Ok(ast) => Some(Script { name, ast }),
+                Err(err) => {
+                    tracing::warn!("Mutator {name} does not compile: {err}");
+                    None
+                }
+            })
+            .collect();
+        if !scripts.is_empty() {
+            tracing::info!("Loaded {} mutator script(s)", scripts.len());
+        }
+        Self {
+            host,
+            scripts,
+            requested,
+        }
+    }
+
+    /// A script engine with the limits and the two functions mutators get.
+    fn sandbox(requested: &Rc<RefCell<Vec<Mutation>>>) -> rhai::Engine {
+        let mut host = rhai::Engine::new();
+        host.set_max_operations(MAX_OPERATIONS)
+            .set_max_call_levels(16)
+            .set_max_expr_depths(32, 32)
+            .set_max_string_size(1024)
+            .set_max_array_size(256)
+            .set_max_map_size(64)
+            .set_module_resolver(DummyModuleResolver::new())
+            .disable_symbol("eval")
+            .on_print(|text| tracing::info!(target: "mutator", "{text}"))
+            .on_debug(|text, _, _| tracing::debug!(target: "mutator", "{text}"));
+        let queue = requested.clone();
+        host.register_fn("spawn_food", move |count: i64| {
+            let count = count.clamp(1, MAX_EXTRA_FOOD as i64) as u8;
+            queue.borrow_mut().push(Mutation::ExtraFood(count));
+        });
+        let queue = requested.clone();
+        host.register_fn("change_speed", move |percent: i64| {
+            let bound = MAX_SPEED_BIAS as i64;
+            let percent = percent.clamp(-bound, bound) as i8;
+            queue.borrow_mut().push(Mutation::Speed(percent));
+        });
+        host
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.scripts.is_empty()
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.scripts.iter().map(|script| script.name.as_str())
+    }
+
+    /// Runs each script's hook for `event`, if it has one, returning the
+    /// changes they asked for. A script that errors or runs too long only
+    /// loses that call.
+    pub fn react(&self, event: &GameEvent, engine: &Engine) -> Vec<Mutation> {
+        let hook = match event {
+            GameEvent::TickAdvanced => "on_tick",
+            GameEvent::FoodEaten { .. } => "on_food",
+            GameEvent::SpeedUp { .. } => "on_speed_up",
+            GameEvent::LevelUp { .. } => "on_level_up",
+            _ => return Vec::new(),
+        };
+        let mut mutations = Vec::new();
+        for script in &self.scripts {
+            let defined = script
+                .ast
+                .iter_functions()
+                .any(|function| function.name == hook && function.params.len() == 1);
+            if !defined {
+                continue;
+            }
+            let result = self.host.call_fn_with_options::<Dynamic>(
+                CallFnOptions::new().eval_ast(false),
+                &mut Scope::new(),
+                &script.ast,
+                hook,
+                (Self::game_map(engine),),
+            );
+            let requested: Vec<Mutation> = self.requested.borrow_mut().drain(..).collect();
+            match result {
+                Ok(_) => mutations.extend(requested.into_iter().take(MAX_MUTATIONS)),
+                Err(err) => tracing::warn!("Mutator {} failed in {hook}: {err}", script.name),
+            }
+        }
+        mutations
+    }
+
+    fn game_map(engine: &Engine) -> Map {
+        let mut game = Map::new();
+        game.insert("score".into(), (engine.score() as i64).into());
+        game.insert("tick".into(), (engine.ticks() as i64).into());
+        game.insert("length".into(), (engine.snake().len() as i64).into());
+        game.insert(
+            "tick_ms".into(),
+            (engine.tick_delay().as_millis() as i64).into(),
+        );
+        game.insert("width".into(), (engine.board_width() as i64).into());
+        game.insert("height".into(), (engine.board_height() as i64).into());
+        game
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{BoardLayout, GameConfig};
+
+    fn compile(source: &str) -> Mutators {
+        Mutators::compile(vec![("test".into(), source.into())])
+    }
+
+    fn engine() -> Engine {
+        let mut engine = Engine::new(&GameConfig::new(BoardLayout::Open), 3);
+        engine.start();
+        engine
+    }
+
+    #[test]
+    fn hooks_ask_for_bounded_changes() {
+        let mutators = compile(
+            "fn on_food(game) { spawn_food(99); if game.score >= 0 { change_speed(-80); } }",
+        );
+        let event = GameEvent::FoodEaten {
+            cell: engine().food(),
+            score: 1,
+            multiplier: 1,
+        };
+        assert_eq!(
+            mutators.react(&event, &engine()),
+            [
+                Mutation::ExtraFood(MAX_EXTRA_FOOD),
+                Mutation::Speed(-MAX_SPEED_BIAS)
+            ]
+        );
+        assert!(
+            mutators
+                .react(&GameEvent::TickAdvanced, &engine())
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn runaway_scripts_are_cut_off() {
+        let mutators = compile("fn on_tick(game) { spawn_food(1); loop {} }");
+        assert!(
+            mutators
+                .react(&GameEvent::TickAdvanced, &engine())
+                .is_empty()
+        );
+        let mutators = compile("fn on_tick(game) { for i in 0..10 { spawn_food(1); } }");
+        assert_eq!(
+            mutators.react(&GameEvent::TickAdvanced, &engine()).len(),
+            MAX_MUTATIONS
+        );
+    }
+
+    #[test]
+    fn scripts_cannot_reach_outside() {
+        let mutators = compile(r#"fn on_tick(game) { import "os" as os; spawn_food(1); }"#);
+        assert!(!mutators.is_empty());
+        assert!(
+            mutators
+                .react(&GameEvent::TickAdvanced, &engine())
+                .is_empty()
+        );
+        assert!(compile(r#"fn on_tick(game) { eval("spawn_food(1)"); }"#).is_empty());
+        assert!(compile("fn broken( {").is_empty());
+    }
+
+    #[test]
+    fn mutations_replay() {
+        let mut live = engine();
+        live.mutate(Mutation::ExtraFood(2));
+        live.mutate(Mutation::Speed(20));
+        for _ in 0..5 {
+            live.tick();
+        }
+        let mut player = crate::game::ReplayPlayer::new(live.replay());
+        for _ in 0..5 {
+            player.step();
+        }
+        assert_eq!(player.engine().pellets().len(), live.pellets().len());
+        assert_eq!(player.engine().tick_delay(), live.tick_delay());
+        assert!(live.tick_delay() < engine().tick_delay());
+    }
+}
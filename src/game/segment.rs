@@ -0,0 +1,32 @@
+//! file: segment.rs
+//! author: Jacob Xie
+//! date: 2026/10/16 14:40:13 Friday
+//! brief:
+
+use serde::{Deserialize, Serialize};
+
+/// Points each glowing segment adds to the final score if it survives to game end.
+pub const GLOW_BONUS: u32 = 5;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SegmentEffect {
+    /// Absorbs one fatal collision, then becomes a plain segment.
+    Armored,
+    Glowing,
+}
+
+impl SegmentEffect {
+    pub fn glyph(self) -> &'static str {
+        match self {
+            SegmentEffect::Armored => "◆",
+            SegmentEffect::Glowing => "★",
+        }
+    }
+
+    pub fn color(self) -> u32 {
+        match self {
+            SegmentEffect::Armored => 0x94a3b8,
+            SegmentEffect::Glowing => 0xfde047,
+        }
+    }
+}
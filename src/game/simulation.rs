@@ -0,0 +1,125 @@
+//! file: simulation.rs
+//! author: Jacob Xie
+//! date: 2026/10/16 17:42:37 Friday
+//! brief:
+
+use std::time::{Duration, Instant};
+
+use rand::{SeedableRng, seq::SliceRandom};
+use rand_chacha::ChaCha12Rng;
+
+use super::{Direction, Engine, GameConfig, GameStatus, autopilot};
+
+/// Who steers a headless game.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Policy {
+    /// The attract-mode autopilot: shortest path to the food, else most room.
+    Autopilot,
+    /// Whichever safe move gets closest to the food, with no lookahead.
+    Greedy,
+    /// Any safe move, picked at random.
+    Random,
+}
+
+impl Policy {
+    pub const ALL: [Policy; 3] = [Policy::Autopilot, Policy::Greedy, Policy::Random];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Policy::Autopilot => "autopilot",
+            Policy::Greedy => "greedy",
+            Policy::Random => "random",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|policy| policy.name() == name)
+    }
+
+    fn choose(self, engine: &Engine, rng: &mut ChaCha12Rng) -> Option<Direction> {
+        if self == Policy::Autopilot {
+            return autopilot::plan(engine);
+        }
+        let head = engine.snake().front().copied()?;
+        let food = engine.food();
+        let moves: Vec<(Direction, u32)> = Direction::ALL
+            .into_iter()
+            .filter(|direction| !direction.is_opposite(engine.direction()))
+            .filter_map(|direction| {
+                let cell = engine.resolve_step(head, direction).ok()?;
+                (!engine.is_occupied(cell))
+                    .then(|| (direction, cell.x.abs_diff(food.x) + cell.y.abs_diff(food.y)))
+            })
+            .collect();
+        match self {
+            Policy::Greedy => moves.iter().min_by_key(|(_, distance)| *distance),
+            _ => moves.choose(rng),
+        }
+        .map(|(direction, _)| *direction)
+    }
+}
+
+/// How one headless game went.
+#[derive(Clone, Copy, Debug)]
+pub struct SimulationResult {
+    pub score: u32,
+    pub length: usize,
+    pub ticks: u64,
+    /// Wall-clock time spent inside the engine.
+    pub busy: Duration,
+}
+
+/// Plays a game from `seed` to the end, or until `max_ticks` have passed for a
+/// policy that can circle forever without eating.
+pub fn simulate(
+    config: &GameConfig,
+    policy: Policy,
+    seed: u64,
+    max_ticks: u64,
+) -> SimulationResult {
+    let mut engine = Engine::new(config, seed);
+    let mut rng = ChaCha12Rng::seed_from_u64(seed);
+    engine.start();
+    let started = Instant::now();
+    while engine.state() == GameStatus::Running && engine.ticks() < max_ticks {
+        if let Some(direction) = policy.choose(&engine, &mut rng) {
+            engine.queue_direction(direction);
+        }
+        engine.tick();
+    }
+    SimulationResult {
+        score: engine.score(),
+        length: engine.snake().len(),
+        ticks: engine.ticks(),
+        busy: started.elapsed(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::BoardLayout;
+
+    #[test]
+    fn games_are_reproducible_from_their_seed() {
+        let config = GameConfig::new(BoardLayout::Open);
+        for policy in Policy::ALL {
+            let first = simulate(&config, policy, 7, 500);
+            let second = simulate(&config, policy, 7, 500);
+            assert_eq!(
+                (first.score, first.length, first.ticks),
+                (second.score, second.length, second.ticks),
+                "{}",
+                policy.name()
+            );
+        }
+    }
+
+    #[test]
+    fn the_autopilot_outscores_random_play() {
+        let config = GameConfig::new(BoardLayout::Open);
+        let autopilot = simulate(&config, Policy::Autopilot, 3, 500);
+        let random = simulate(&config, Policy::Random, 3, 500);
+        assert!(autopilot.score > random.score);
+    }
+}
@@ -0,0 +1,196 @@
+//! file: skin.rs
+//! author: Jacob Xie
+//! date: 2026/10/16 14:57:48 Friday
+//! brief:
+
+use std::{collections::HashMap, fs, io, path::Path, sync::Arc};
+
+use gpui::{Image, ImageFormat};
+
+use super::{Direction, FoodKind, storage};
+
+/// One picture in a skin. Corners are named by the two sides of the cell the
+/// body leaves through, e.g. `Corner(Up, Right)` links the top and right edges.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Sprite {
+    Head(Direction),
+    BodyHorizontal,
+    BodyVertical,
+    Corner(Direction, Direction),
+    Food(FoodKind),
+}
+
+const CLASSIC: [(Sprite, &[u8]); 13] = [
+    (
+        Sprite::Head(Direction::Up),
+        include_bytes!("../../assets/skins/classic/head_up.png"),
+    ),
+    (
+        Sprite::Head(Direction::Down),
+        include_bytes!("../../assets/skins/classic/head_down.png"),
+    ),
+    (
+        Sprite::Head(Direction::Left),
+        include_bytes!("../../assets/skins/classic/head_left.png"),
+    ),
+    (
+        Sprite::Head(Direction::Right),
+        include_bytes!("../../assets/skins/classic/head_right.png"),
+    ),
+    (
+        Sprite::BodyHorizontal,
+        include_bytes!("../../assets/skins/classic/body_horizontal.png"),
+    ),
+    (
+        Sprite::BodyVertical,
+        include_bytes!("../../assets/skins/classic/body_vertical.png"),
+    ),
+    (
+        Sprite::Corner(Direction::Up, Direction::Right),
+        include_bytes!("../../assets/skins/classic/corner_ne.png"),
+    ),
+    (
+        Sprite::Corner(Direction::Up, Direction::Left),
+        include_bytes!("../../assets/skins/classic/corner_nw.png"),
+    ),
+    (
+        Sprite::Corner(Direction::Down, Direction::Right),
+        include_bytes!("../../assets/skins/classic/corner_se.png"),
+    ),
+    (
+        Sprite::Corner(Direction::Down, Direction::Left),
+        include_bytes!("../../assets/skins/classic/corner_sw.png"),
+    ),
+    (
+        Sprite::Food(FoodKind::Plain),
+        include_bytes!("../../assets/skins/classic/food.png"),
+    ),
+    (
+        Sprite::Food(FoodKind::Armored),
+        include_bytes!("../../assets/skins/classic/food_armored.png"),
+    ),
+    (
+        Sprite::Food(FoodKind::Glowing),
+        include_bytes!("../../assets/skins/classic/food_glowing.png"),
+    ),
+];
+
+impl Sprite {
+    /// The piece for a body segment given the directions towards the segment
+    /// in front of it and the one behind it. A missing neighbour (the tail end,
+    /// or a jump through a portal) falls back to a straight piece.
+    pub fn for_segment(front: Option<Direction>, back: Option<Direction>) -> Self {
+        let is_vertical =
+            |direction: Direction| matches!(direction, Direction::Up | Direction::Down);
+        match (front, back) {
+            (Some(a), Some(b)) if is_vertical(a) != is_vertical(b) => {
+                if is_vertical(a) {
+                    Sprite::Corner(a, b)
+                } else {
+                    Sprite::Corner(b, a)
+                }
+            }
+            (Some(direction), _) | (None, Some(direction)) if is_vertical(direction) => {
+                Sprite::BodyVertical
+            }
+            _ => Sprite::BodyHorizontal,
+        }
+    }
+
+    fn file_name(self) -> &'static str {
+        match self {
+            Sprite::Head(Direction::Up) => "head_up.png",
+            Sprite::Head(Direction::Down) => "head_down.png",
+            Sprite::Head(Direction::Left) => "head_left.png",
+            Sprite::Head(Direction::Right) => "head_right.png",
+            Sprite::BodyHorizontal => "body_horizontal.png",
+            Sprite::BodyVertical => "body_vertical.png",
+            Sprite::Corner(Direction::Up, Direction::Left) => "corner_nw.png",
+            Sprite::Corner(Direction::Down, Direction::Right) => "corner_se.png",
+            Sprite::Corner(Direction::Down, Direction::Left) => "corner_sw.png",
+            Sprite::Corner(..) => "corner_ne.png",
+            Sprite::Food(FoodKind::Plain) => "food.png",
+            Sprite::Food(FoodKind::Armored) => "food_armored.png",
+            Sprite::Food(FoodKind::Glowing) => "food_glowing.png",
+            Sprite::Food(FoodKind::Tough) => "food_tough.png",
+            Sprite::Food(FoodKind::Heavy) => "food_heavy.png",
+            Sprite::Food(FoodKind::Shrink) => "food_shrink.png",
+        }
+    }
+}
+
+/// A set of PNG sprites. Cells whose sprite is missing from the set are drawn
+/// in the flat colored style, so an empty skin is the classic flat look.
+pub struct Skin {
+    name: String,
+    sprites: HashMap<Sprite, Arc<Image>>,
+}
+
+impl Skin {
+    pub fn flat() -> Self {
+        Self {
+            name: "Flat".into(),
+            sprites: HashMap::new(),
+        }
+    }
+
+    pub fn classic() -> Self {
+        let sprites = CLASSIC
+            .iter()
+            .map(|(sprite, bytes)| {
+                let image = Image::from_bytes(ImageFormat::Png, bytes.to_vec());
+                (*sprite, Arc::new(image))
+            })
+            .collect();
+        Self {
+            name: "Classic".into(),
+            sprites,
+        }
+    }
+
+    /// Loads whichever sprites a skin directory provides, named like the
+    /// bundled set (`head_up.png`, `corner_ne.png`, `food.png`, ...).
+    pub fn load_dir(name: &str, dir: &Path) -> io::Result<Self> {
+        let mut sprites = HashMap::new();
+        for (sprite, _) in CLASSIC {
+            let path = dir.join(sprite.file_name());
+            if path.exists() {
+                let image = Image::from_bytes(ImageFormat::Png, fs::read(path)?);
+                sprites.insert(sprite, Arc::new(image));
+            }
+        }
+        Ok(Self {
+            name: name.to_string(),
+            sprites,
+        })
+    }
+
+    /// The flat style, the bundled skins and any skins installed under the
+    /// data directory's `skins` folder, one sub-folder per skin.
+    pub fn available() -> Vec<Skin> {
+        let mut skins = vec![Skin::flat(), Skin::classic()];
+        let Ok(entries) = fs::read_dir(storage::shared_path("skins")) else {
+            return skins;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if !path.is_dir() {
+                continue;
+            }
+            let name = entry.file_name().to_string_lossy().into_owned();
+            match Skin::load_dir(&name, &path) {
+                Ok(skin) => skins.push(skin),
+                Err(err) => tracing::warn!("Failed to load skin {name}: {err}"),
+            }
+        }
+        skins
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn sprite(&self, sprite: Sprite) -> Option<Arc<Image>> {
+        self.sprites.get(&sprite).cloned()
+    }
+}
@@ -0,0 +1,95 @@
+//! file: spawn.rs
+//! author: Jacob Xie
+//! date: 2026/10/16 14:55:03 Friday
+//! brief:
+
+use serde::{Deserialize, Serialize};
+
+use super::{Cell, Direction, Tile, TileMap};
+
+const DEFAULT_LENGTH: usize = 4;
+
+/// Where the snake starts: its head, how long it is and which way it is
+/// heading. The body trails straight back from the head.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct Spawn {
+    pub head: Cell,
+    pub length: usize,
+    pub direction: Direction,
+}
+
+impl Spawn {
+    /// The default spawn: four cells in the middle of the board, heading right.
+    pub fn centered(width: i32, height: i32) -> Self {
+        Self {
+            head: Cell {
+                x: width / 2,
+                y: height / 2,
+            },
+            length: DEFAULT_LENGTH,
+            direction: Direction::Right,
+        }
+    }
+
+    /// Body cells from head to tail.
+    pub fn cells(&self) -> impl Iterator<Item = Cell> + '_ {
+        let back = self.direction.opposite();
+        std::iter::successors(Some(self.head), move |cell| Some(cell.offset(back)))
+            .take(self.length)
+    }
+
+    /// Whether `cell` lies on the spawn's row or column, which is kept free of
+    /// random obstacles so the snake has room to get going.
+    pub fn lane_contains(&self, cell: Cell) -> bool {
+        match self.direction {
+            Direction::Left | Direction::Right => cell.y == self.head.y,
+            Direction::Up | Direction::Down => cell.x == self.head.x,
+        }
+    }
+
+    /// A spawn of the same length that fits `tiles`, for when this one does
+    /// not: the board centre if it is free, otherwise the first lane found.
+    pub fn fallback(&self, tiles: &TileMap) -> Option<Spawn> {
+        let length = self.length.max(1);
+        let centered = Spawn {
+            length,
+            ..Spawn::centered(tiles.width(), tiles.height())
+        };
+        let anywhere = (0..tiles.height())
+            .flat_map(|y| (0..tiles.width()).map(move |x| Cell { x, y }))
+            .flat_map(|head| {
+                Direction::ALL.map(|direction| Spawn {
+                    head,
+                    length,
+                    direction,
+                })
+            });
+        std::iter::once(centered)
+            .chain(anywhere)
+            .find(|spawn| spawn.validate(tiles).is_ok())
+    }
+
+    pub fn validate(&self, tiles: &TileMap) -> Result<(), String> {
+        if self.length == 0 {
+            return Err("spawn length must be at least 1".into());
+        }
+        let ahead = self.head.offset(self.direction);
+        for cell in self.cells().chain([ahead]) {
+            if !(0..tiles.width()).contains(&cell.x) || !(0..tiles.height()).contains(&cell.y) {
+                return Err(format!(
+                    "spawn of length {} at ({}, {}) heading {:?} does not fit a {}x{} board",
+                    self.length,
+                    self.head.x,
+                    self.head.y,
+                    self.direction,
+                    tiles.width(),
+                    tiles.height()
+                ));
+            }
+            if tiles.get(cell) != Tile::Empty {
+                return Err(format!("spawn cell ({}, {}) is not empty", cell.x, cell.y));
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,135 @@
+//! file: spectator.rs
+//! author: Jacob Xie
+//! date: 2026/10/16 18:15:25 Friday
+//! brief:
+
+use gpui::{
+    Bounds, Context, Entity, IntoElement, Render, SharedString, Subscription, Window, canvas, div,
+    fill, point, prelude::*, px, rgb, size,
+};
+
+use super::{Cell, CellView, SnakeGame, Tile, Weather, speedrun};
+
+/// A read-only second view of the same game, for streaming on another monitor
+/// while the main window stays compact: the whole board scaled to fit and the
+/// live stats, with no controls of its own.
+pub struct Spectator {
+    game: Entity<SnakeGame>,
+    _observer: Subscription,
+}
+
+impl Spectator {
+    pub fn new(game: Entity<SnakeGame>, cx: &mut Context<Self>) -> Self {
+        let observer = cx.observe(&game, |_, _, cx| cx.notify());
+        Self {
+            game,
+            _observer: observer,
+        }
+    }
+}
+
+impl Render for Spectator {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let game = self.game.read(cx);
+        let engine = game.displayed_engine();
+        let palette = game.accessibility.palette;
+        let (width, height) = (engine.board_width(), engine.board_height());
+
+        // Painted like the minimap, as the board is drawn at any size here.
+        let mut cells: Vec<(Cell, u32)> = engine
+            .tiles()
+            .cells()
+            .filter(|(_, tile)| *tile != Tile::Empty)
+            .map(|(cell, tile)| (cell, CellView::Tile(tile).color(palette, false, false)))
+            .collect();
+        cells.extend(
+            engine
+                .pellets()
+                .iter()
+                .map(|pellet| (pellet.cell, palette.pellet())),
+        );
+        if let Some(pickup) = engine.pickup() {
+            cells.push((pickup.cell, palette.pickup(pickup.power_up)));
+        }
+        if engine.weather() != Some(Weather::Rain) {
+            cells.push((engine.food(), palette.food(engine.food_kind())));
+        }
+        cells.extend(
+            engine
+                .snake()
+                .iter()
+                .map(|cell| (*cell, palette.body(false))),
+        );
+        if let Some(head) = engine.snake().front() {
+            cells.push((*head, palette.head()));
+        }
+        if engine.config().mirror {
+            for (cell, _) in &mut cells {
+                cell.x = width - 1 - cell.x;
+            }
+        }
+        let (empty, board) = (palette.empty(), palette.board());
+
+        let (status, status_color) = game.status_text();
+        let stat = |label: &'static str, value: SharedString| {
+            div()
+                .flex()
+                .flex_col()
+                .child(div().text_sm().text_color(rgb(0x94a3b8)).child(label))
+                .child(div().text_2xl().child(value))
+        };
+
+        div()
+            .size_full()
+            .flex()
+            .flex_col()
+            .gap_4()
+            .p_6()
+            .bg(rgb(0x020617))
+            .text_color(rgb(0xf8fafc))
+            .child(
+                div()
+                    .flex()
+                    .items_end()
+                    .gap_8()
+                    .child(stat("Player", game.roster.active().name.clone().into()))
+                    .child(stat("Score", engine.score().to_string().into()))
+                    .child(stat("Best", game.high_score.to_string().into()))
+                    .child(stat("Length", engine.snake().len().to_string().into()))
+                    .child(stat("Level", engine.level().to_string().into()))
+                    .child(stat("Time", speedrun::timer_text(engine.elapsed()).into()))
+                    .child(stat(
+                        "Tick",
+                        format!("{} ms", engine.tick_delay().as_millis()).into(),
+                    ))
+                    .child(div().text_xl().text_color(rgb(status_color)).child(status)),
+            )
+            .child(
+                canvas(
+                    |_, _, _| {},
+                    move |bounds, _, window, _| {
+                        window.paint_quad(fill(bounds, rgb(board)));
+                        let cell_px = (f32::from(bounds.size.width) / width as f32)
+                            .min(f32::from(bounds.size.height) / height as f32);
+                        let origin = bounds.origin
+                            + point(
+                                (bounds.size.width - px(cell_px * width as f32)) / 2.0,
+                                (bounds.size.height - px(cell_px * height as f32)) / 2.0,
+                            );
+                        let area = |x: i32, y: i32, w: i32, h: i32| {
+                            Bounds::new(
+                                origin + point(px(x as f32 * cell_px), px(y as f32 * cell_px)),
+                                size(px(w as f32 * cell_px), px(h as f32 * cell_px)),
+                            )
+                        };
+                        window.paint_quad(fill(area(0, 0, width, height), rgb(empty)));
+                        for (cell, color) in &cells {
+                            window.paint_quad(fill(area(cell.x, cell.y, 1, 1), rgb(*color)));
+                        }
+                    },
+                )
+                .flex_1()
+                .w_full(),
+            )
+    }
+}
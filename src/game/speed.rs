@@ -0,0 +1,197 @@
+//! file: speed.rs
+//! author: Jacob Xie
+//! date: 2026/10/16 17:18:00 Friday
+//! brief:
+
+use serde::{Deserialize, Serialize};
+
+use super::{SCORE_PER_LEVEL, SPEED_STEP_MS};
+
+/// How the tick shortens as the score climbs.
+pub trait SpeedCurve {
+    /// Tick length in ms at `score` for a run starting at `base_ms`, before
+    /// the engine's floor is applied.
+    fn tick_ms(&self, base_ms: u64, score: u32) -> u64;
+}
+
+/// Shortens the tick by `step_ms` every level.
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Linear {
+    pub step_ms: u64,
+}
+
+impl SpeedCurve for Linear {
+    fn tick_ms(&self, base_ms: u64, score: u32) -> u64 {
+        base_ms.saturating_sub(u64::from(score / SCORE_PER_LEVEL) * self.step_ms)
+    }
+}
+
+/// Holds the pace, then drops `step_ms` at once every `every` points.
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Stepped {
+    pub every: u32,
+    pub step_ms: u64,
+}
+
+impl SpeedCurve for Stepped {
+    fn tick_ms(&self, base_ms: u64, score: u32) -> u64 {
+        base_ms.saturating_sub(u64::from(score / self.every.max(1)) * self.step_ms)
+    }
+}
+
+/// Takes `percent` off the previous tick every level, so early levels speed
+/// up the most.
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Exponential {
+    pub percent: u32,
+}
+
+impl SpeedCurve for Exponential {
+    fn tick_ms(&self, base_ms: u64, score: u32) -> u64 {
+        let keep = 1.0 - f64::from(self.percent.min(100)) / 100.0;
+        let levels = (score / SCORE_PER_LEVEL).min(i32::MAX as u32) as i32;
+        (base_ms as f64 * keep.powi(levels)).round() as u64
+    }
+}
+
+/// Never speeds up.
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Flat;
+
+impl SpeedCurve for Flat {
+    fn tick_ms(&self, base_ms: u64, _: u32) -> u64 {
+        base_ms
+    }
+}
+
+const LINEAR_STEPS: [u64; 4] = [2, 4, 6, 8];
+const STEPPED_PRESETS: [(u32, u64); 3] = [(10, 10), (20, 20), (30, 30)];
+const EXPONENTIAL_PERCENTS: [u32; 3] = [2, 4, 8];
+
+/// The speed curve a run uses, with its parameters.
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum SpeedSetting {
+    Linear(Linear),
+    Stepped(Stepped),
+    Exponential(Exponential),
+    Flat(Flat),
+}
+
+impl Default for SpeedSetting {
+    fn default() -> Self {
+        SpeedSetting::Linear(Linear {
+            step_ms: SPEED_STEP_MS,
+        })
+    }
+}
+
+impl SpeedSetting {
+    pub fn curve(&self) -> &dyn SpeedCurve {
+        match self {
+            SpeedSetting::Linear(curve) => curve,
+            SpeedSetting::Stepped(curve) => curve,
+            SpeedSetting::Exponential(curve) => curve,
+            SpeedSetting::Flat(curve) => curve,
+        }
+    }
+
+    /// The next kind of curve, at its default parameters.
+    pub fn next_kind(self) -> Self {
+        match self {
+            SpeedSetting::Linear(_) => SpeedSetting::Stepped(Stepped {
+                every: STEPPED_PRESETS[0].0,
+                step_ms: STEPPED_PRESETS[0].1,
+            }),
+            SpeedSetting::Stepped(_) => SpeedSetting::Exponential(Exponential {
+                percent: EXPONENTIAL_PERCENTS[0],
+            }),
+            SpeedSetting::Exponential(_) => SpeedSetting::Flat(Flat),
+            SpeedSetting::Flat(_) => SpeedSetting::default(),
+        }
+    }
+
+    /// The same curve with its next preset parameters.
+    pub fn tune(self) -> Self {
+        fn next<T: Copy + PartialEq>(presets: &[T], current: T) -> T {
+            let index = presets.iter().position(|preset| *preset == current);
+            presets[index.map_or(0, |index| (index + 1) % presets.len())]
+        }
+        match self {
+            SpeedSetting::Linear(curve) => SpeedSetting::Linear(Linear {
+                step_ms: next(&LINEAR_STEPS, curve.step_ms),
+            }),
+            SpeedSetting::Stepped(curve) => {
+                let (every, step_ms) = next(&STEPPED_PRESETS, (curve.every, curve.step_ms));
+                SpeedSetting::Stepped(Stepped { every, step_ms })
+            }
+            SpeedSetting::Exponential(curve) => SpeedSetting::Exponential(Exponential {
+                percent: next(&EXPONENTIAL_PERCENTS, curve.percent),
+            }),
+            SpeedSetting::Flat(curve) => SpeedSetting::Flat(curve),
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SpeedSetting::Linear(_) => "Linear",
+            SpeedSetting::Stepped(_) => "Stepped",
+            SpeedSetting::Exponential(_) => "Exponential",
+            SpeedSetting::Flat(_) => "Flat",
+        }
+    }
+
+    /// The tunable parameters, or `None` for a curve without any.
+    pub fn params_text(&self) -> Option<String> {
+        match self {
+            SpeedSetting::Linear(curve) => Some(format!("-{}ms/level", curve.step_ms)),
+            SpeedSetting::Stepped(curve) => {
+                Some(format!("-{}ms every {} pts", curve.step_ms, curve.every))
+            }
+            SpeedSetting::Exponential(curve) => Some(format!("-{}%/level", curve.percent)),
+            SpeedSetting::Flat(_) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_default_curve_keeps_the_old_speedup() {
+        let curve = SpeedSetting::default();
+        for score in [0, 3, 4, 17, 40] {
+            let old = 150u64.saturating_sub(u64::from(score / 4) * SPEED_STEP_MS);
+            assert_eq!(curve.curve().tick_ms(150, score), old);
+        }
+    }
+
+    #[test]
+    fn curves_only_ever_speed_up() {
+        let mut setting = SpeedSetting::default();
+        for _ in 0..4 {
+            let ticks: Vec<u64> = (0..200)
+                .map(|score| setting.curve().tick_ms(150, score))
+                .collect();
+            assert!(
+                ticks.windows(2).all(|pair| pair[1] <= pair[0]),
+                "{}",
+                setting.label()
+            );
+            setting = setting.next_kind();
+        }
+        assert!(setting == SpeedSetting::default());
+    }
+
+    #[test]
+    fn tuning_cycles_back_to_the_start() {
+        let start = SpeedSetting::default();
+        let mut setting = start.tune();
+        let mut seen = 1;
+        while setting != start {
+            setting = setting.tune();
+            seen += 1;
+        }
+        assert_eq!(seen, LINEAR_STEPS.len());
+    }
+}
@@ -0,0 +1,106 @@
+//! file: speedrun.rs
+//! author: Jacob Xie
+//! date: 2026/10/16 14:44:09 Friday
+//! brief:
+
+use std::{collections::HashMap, time::Duration};
+
+use serde::{Deserialize, Serialize};
+
+use super::{GameConfig, storage};
+
+/// Snake length a speedrun has to reach.
+pub const SPEEDRUN_GOAL: usize = 50;
+/// A split is taken every time the snake grows by this many cells.
+pub const SPLIT_EVERY: usize = 10;
+/// A ghost checkpoint is taken every time the snake eats this many pieces of food.
+pub const CHECKPOINT_EVERY: usize = 5;
+const SPLITS_FILE: &str = "splits.json";
+
+/// Personal-best split times, keyed by board and goal length. Each entry holds
+/// the fastest time ever recorded to reach each split on its own, so the last
+/// entry doubles as the best final time. Alongside them are the checkpoint
+/// times of the personal-best run, the ghost live runs race against.
+#[derive(Default, Serialize, Deserialize)]
+pub struct SplitRecords {
+    #[serde(default)]
+    best: HashMap<String, Vec<Duration>>,
+    #[serde(default, alias = "runs")]
+    ghosts: HashMap<String, Vec<Duration>>,
+}
+
+impl SplitRecords {
+    pub fn load() -> Self {
+        storage::read_json(&storage::data_path(SPLITS_FILE)).unwrap_or_default()
+    }
+
+    fn key(config: &GameConfig) -> String {
+        format!(
+            "{}/{}",
+            config.layout.label(),
+            config.goal_length.unwrap_or_default()
+        )
+    }
+
+    pub fn best(&self, config: &GameConfig) -> &[Duration] {
+        self.best
+            .get(&Self::key(config))
+            .map_or(&[], |splits| splits.as_slice())
+    }
+
+    pub fn ghost(&self, config: &GameConfig) -> &[Duration] {
+        self.ghosts
+            .get(&Self::key(config))
+            .map_or(&[], |checkpoints| checkpoints.as_slice())
+    }
+
+    /// Folds a completed run into the records and saves them, returning whether
+    /// the final time is a new personal best. A new best also becomes the ghost.
+    pub fn record(
+        &mut self,
+        config: &GameConfig,
+        splits: &[Duration],
+        checkpoints: &[Duration],
+    ) -> bool {
+        let key = Self::key(config);
+        let best = self.best.entry(key.clone()).or_default();
+        let new_best = splits.len() > best.len()
+            || splits
+                .last()
+                .zip(best.last())
+                .is_some_and(|(run, pb)| run < pb);
+        for (index, split) in splits.iter().enumerate() {
+            match best.get_mut(index) {
+                Some(pb) => *pb = (*pb).min(*split),
+                None => best.push(*split),
+            }
+        }
+        if new_best {
+            self.ghosts.insert(key, checkpoints.to_vec());
+        }
+        if let Err(err) = storage::write_json(&storage::data_path(SPLITS_FILE), self) {
+            tracing::warn!("Failed to save split records: {err}");
+        }
+        new_best
+    }
+}
+
+pub fn timer_text(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    format!(
+        "{}:{:02}.{:03}",
+        secs / 60,
+        secs % 60,
+        duration.subsec_millis()
+    )
+}
+
+/// Signed difference against a personal best in seconds, e.g. "-0.412" when
+/// ahead.
+pub fn delta_text(split: Duration, best: Duration) -> String {
+    if split <= best {
+        format!("-{:.3}", (best - split).as_secs_f64())
+    } else {
+        format!("+{:.3}", (split - best).as_secs_f64())
+    }
+}
@@ -0,0 +1,134 @@
+//! file: stats.rs
+//! author: Jacob Xie
+//! date: 2026/10/16 15:06:01 Friday
+//! brief:
+
+use std::{collections::HashMap, io};
+
+use serde::{Deserialize, Serialize};
+
+use super::storage;
+
+const PROFILE_FILE: &str = "profile.json";
+/// Runs with less food than this are too short to judge a playstyle.
+const MIN_FOOD: usize = 10;
+/// Most ticks per piece of food for Speed Demon.
+const SPEED_DEMON_PACE: u64 = 15;
+
+/// Playstyle numbers gathered over a single run.
+#[derive(Clone, Copy)]
+pub struct RunStats {
+    pub ticks: u64,
+    pub eaten: usize,
+    pub turns: usize,
+    /// Ticks spent with the head right next to a wall or obstacle.
+    pub wall_ticks: u64,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Title {
+    WallHugger,
+    SpeedDemon,
+    Minimalist,
+}
+
+impl Title {
+    pub const ALL: [Title; 3] = [Title::WallHugger, Title::SpeedDemon, Title::Minimalist];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Title::WallHugger => "Wall Hugger",
+            Title::SpeedDemon => "Speed Demon",
+            Title::Minimalist => "Minimalist",
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            Title::WallHugger => "Spent half the run right next to a wall",
+            Title::SpeedDemon => "Averaged a meal every 15 ticks or faster",
+            Title::Minimalist => "Took no more than three turns for every two meals",
+        }
+    }
+}
+
+impl RunStats {
+    /// The titles this run's playstyle earns.
+    pub fn titles(&self) -> Vec<Title> {
+        Title::ALL
+            .into_iter()
+            .filter(|title| self.earns(*title))
+            .collect()
+    }
+
+    fn earns(&self, title: Title) -> bool {
+        if self.eaten < MIN_FOOD {
+            return false;
+        }
+        match title {
+            Title::WallHugger => self.wall_ticks * 2 >= self.ticks,
+            Title::SpeedDemon => self.ticks <= self.eaten as u64 * SPEED_DEMON_PACE,
+            Title::Minimalist => self.turns * 2 <= self.eaten * 3,
+        }
+    }
+}
+
+/// Lifetime totals and earned titles, shown on the profile page.
+#[derive(Default, Serialize, Deserialize)]
+pub struct Profile {
+    runs: u32,
+    food: u64,
+    best_score: u32,
+    /// Best on a mirrored board, kept apart as it is a different challenge.
+    #[serde(default)]
+    mirror_best: u32,
+    titles: HashMap<Title, u32>,
+}
+
+impl Profile {
+    pub fn load() -> Self {
+        storage::read_json(&storage::data_path(PROFILE_FILE)).unwrap_or_default()
+    }
+
+    fn store(&self) -> io::Result<()> {
+        storage::write_json(&storage::data_path(PROFILE_FILE), self)
+    }
+
+    pub fn runs(&self) -> u32 {
+        self.runs
+    }
+
+    pub fn food(&self) -> u64 {
+        self.food
+    }
+
+    pub fn best_score(&self, mirror: bool) -> u32 {
+        if mirror {
+            self.mirror_best
+        } else {
+            self.best_score
+        }
+    }
+
+    /// How many runs have earned `title`.
+    pub fn title_count(&self, title: Title) -> u32 {
+        self.titles.get(&title).copied().unwrap_or_default()
+    }
+
+    pub fn record(&mut self, stats: &RunStats, score: u32, titles: &[Title], mirror: bool) {
+        self.runs += 1;
+        self.food += stats.eaten as u64;
+        let best = if mirror {
+            &mut self.mirror_best
+        } else {
+            &mut self.best_score
+        };
+        *best = (*best).max(score);
+        for title in titles {
+            *self.titles.entry(*title).or_default() += 1;
+        }
+        if let Err(err) = self.store() {
+            tracing::warn!("Failed to save profile: {err}");
+        }
+    }
+}
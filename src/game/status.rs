@@ -3,7 +3,9 @@
 //! date: 2025/12/14 23:44:54 Sunday
 //! brief:
 
-#[derive(Clone, Copy, PartialEq, Eq)]
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum GameStatus {
     Ready,
     Running,
@@ -0,0 +1,67 @@
+//! file: steering.rs
+//! author: Jacob Xie
+//! date: 2026/10/16 18:39:05 Friday
+//! brief:
+
+use super::Direction;
+
+/// A turn relative to the way the snake is heading, for the two-key scheme.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Turn {
+    Left,
+    Right,
+}
+
+impl Turn {
+    /// The board direction taken by turning this way from `heading`.
+    pub fn from(self, heading: Direction) -> Direction {
+        match (self, heading) {
+            (Turn::Left, Direction::Up) | (Turn::Right, Direction::Down) => Direction::Left,
+            (Turn::Left, Direction::Down) | (Turn::Right, Direction::Up) => Direction::Right,
+            (Turn::Left, Direction::Right) | (Turn::Right, Direction::Left) => Direction::Up,
+            (Turn::Left, Direction::Left) | (Turn::Right, Direction::Right) => Direction::Down,
+        }
+    }
+
+    /// The same key read as a plain direction, for screens with no heading.
+    pub fn direction(self) -> Direction {
+        match self {
+            Turn::Left => Direction::Left,
+            Turn::Right => Direction::Right,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn four_turns_the_same_way_come_full_circle() {
+        for turn in [Turn::Left, Turn::Right] {
+            let mut heading = Direction::Up;
+            let mut seen = Vec::new();
+            for _ in 0..4 {
+                heading = turn.from(heading);
+                assert!(!heading.is_opposite(*seen.last().unwrap_or(&Direction::Up)));
+                seen.push(heading);
+            }
+            assert_eq!(heading, Direction::Up);
+            assert_eq!(
+                seen[0],
+                if turn == Turn::Left {
+                    Direction::Left
+                } else {
+                    Direction::Right
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn left_then_right_undoes_a_turn() {
+        for heading in Direction::ALL {
+            assert_eq!(Turn::Right.from(Turn::Left.from(heading)), heading);
+        }
+    }
+}
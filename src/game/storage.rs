@@ -0,0 +1,73 @@
+//! file: storage.rs
+//! author: Jacob Xie
+//! date: 2026/10/16 14:32:55 Friday
+//! brief:
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    sync::RwLock,
+};
+
+use serde::{Serialize, de::DeserializeOwned};
+
+pub fn data_dir() -> PathBuf {
+    dirs::data_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("gpui-snake")
+}
+
+/// The active player's folder under the data directory.
+static PLAYER_DIR: RwLock<String> = RwLock::new(String::new());
+
+pub fn set_player_dir(dir: &str) {
+    *PLAYER_DIR.write().unwrap_or_else(|err| err.into_inner()) = dir.to_string();
+}
+
+/// A file belonging to the active player.
+pub fn data_path(name: &str) -> PathBuf {
+    let dir = PLAYER_DIR.read().unwrap_or_else(|err| err.into_inner());
+    data_dir().join(&*dir).join(name)
+}
+
+/// A file shared by every player.
+pub fn shared_path(name: &str) -> PathBuf {
+    data_dir().join(name)
+}
+
+/// Deletes the whole data directory: every player's records and settings,
+/// replays, saves, logs and scripts.
+pub fn wipe() -> io::Result<()> {
+    match fs::remove_dir_all(data_dir()) {
+        Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err),
+        _ => Ok(()),
+    }
+}
+
+/// Total size of everything under `path`, zero if it cannot be read.
+pub fn used_bytes(path: &Path) -> u64 {
+    let Ok(entries) = fs::read_dir(path) else {
+        return 0;
+    };
+    entries
+        .flatten()
+        .map(|entry| match entry.metadata() {
+            Ok(meta) if meta.is_dir() => used_bytes(&entry.path()),
+            Ok(meta) => meta.len(),
+            Err(_) => 0,
+        })
+        .sum()
+}
+
+pub fn read_json<T: DeserializeOwned>(path: &Path) -> io::Result<T> {
+    let text = fs::read_to_string(path)?;
+    serde_json::from_str(&text).map_err(io::Error::other)
+}
+
+pub fn write_json<T: Serialize>(path: &Path, value: &T) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let text = serde_json::to_string_pretty(value).map_err(io::Error::other)?;
+    fs::write(path, text)
+}
@@ -0,0 +1,59 @@
+//! file: summary.rs
+//! author: Jacob Xie
+//! date: 2026/10/16 14:26:15 Friday
+//! brief:
+
+use std::time::Duration;
+
+use super::{DeathCause, Engine, Replay, RunStats, Timeline, Title};
+
+pub struct RunSummary {
+    pub score: u32,
+    pub length: usize,
+    pub duration: Duration,
+    pub cause: Option<DeathCause>,
+    pub new_record: bool,
+    /// Final speedrun time, set only when the goal length was reached.
+    pub final_time: Option<Duration>,
+    pub personal_best: bool,
+    pub stats: RunStats,
+    pub titles: Vec<Title>,
+    pub replay: Replay,
+    /// The run laid out for scrubbing on the summary screen.
+    pub timeline: Timeline,
+    /// Who played the run, and whose records it counts towards.
+    pub player: String,
+}
+
+impl RunSummary {
+    pub fn from_engine(engine: &Engine, previous_best: u32, player: &str) -> Self {
+        let stats = engine.stats();
+        Self {
+            score: engine.score(),
+            length: engine.snake().len(),
+            duration: engine.elapsed(),
+            cause: engine.death(),
+            new_record: engine.score() > previous_best,
+            final_time: engine
+                .completed()
+                .then(|| engine.splits().last().copied())
+                .flatten(),
+            personal_best: false,
+            stats,
+            titles: stats.titles(),
+            replay: engine.replay(),
+            timeline: Timeline::new(engine.replay(), engine.ticks()),
+            player: player.to_string(),
+        }
+    }
+
+    pub fn duration_text(&self) -> String {
+        let secs = self.duration.as_secs();
+        format!(
+            "{}:{:02}.{}",
+            secs / 60,
+            secs % 60,
+            self.duration.subsec_millis() / 100
+        )
+    }
+}
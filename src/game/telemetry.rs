@@ -0,0 +1,119 @@
+//! file: telemetry.rs
+//! author: Jacob Xie
+//! date: 2026/10/16 17:23:54 Friday
+//! brief:
+
+use std::{
+    backtrace::Backtrace,
+    fs::{self, File},
+    io, panic,
+    path::Path,
+    sync::Mutex,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use tracing_subscriber::{
+    Layer, filter::LevelFilter, fmt, layer::SubscriberExt, util::SubscriberInitExt,
+};
+
+use super::{Privacy, storage};
+
+const LOG_DIR: &str = "logs";
+/// Session logs kept on disk, counting the one being started.
+const KEEP_SESSIONS: usize = 10;
+
+/// Starts the session log. Everything is written to a fresh file under the
+/// data directory as it happens, so a crash loses nothing, unless input
+/// traces are turned off in the privacy settings; stderr gets warnings only,
+/// or everything with `verbose`. Panics are logged with a backtrace before
+/// the default hook runs.
+pub fn init_logging(verbose: bool) {
+    let file = match Privacy::load()
+        .input_traces
+        .then(|| open_session_log(&storage::shared_path(LOG_DIR)))
+    {
+        Some(Ok(file)) => Some(file),
+        Some(Err(err)) => {
+            eprintln!("Failed to open session log: {err}");
+            None
+        }
+        None => None,
+    };
+    let stderr_level = if verbose {
+        LevelFilter::DEBUG
+    } else {
+        LevelFilter::WARN
+    };
+    tracing_subscriber::registry()
+        .with(
+            fmt::layer()
+                .with_writer(io::stderr)
+                .with_filter(stderr_level),
+        )
+        .with(file.map(|file| {
+            fmt::layer()
+                .with_ansi(false)
+                .with_writer(Mutex::new(file))
+                .with_filter(LevelFilter::DEBUG)
+        }))
+        .init();
+
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        tracing::error!(target: "panic", "{info}\n{}", Backtrace::force_capture());
+        default_hook(info);
+    }));
+    tracing::info!("Session started, version {}", env!("CARGO_PKG_VERSION"));
+}
+
+/// Creates this session's log file, first deleting the oldest ones beyond
+/// `KEEP_SESSIONS`.
+fn open_session_log(dir: &Path) -> io::Result<File> {
+    fs::create_dir_all(dir)?;
+    let names: Vec<String> = fs::read_dir(dir)?
+        .flatten()
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .collect();
+    for name in stale_logs(names) {
+        if let Err(err) = fs::remove_file(dir.join(&name)) {
+            eprintln!("Failed to remove old session log {name}: {err}");
+        }
+    }
+    let started = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis();
+    File::create(dir.join(format!("session-{started}.log")))
+}
+
+/// Session logs among `names` to delete so a new one fits within
+/// `KEEP_SESSIONS`. Names carry their start time, so they sort oldest first.
+fn stale_logs(names: Vec<String>) -> Vec<String> {
+    let mut logs: Vec<String> = names
+        .into_iter()
+        .filter(|name| name.starts_with("session-") && name.ends_with(".log"))
+        .collect();
+    logs.sort();
+    let stale = logs.len().saturating_sub(KEEP_SESSIONS - 1);
+    logs.truncate(stale);
+    logs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_the_oldest_logs_go() {
+        let mut names: Vec<String> = (0..KEEP_SESSIONS + 2)
+            .map(|index| format!("session-{}.log", 1000 + index))
+            .collect();
+        names.push("notes.txt".into());
+        names.reverse();
+        assert_eq!(
+            stale_logs(names),
+            ["session-1000.log", "session-1001.log", "session-1002.log"]
+        );
+        assert!(stale_logs(vec!["session-1.log".into()]).is_empty());
+    }
+}
@@ -0,0 +1,178 @@
+//! file: theme.rs
+//! author: Jacob Xie
+//! date: 2026/10/16 18:21:03 Friday
+//! brief:
+
+use std::{collections::HashMap, io};
+
+use serde::{Deserialize, Serialize};
+
+use super::storage;
+
+const THEME_FILE: &str = "themes.json";
+
+/// The kind of run on the board, each with its own look.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum GameMode {
+    FreePlay,
+    Daily,
+    Speedrun,
+    Drill,
+    Practice,
+    Tutorial,
+    Versus,
+}
+
+impl GameMode {
+    pub fn label(self) -> &'static str {
+        match self {
+            GameMode::FreePlay => "Free play",
+            GameMode::Daily => "Daily challenge",
+            GameMode::Speedrun => "Speedrun",
+            GameMode::Drill => "Drill",
+            GameMode::Practice => "Practice",
+            GameMode::Tutorial => "Tutorial",
+            GameMode::Versus => "Versus",
+        }
+    }
+
+    /// The theme a mode wears until the player picks another.
+    pub fn default_theme(self) -> Theme {
+        match self {
+            GameMode::FreePlay => Theme::Slate,
+            GameMode::Daily => Theme::Gold,
+            GameMode::Speedrun => Theme::Crimson,
+            GameMode::Drill => Theme::Emerald,
+            GameMode::Practice | GameMode::Tutorial => Theme::Sky,
+            GameMode::Versus => Theme::Violet,
+        }
+    }
+}
+
+/// Accent drawn around the board. Cell colors stay with the palette, so an
+/// accessible palette is never traded away for a mode's look.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Theme {
+    Slate,
+    Gold,
+    Crimson,
+    Emerald,
+    Sky,
+    Violet,
+}
+
+impl Theme {
+    pub const ALL: [Theme; 6] = [
+        Theme::Slate,
+        Theme::Gold,
+        Theme::Crimson,
+        Theme::Emerald,
+        Theme::Sky,
+        Theme::Violet,
+    ];
+
+    pub fn next(self) -> Self {
+        let index = Self::ALL
+            .iter()
+            .position(|theme| *theme == self)
+            .unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Theme::Slate => "Slate",
+            Theme::Gold => "Gold",
+            Theme::Crimson => "Crimson",
+            Theme::Emerald => "Emerald",
+            Theme::Sky => "Sky",
+            Theme::Violet => "Violet",
+        }
+    }
+
+    pub fn accent(self) -> u32 {
+        match self {
+            Theme::Slate => 0x475569,
+            Theme::Gold => 0xfacc15,
+            Theme::Crimson => 0xef4444,
+            Theme::Emerald => 0x10b981,
+            Theme::Sky => 0x38bdf8,
+            Theme::Violet => 0xa78bfa,
+        }
+    }
+}
+
+/// Themes the player picked for particular modes, over the modes' defaults.
+#[derive(Default, Serialize, Deserialize)]
+pub struct ThemeOverrides {
+    picked: HashMap<GameMode, Theme>,
+}
+
+impl ThemeOverrides {
+    pub fn load() -> Self {
+        storage::read_json(&storage::data_path(THEME_FILE)).unwrap_or_default()
+    }
+
+    pub fn store(&self) -> io::Result<()> {
+        storage::write_json(&storage::data_path(THEME_FILE), self)
+    }
+
+    pub fn theme(&self, mode: GameMode) -> Theme {
+        self.picked
+            .get(&mode)
+            .copied()
+            .unwrap_or_else(|| mode.default_theme())
+    }
+
+    pub fn is_overridden(&self, mode: GameMode) -> bool {
+        self.picked.contains_key(&mode)
+    }
+
+    /// Moves the mode on to the next theme. Landing back on its default
+    /// clears the pick, so later changes to the default reach it again.
+    pub fn cycle(&mut self, mode: GameMode) {
+        let next = self.theme(mode).next();
+        if next == mode.default_theme() {
+            self.picked.remove(&mode);
+        } else {
+            self.picked.insert(mode, next);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn modes_use_their_default_until_overridden() {
+        let mut themes = ThemeOverrides::default();
+        assert_eq!(themes.theme(GameMode::Daily), Theme::Gold);
+        assert_eq!(themes.theme(GameMode::Speedrun), Theme::Crimson);
+
+        themes.cycle(GameMode::Daily);
+        assert_eq!(themes.theme(GameMode::Daily), Theme::Crimson);
+        assert!(themes.is_overridden(GameMode::Daily));
+        assert_eq!(themes.theme(GameMode::FreePlay), Theme::Slate);
+        assert!(!themes.is_overridden(GameMode::FreePlay));
+    }
+
+    #[test]
+    fn cycling_back_to_the_default_clears_the_override() {
+        let mut themes = ThemeOverrides::default();
+        for _ in 0..Theme::ALL.len() {
+            themes.cycle(GameMode::Versus);
+        }
+        assert_eq!(themes.theme(GameMode::Versus), Theme::Violet);
+        assert!(!themes.is_overridden(GameMode::Versus));
+    }
+
+    #[test]
+    fn overrides_survive_a_round_trip() {
+        let mut themes = ThemeOverrides::default();
+        themes.cycle(GameMode::Drill);
+        let json = serde_json::to_string(&themes).unwrap();
+        let back: ThemeOverrides = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.theme(GameMode::Drill), Theme::Sky);
+    }
+}
@@ -0,0 +1,183 @@
+//! file: tiles.rs
+//! author: Jacob Xie
+//! date: 2026/10/16 14:29:30 Friday
+//! brief:
+
+use serde::{Deserialize, Serialize};
+
+use super::{Cell, Direction};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Tile {
+    Empty,
+    Wall,
+    /// One end of a portal pair; both ends share the same id.
+    Portal(u8),
+    /// Pushes a head that lands on it one more cell this way.
+    Conveyor(Direction),
+    /// Can only be entered heading this way; from any other side it is a wall.
+    Gate(Direction),
+}
+
+impl Tile {
+    /// Numbers portal pairs, so the two ends can be matched without color,
+    /// and points conveyors and gates the way they work.
+    pub fn glyph(self) -> Option<&'static str> {
+        const PAIRS: [&str; 4] = ["1", "2", "3", "4"];
+        match self {
+            Tile::Portal(id) => Some(PAIRS[id as usize % PAIRS.len()]),
+            Tile::Conveyor(direction) => Some(match direction {
+                Direction::Up => "↑",
+                Direction::Down => "↓",
+                Direction::Left => "←",
+                Direction::Right => "→",
+            }),
+            Tile::Gate(direction) => Some(match direction {
+                Direction::Up => "⇧",
+                Direction::Down => "⇩",
+                Direction::Left => "⇦",
+                Direction::Right => "⇨",
+            }),
+            Tile::Empty | Tile::Wall => None,
+        }
+    }
+
+    /// This tile as drawn on a mirrored board, so its arrow matches the view.
+    pub fn mirrored(self) -> Tile {
+        match self {
+            Tile::Conveyor(direction) => Tile::Conveyor(direction.mirrored()),
+            Tile::Gate(direction) => Tile::Gate(direction.mirrored()),
+            tile => tile,
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct TileMap {
+    width: i32,
+    height: i32,
+    tiles: Vec<Tile>,
+}
+
+impl TileMap {
+    pub fn new(width: i32, height: i32) -> Self {
+        Self {
+            width,
+            height,
+            tiles: vec![Tile::Empty; (width.max(0) * height.max(0)) as usize],
+        }
+    }
+
+    /// Checks that the map holds exactly one tile per cell, which a map read
+    /// from elsewhere may not.
+    pub fn validate(&self) -> Result<(), String> {
+        let cells = self.width.max(0) as usize * self.height.max(0) as usize;
+        if self.tiles.len() != cells {
+            return Err(format!(
+                "tile map has {} tiles for a {}x{} board",
+                self.tiles.len(),
+                self.width,
+                self.height
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn width(&self) -> i32 {
+        self.width
+    }
+
+    pub fn height(&self) -> i32 {
+        self.height
+    }
+
+    fn index(&self, cell: Cell) -> Option<usize> {
+        ((0..self.width).contains(&cell.x) && (0..self.height).contains(&cell.y))
+            .then(|| (cell.y * self.width + cell.x) as usize)
+    }
+
+    pub fn get(&self, cell: Cell) -> Tile {
+        self.index(cell)
+            .map_or(Tile::Empty, |index| self.tiles[index])
+    }
+
+    pub fn set(&mut self, cell: Cell, tile: Tile) {
+        if let Some(index) = self.index(cell) {
+            self.tiles[index] = tile;
+        }
+    }
+
+    pub fn cells(&self) -> impl Iterator<Item = (Cell, Tile)> + '_ {
+        (0..self.height)
+            .flat_map(move |y| (0..self.width).map(move |x| Cell { x, y }))
+            .map(|cell| (cell, self.get(cell)))
+    }
+
+    /// Where the head comes out when it enters the portal at `cell`.
+    pub fn portal_exit(&self, cell: Cell) -> Option<Cell> {
+        let Tile::Portal(id) = self.get(cell) else {
+            return None;
+        };
+        self.cells()
+            .find(|&(other, tile)| other != cell && tile == Tile::Portal(id))
+            .map(|(other, _)| other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn map() -> TileMap {
+        let mut tiles = TileMap::new(6, 4);
+        tiles.set(Cell { x: 1, y: 1 }, Tile::Portal(0));
+        tiles.set(Cell { x: 4, y: 2 }, Tile::Portal(0));
+        tiles.set(Cell { x: 2, y: 3 }, Tile::Portal(1));
+        tiles.set(Cell { x: 0, y: 0 }, Tile::Wall);
+        tiles
+    }
+
+    #[test]
+    fn a_portal_leads_to_its_pair() {
+        let tiles = map();
+        assert_eq!(
+            tiles.portal_exit(Cell { x: 1, y: 1 }),
+            Some(Cell { x: 4, y: 2 })
+        );
+        assert_eq!(
+            tiles.portal_exit(Cell { x: 4, y: 2 }),
+            Some(Cell { x: 1, y: 1 })
+        );
+    }
+
+    #[test]
+    fn unpaired_portals_and_other_tiles_have_no_exit() {
+        let tiles = map();
+        assert_eq!(tiles.portal_exit(Cell { x: 2, y: 3 }), None);
+        assert_eq!(tiles.portal_exit(Cell { x: 0, y: 0 }), None);
+        assert_eq!(tiles.portal_exit(Cell { x: 3, y: 3 }), None);
+    }
+
+    #[test]
+    fn cells_off_the_map_read_as_empty() {
+        let mut tiles = map();
+        let outside = Cell { x: 6, y: 0 };
+        tiles.set(outside, Tile::Wall);
+        assert_eq!(tiles.get(outside), Tile::Empty);
+        assert_eq!(tiles.cells().count(), 24);
+        assert_eq!(tiles.validate(), Ok(()));
+    }
+
+    #[test]
+    fn mirroring_flips_only_sideways_arrows() {
+        assert_eq!(
+            Tile::Conveyor(Direction::Left).mirrored(),
+            Tile::Conveyor(Direction::Right)
+        );
+        assert_eq!(
+            Tile::Gate(Direction::Up).mirrored(),
+            Tile::Gate(Direction::Up)
+        );
+        assert_eq!(Tile::Portal(2).mirrored(), Tile::Portal(2));
+    }
+}
@@ -0,0 +1,100 @@
+//! file: training.rs
+//! author: Jacob Xie
+//! date: 2026/10/16 17:27:06 Friday
+//! brief:
+
+use std::time::Duration;
+
+use super::Engine;
+
+/// Speed multipliers practice mode steps through with +/-.
+const SPEEDS: [f32; 7] = [0.25, 0.5, 0.75, 1.0, 1.5, 2.0, 3.0];
+const NORMAL_SPEED: usize = 3;
+
+/// Practice mode: a free run that records nothing, played at any speed,
+/// frozen and stepped a tick at a time, or with walls made harmless.
+pub struct Training {
+    speed_index: usize,
+    frozen: bool,
+    walls_off: bool,
+}
+
+impl Training {
+    pub fn new() -> Self {
+        Self {
+            speed_index: NORMAL_SPEED,
+            frozen: false,
+            walls_off: false,
+        }
+    }
+
+    pub fn faster(&mut self) {
+        self.speed_index = (self.speed_index + 1).min(SPEEDS.len() - 1);
+    }
+
+    pub fn slower(&mut self) {
+        self.speed_index = self.speed_index.saturating_sub(1);
+    }
+
+    pub fn speed(&self) -> f32 {
+        SPEEDS[self.speed_index]
+    }
+
+    /// How long a tick of `delay` lasts at the chosen speed.
+    pub fn scale(&self, delay: Duration) -> Duration {
+        delay.div_f32(self.speed())
+    }
+
+    pub fn frozen(&self) -> bool {
+        self.frozen
+    }
+
+    pub fn set_frozen(&mut self, engine: &mut Engine, frozen: bool) {
+        self.frozen = frozen;
+        self.apply(engine);
+    }
+
+    pub fn toggle_walls(&mut self, engine: &mut Engine) {
+        self.walls_off = !self.walls_off;
+        self.apply(engine);
+    }
+
+    /// Carries the mode's flags over to `engine`, which a fresh engine lacks.
+    pub fn apply(&self, engine: &mut Engine) {
+        engine.set_stepping(self.frozen);
+        engine.set_wall_immune(self.walls_off);
+    }
+
+    pub fn status_text(&self) -> String {
+        let mut text = format!("Practice mode - {}x speed (-/=)", self.speed());
+        if self.frozen {
+            text.push_str(", frozen (. to step, / to resume)");
+        } else {
+            text.push_str(", . to freeze");
+        }
+        if self.walls_off {
+            text.push_str(", walls off (Shift+W)");
+        }
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn speed_stays_within_its_steps() {
+        let mut training = Training::new();
+        assert_eq!(training.scale(Duration::from_millis(150)).as_millis(), 150);
+        for _ in 0..SPEEDS.len() {
+            training.faster();
+        }
+        assert_eq!(training.speed(), SPEEDS[SPEEDS.len() - 1]);
+        assert_eq!(training.scale(Duration::from_millis(150)).as_millis(), 50);
+        for _ in 0..SPEEDS.len() {
+            training.slower();
+        }
+        assert_eq!(training.scale(Duration::from_millis(150)).as_millis(), 600);
+    }
+}
@@ -0,0 +1,94 @@
+//! file: tutorial.rs
+//! author: Jacob Xie
+//! date: 2026/10/16 15:13:43 Friday
+//! brief:
+
+use super::{GameEvent, GameStatus};
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum TutorialStep {
+    Steer,
+    Eat,
+    Pause,
+    Resume,
+    Reverse,
+    Done,
+}
+
+impl TutorialStep {
+    const LESSONS: usize = 5;
+
+    fn next(self) -> Self {
+        match self {
+            TutorialStep::Steer => TutorialStep::Eat,
+            TutorialStep::Eat => TutorialStep::Pause,
+            TutorialStep::Pause => TutorialStep::Resume,
+            TutorialStep::Resume => TutorialStep::Reverse,
+            TutorialStep::Reverse | TutorialStep::Done => TutorialStep::Done,
+        }
+    }
+}
+
+/// Walks a new player through the basics one prompt at a time, moving on once
+/// the player has done what the current prompt asks.
+pub struct Tutorial {
+    step: TutorialStep,
+}
+
+impl Tutorial {
+    pub fn new() -> Self {
+        Self {
+            step: TutorialStep::Steer,
+        }
+    }
+
+    pub fn prompt(&self) -> &'static str {
+        match self.step {
+            TutorialStep::Steer => "Use the arrow keys to turn the snake",
+            TutorialStep::Eat => "Steer into the food to eat it and grow",
+            TutorialStep::Pause => "Press Space to pause",
+            TutorialStep::Resume => "Press Space again to carry on",
+            TutorialStep::Reverse => {
+                "Try turning straight back - the snake can't reverse into its own body"
+            }
+            TutorialStep::Done => "You're ready! Press Enter to start a real game",
+        }
+    }
+
+    /// Progress label such as "Step 2 of 5", or `None` once finished.
+    pub fn progress(&self) -> Option<String> {
+        let index = match self.step {
+            TutorialStep::Steer => 1,
+            TutorialStep::Eat => 2,
+            TutorialStep::Pause => 3,
+            TutorialStep::Resume => 4,
+            TutorialStep::Reverse => 5,
+            TutorialStep::Done => return None,
+        };
+        Some(format!("Step {index} of {}", TutorialStep::LESSONS))
+    }
+
+    /// Advances past the current step if `event` is what it asks for.
+    pub fn observe(&mut self, event: &GameEvent) {
+        let done = matches!(
+            (self.step, event),
+            (TutorialStep::Steer, GameEvent::DirectionChanged(_))
+                | (TutorialStep::Eat, GameEvent::FoodEaten { .. })
+                | (TutorialStep::Reverse, GameEvent::ReversalBlocked)
+        );
+        if done {
+            self.step = self.step.next();
+        }
+    }
+
+    /// Advances the pause steps, which happen outside the simulation.
+    pub fn observe_status(&mut self, status: GameStatus) {
+        let done = matches!(
+            (self.step, status),
+            (TutorialStep::Pause, GameStatus::Paused) | (TutorialStep::Resume, GameStatus::Running)
+        );
+        if done {
+            self.step = self.step.next();
+        }
+    }
+}
@@ -0,0 +1,352 @@
+//! file: versus.rs
+//! author: Jacob Xie
+//! date: 2026/10/16 15:09:15 Friday
+//! brief:
+
+use std::{collections::HashMap, time::Duration};
+
+use gpui::BackgroundExecutor;
+
+use super::{
+    Engine, GameConfig, GameStatus, Input,
+    net::{Connection, Message, NetEvent, Role},
+};
+
+/// Ticks between sending a frame and simulating it, so the other player's
+/// frame has time to arrive before it is needed.
+const INPUT_DELAY: u64 = 3;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Won,
+    Lost,
+    Draw,
+}
+
+impl Outcome {
+    pub fn label(self) -> &'static str {
+        match self {
+            Outcome::Won => "You win!",
+            Outcome::Lost => "You lose",
+            Outcome::Draw => "Draw",
+        }
+    }
+}
+
+pub enum VersusStatus {
+    Connecting,
+    Playing,
+    Over(Outcome),
+    Disconnected(String),
+}
+
+/// Both snakes of a head-to-head match. Each instance simulates both boards
+/// from the same seed and trades inputs with the other one tick by tick; a
+/// tick only runs once both players' frames for it are in.
+struct Match {
+    local: Engine,
+    remote: Engine,
+    tick: u64,
+    /// The next tick this side sends a frame for.
+    next_frame: u64,
+    pending: Vec<Input>,
+    local_frames: HashMap<u64, Vec<Input>>,
+    remote_frames: HashMap<u64, Vec<Input>>,
+    stalled: bool,
+}
+
+impl Match {
+    fn new(config: &GameConfig, seed: u64) -> Self {
+        let mut local = Engine::new(config, seed);
+        let mut remote = Engine::new(config, seed);
+        local.start();
+        remote.start();
+        Self {
+            local,
+            remote,
+            tick: 0,
+            next_frame: INPUT_DELAY,
+            pending: Vec::new(),
+            local_frames: HashMap::new(),
+            remote_frames: HashMap::new(),
+            stalled: false,
+        }
+    }
+
+    fn frame(frames: &mut HashMap<u64, Vec<Input>>, tick: u64) -> Option<Vec<Input>> {
+        if tick < INPUT_DELAY {
+            Some(Vec::new())
+        } else {
+            frames.remove(&tick)
+        }
+    }
+
+    /// This side's next frame to send, once the simulation is within
+    /// `INPUT_DELAY` ticks of it.
+    fn next_outgoing(&mut self) -> Option<(u64, Vec<Input>)> {
+        if self.next_frame > self.tick + INPUT_DELAY {
+            return None;
+        }
+        let tick = self.next_frame;
+        let inputs = std::mem::take(&mut self.pending);
+        self.local_frames.insert(tick, inputs.clone());
+        self.next_frame += 1;
+        Some((tick, inputs))
+    }
+
+    /// Runs the next tick if the other player's frame for it is in. Returns
+    /// false, and marks the match stalled, if it is not.
+    fn advance(&mut self) -> bool {
+        let Some(remote) = Self::frame(&mut self.remote_frames, self.tick) else {
+            self.stalled = true;
+            return false;
+        };
+        let local = Self::frame(&mut self.local_frames, self.tick).unwrap_or_default();
+        self.stalled = false;
+        Self::apply(&mut self.local, &local);
+        Self::apply(&mut self.remote, &remote);
+        self.tick += 1;
+        true
+    }
+
+    fn apply(engine: &mut Engine, inputs: &[Input]) {
+        for input in inputs {
+            match *input {
+                Input::Turn(direction) => engine.queue_direction(direction),
+                Input::Activate(slot) => engine.activate(slot),
+                Input::Mutate(mutation) => engine.mutate(mutation),
+            }
+        }
+        engine.tick();
+    }
+
+    fn outcome(&self) -> Option<Outcome> {
+        let local_over = self.local.state() == GameStatus::GameOver;
+        let remote_over = self.remote.state() == GameStatus::GameOver;
+        match (local_over, remote_over) {
+            (false, false) => None,
+            (false, true) => Some(Outcome::Won),
+            (true, false) => Some(Outcome::Lost),
+            (true, true) => Some(match self.local.score().cmp(&self.remote.score()) {
+                std::cmp::Ordering::Greater => Outcome::Won,
+                std::cmp::Ordering::Less => Outcome::Lost,
+                std::cmp::Ordering::Equal => Outcome::Draw,
+            }),
+        }
+    }
+}
+
+pub struct Versus {
+    role: Role,
+    address: String,
+    connection: Connection,
+    status: VersusStatus,
+    game: Option<Match>,
+    /// Board the host picked, sent to the guest once it connects.
+    config: GameConfig,
+}
+
+impl Versus {
+    pub fn open(
+        role: Role,
+        address: String,
+        config: GameConfig,
+        executor: &BackgroundExecutor,
+    ) -> Self {
+        Self {
+            role,
+            connection: Connection::open(role, address.clone(), executor),
+            address,
+            status: VersusStatus::Connecting,
+            game: None,
+            config,
+        }
+    }
+
+    pub fn role(&self) -> Role {
+        self.role
+    }
+
+    pub fn address(&self) -> &str {
+        &self.address
+    }
+
+    pub fn status(&self) -> &VersusStatus {
+        &self.status
+    }
+
+    pub fn is_playing(&self) -> bool {
+        matches!(self.status, VersusStatus::Playing)
+    }
+
+    /// Whether the match is waiting on the other player's frame.
+    pub fn is_stalled(&self) -> bool {
+        self.game.as_ref().is_some_and(|game| game.stalled)
+    }
+
+    pub fn local(&self) -> Option<&Engine> {
+        self.game.as_ref().map(|game| &game.local)
+    }
+
+    pub fn remote(&self) -> Option<&Engine> {
+        self.game.as_ref().map(|game| &game.remote)
+    }
+
+    /// Both players tick at the faster snake's pace, so neither side keeps
+    /// waiting on the other.
+    pub fn tick_delay(&self) -> Option<Duration> {
+        self.game
+            .as_ref()
+            .map(|game| game.local.tick_delay().min(game.remote.tick_delay()))
+    }
+
+    /// Buffers a local input for the next frame sent.
+    pub fn queue(&mut self, input: Input) {
+        if !self.is_playing() {
+            return;
+        }
+        if let Some(game) = &mut self.game {
+            game.pending.push(input);
+        }
+    }
+
+    fn start(&mut self, config: &GameConfig, seed: u64) {
+        self.game = Some(Match::new(config, seed));
+        self.status = VersusStatus::Playing;
+    }
+
+    /// Handles everything the socket task has delivered since the last call.
+    fn poll(&mut self) {
+        while let Some(event) = self.connection.poll() {
+            match event {
+                NetEvent::Connected if self.role == Role::Host => {
+                    let seed = rand::random();
+                    let config = self.config.clone();
+                    self.connection.send(Message::Start {
+                        config: Box::new(config.clone()),
+                        seed,
+                    });
+                    self.start(&config, seed);
+                }
+                NetEvent::Connected => {}
+                NetEvent::Message(Message::Start { config, seed }) => match config.validate() {
+                    Ok(()) => self.start(&config, seed),
+                    Err(err) => {
+                        tracing::warn!("Rejected the host's board: {err}");
+                        self.status =
+                            VersusStatus::Disconnected(format!("Host sent a bad board: {err}"));
+                    }
+                },
+                NetEvent::Message(Message::Frame { tick, inputs }) => {
+                    if let Some(game) = &mut self.game {
+                        game.remote_frames.insert(tick, inputs);
+                    }
+                }
+                NetEvent::Disconnected(reason) => {
+                    if !matches!(self.status, VersusStatus::Over(_)) {
+                        self.status = VersusStatus::Disconnected(reason);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Sends this side's frame and runs the next tick once both frames for it
+    /// are in. Returns whether anything changed.
+    pub fn tick(&mut self) -> bool {
+        let was_stalled = self.is_stalled();
+        self.poll();
+        if !self.is_playing() {
+            return true;
+        }
+        let Some(game) = self.game.as_mut() else {
+            return true;
+        };
+        if let Some((tick, inputs)) = game.next_outgoing() {
+            self.connection.send(Message::Frame { tick, inputs });
+        }
+        if !game.advance() {
+            return !was_stalled;
+        }
+        if let Some(outcome) = game.outcome() {
+            self.status = VersusStatus::Over(outcome);
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{BoardLayout, Direction};
+
+    fn pair() -> (Match, Match) {
+        let config = GameConfig::new(BoardLayout::Open);
+        (Match::new(&config, 5), Match::new(&config, 5))
+    }
+
+    /// One round of the socket: each side sends its next frame to the other,
+    /// then both try to run a tick.
+    fn exchange(host: &mut Match, guest: &mut Match) -> (bool, bool) {
+        if let Some((tick, inputs)) = host.next_outgoing() {
+            guest.remote_frames.insert(tick, inputs);
+        }
+        if let Some((tick, inputs)) = guest.next_outgoing() {
+            host.remote_frames.insert(tick, inputs);
+        }
+        (host.advance(), guest.advance())
+    }
+
+    #[test]
+    fn both_sides_simulate_the_same_match() {
+        let (mut host, mut guest) = pair();
+        host.pending.push(Input::Turn(Direction::Up));
+        guest.pending.push(Input::Turn(Direction::Down));
+        for _ in 0..8 {
+            assert_eq!(exchange(&mut host, &mut guest), (true, true));
+        }
+        assert_eq!(host.tick, 8);
+        assert_eq!(host.local.direction(), Direction::Up);
+        assert_eq!(guest.local.direction(), Direction::Down);
+        assert!(host.local.snake().iter().eq(guest.remote.snake()));
+        assert!(host.remote.snake().iter().eq(guest.local.snake()));
+    }
+
+    #[test]
+    fn inputs_land_after_the_input_delay() {
+        let (mut host, mut guest) = pair();
+        host.pending.push(Input::Turn(Direction::Up));
+        for _ in 0..INPUT_DELAY {
+            exchange(&mut host, &mut guest);
+            assert_eq!(guest.remote.direction(), Direction::Right);
+        }
+        exchange(&mut host, &mut guest);
+        assert_eq!(guest.remote.direction(), Direction::Up);
+        assert_eq!(host.local.direction(), Direction::Up);
+    }
+
+    #[test]
+    fn a_tick_waits_for_the_other_frame() {
+        let (mut host, _) = pair();
+        for _ in 0..INPUT_DELAY {
+            host.next_outgoing();
+            assert!(host.advance());
+        }
+        host.next_outgoing();
+        assert!(!host.advance());
+        assert!(host.stalled);
+        assert_eq!(host.tick, INPUT_DELAY);
+        host.remote_frames.insert(INPUT_DELAY, Vec::new());
+        assert!(host.advance());
+        assert!(!host.stalled);
+    }
+
+    #[test]
+    fn the_frame_window_stops_at_the_input_delay() {
+        let (mut host, _) = pair();
+        let sent: Vec<u64> = std::iter::from_fn(|| host.next_outgoing())
+            .map(|(tick, _)| tick)
+            .collect();
+        assert_eq!(sent, vec![INPUT_DELAY]);
+    }
+}
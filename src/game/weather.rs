@@ -0,0 +1,56 @@
+//! file: weather.rs
+//! author: Jacob Xie
+//! date: 2026/10/16 14:42:10 Friday
+//! brief:
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use super::Direction;
+
+/// Wind pushes the snake one extra cell every this many ticks.
+pub const WIND_EVERY: u64 = 4;
+/// Cells further than this from the head are hidden while it rains.
+pub const RAIN_VISIBILITY: i32 = 5;
+const CALM_TICKS: std::ops::Range<u64> = 60..120;
+const EVENT_TICKS: std::ops::Range<u32> = 30..50;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Weather {
+    Wind(Direction),
+    Rain,
+}
+
+impl Weather {
+    pub fn banner(self) -> String {
+        match self {
+            Weather::Wind(direction) => format!("Wind blowing {direction:?}"),
+            Weather::Rain => "Rain - visibility reduced".into(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub struct WeatherEvent {
+    pub weather: Weather,
+    pub remaining_ticks: u32,
+}
+
+impl WeatherEvent {
+    pub fn roll(rng: &mut impl Rng) -> Self {
+        let weather = if rng.gen_bool(0.5) {
+            Weather::Rain
+        } else {
+            Weather::Wind(Direction::ALL[rng.gen_range(0..Direction::ALL.len())])
+        };
+        Self {
+            weather,
+            remaining_ticks: rng.gen_range(EVENT_TICKS),
+        }
+    }
+
+    /// Ticks of calm weather before the next event.
+    pub fn calm_ticks(rng: &mut impl Rng) -> u64 {
+        rng.gen_range(CALM_TICKS)
+    }
+}
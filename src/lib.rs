@@ -0,0 +1,12 @@
+//! file: lib.rs
+//! author: Jacob Xie
+//! date: 2026/10/16 17:42:37 Friday
+//! brief:
+
+mod app;
+mod game;
+
+pub use app::run;
+pub use game::{
+    BoardLayout, BotInput, GameConfig, Policy, Roster, SimulationResult, init_logging, simulate,
+};
@@ -3,9 +3,25 @@
 //! date: 2025/12/14 23:45:04 Sunday
 //! brief:
 
-mod app;
-mod game;
+use gpui_snake::{BotInput, Roster, init_logging, run};
 
 fn main() {
-    app::run();
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let verbose = args.iter().any(|arg| arg == "--verbose");
+    let spectator = args.iter().any(|arg| arg == "--spectator");
+    let player = args
+        .iter()
+        .position(|arg| arg == "--player")
+        .and_then(|index| args.get(index + 1));
+    // Moves come from stdin with --bot, or from a named pipe with --bot-pipe.
+    let bot = match args.iter().position(|arg| arg == "--bot-pipe") {
+        Some(index) => args.get(index + 1).map(|path| BotInput::Pipe(path.into())),
+        None => args
+            .iter()
+            .any(|arg| arg == "--bot")
+            .then_some(BotInput::Stdin),
+    };
+    init_logging(verbose);
+    Roster::launch(player.map(String::as_str));
+    run(spectator, bot);
 }